@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Progress of background work on any of the user's videos
+ */
+export type ProgressEvent = { "type": "UploadProcessing", video_id: string, filename: string, } | { "type": "UploadReady", video_id: string, } | { "type": "UploadFailed", video_id: string, filename: string, error: string, } | { "type": "TranscriptionProgress", video_id: string, job_id: string, status: "queued" | "running" | "cancelled" | "failed" | "done", chunks_completed: number, chunks_total: number, 
+/**
+ * 0-100
+ */
+percent: number, } | { "type": "TranscriptionFailed", video_id: string, job_id: string, error: string, };
@@ -4,4 +4,4 @@ import type { SessionState } from "./SessionState";
 /**
  * Messages sent from server to client
  */
-export type ServerMessage = { "type": "TestMessage", text: string, } | { "type": "StateSync", session: SessionState, } | { "type": "VideoMetadata", width: number | null, height: number | null, duration_seconds: number | null, };
+export type ServerMessage = { "type": "TestMessage", text: string, } | { "type": "StateSync", session: SessionState, } | { "type": "VideoMetadata", width: number | null, height: number | null, duration_seconds: number | null, } | { "type": "ChunkStarted", chunk_index: number, start: number, end: number, } | { "type": "BlockTextDelta", block_id: string, chunk_index: number, start: number, end: number, text: string, } | { "type": "ChunkCompleted", chunk_index: number, chunks_completed: number, chunks_total: number, };
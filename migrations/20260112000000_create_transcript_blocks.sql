@@ -0,0 +1,12 @@
+CREATE TABLE transcript_blocks (
+    id TEXT PRIMARY KEY NOT NULL,
+    video_id TEXT NOT NULL,
+    start_time REAL NOT NULL,
+    end_time REAL NOT NULL,
+    text TEXT NOT NULL,
+    ordering INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_transcript_blocks_video ON transcript_blocks(video_id, ordering);
@@ -0,0 +1,16 @@
+-- Parameters of the most recent silence analysis per video
+CREATE TABLE silence_analyses (
+    video_id TEXT PRIMARY KEY NOT NULL,
+    noise_db REAL NOT NULL,
+    min_duration REAL NOT NULL,
+    analyzed_at TEXT NOT NULL
+);
+
+-- Silent intervals detected by ffmpeg silencedetect
+CREATE TABLE silence_intervals (
+    video_id TEXT NOT NULL,
+    start_time REAL NOT NULL,
+    end_time REAL NOT NULL
+);
+
+CREATE INDEX idx_silence_intervals_video ON silence_intervals(video_id, start_time);
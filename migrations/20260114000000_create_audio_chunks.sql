@@ -0,0 +1,12 @@
+-- Audio chunks cut on silence boundaries for transcription
+CREATE TABLE audio_chunks (
+    video_id TEXT NOT NULL,
+    chunk_index INTEGER NOT NULL,
+    source_start REAL NOT NULL,
+    source_end REAL NOT NULL,
+    trimmed_start REAL NOT NULL,
+    trimmed_end REAL NOT NULL,
+    file_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (video_id, chunk_index)
+);
@@ -0,0 +1,25 @@
+-- Background transcription jobs, one row per run over a video
+CREATE TABLE transcription_jobs (
+    id TEXT PRIMARY KEY NOT NULL,
+    video_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    error TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_transcription_jobs_video ON transcription_jobs(video_id, created_at);
+CREATE INDEX idx_transcription_jobs_status ON transcription_jobs(status);
+-- At most one unfinished job per video, so parallel starts can't both run
+CREATE UNIQUE INDEX idx_transcription_jobs_active ON transcription_jobs(video_id)
+    WHERE status NOT IN ('done', 'failed', 'cancelled');
+
+-- Per-chunk progress of a job, so a restarted job resumes at the first unfinished chunk
+CREATE TABLE transcription_job_chunks (
+    job_id TEXT NOT NULL,
+    chunk_index INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    completed_at TEXT,
+    PRIMARY KEY (job_id, chunk_index)
+);
@@ -0,0 +1,2 @@
+-- Text a block had before it was last retranscribed
+ALTER TABLE transcript_blocks ADD COLUMN previous_text TEXT;
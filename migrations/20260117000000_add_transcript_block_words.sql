@@ -0,0 +1,2 @@
+-- Word-level timings and confidence of a block, as a JSON array of TranscriptWord
+ALTER TABLE transcript_blocks ADD COLUMN words TEXT NOT NULL DEFAULT '[]';
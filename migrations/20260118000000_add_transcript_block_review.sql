@@ -0,0 +1,5 @@
+-- Block confidence (mean of its scored words) and review state for the review queue
+ALTER TABLE transcript_blocks ADD COLUMN confidence REAL;
+ALTER TABLE transcript_blocks ADD COLUMN reviewed_at TEXT;
+
+CREATE INDEX idx_transcript_blocks_confidence ON transcript_blocks(video_id, confidence);
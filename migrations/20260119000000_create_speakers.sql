@@ -0,0 +1,26 @@
+-- Speakers of a video, either created by diarization or by hand
+CREATE TABLE speakers (
+    id TEXT PRIMARY KEY NOT NULL,
+    video_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    color TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_speakers_video ON speakers(video_id);
+
+-- Speaker turns from the latest diarization, used to pre-assign speakers to new blocks
+CREATE TABLE speaker_turns (
+    video_id TEXT NOT NULL,
+    speaker_id TEXT NOT NULL,
+    start_time REAL NOT NULL,
+    end_time REAL NOT NULL
+);
+
+CREATE INDEX idx_speaker_turns_video ON speaker_turns(video_id, start_time);
+
+ALTER TABLE transcript_blocks ADD COLUMN speaker_id TEXT;
+
+-- Mark videos whose speakers or block speakers were changed by hand, so re-running
+-- diarization can't replace that labelling
+ALTER TABLE videos ADD COLUMN speakers_edited BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- Spoken language of a video (e.g. "en", "hi"), set by the user or detected before transcription
+ALTER TABLE videos ADD COLUMN language TEXT;
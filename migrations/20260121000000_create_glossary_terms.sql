@@ -0,0 +1,13 @@
+-- A user's glossary of domain terms with their preferred spelling
+CREATE TABLE glossary_terms (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    term TEXT NOT NULL,
+    variants TEXT NOT NULL DEFAULT '[]',
+    fuzzy BOOLEAN NOT NULL DEFAULT 0, -- Also correct near-miss spellings, not just exact ones and listed variants
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX idx_glossary_terms_user_term ON glossary_terms(user_id, term COLLATE NOCASE);
@@ -0,0 +1,53 @@
+-- Give transcript blocks a stable integer key for the full-text index.
+-- The implicit rowid of a table with a TEXT primary key can change on VACUUM,
+-- which would point search hits at the wrong blocks.
+CREATE TABLE transcript_blocks_new (
+    search_id INTEGER PRIMARY KEY,
+    id TEXT NOT NULL UNIQUE,
+    video_id TEXT NOT NULL,
+    start_time REAL NOT NULL,
+    end_time REAL NOT NULL,
+    text TEXT NOT NULL,
+    ordering INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    previous_text TEXT,
+    words TEXT NOT NULL DEFAULT '[]',
+    confidence REAL,
+    reviewed_at TEXT,
+    speaker_id TEXT
+);
+
+INSERT INTO transcript_blocks_new (id, video_id, start_time, end_time, text, ordering, created_at, updated_at, previous_text, words, confidence, reviewed_at, speaker_id)
+SELECT id, video_id, start_time, end_time, text, ordering, created_at, updated_at, previous_text, words, confidence, reviewed_at, speaker_id
+FROM transcript_blocks;
+
+DROP TABLE transcript_blocks;
+ALTER TABLE transcript_blocks_new RENAME TO transcript_blocks;
+
+CREATE INDEX idx_transcript_blocks_video ON transcript_blocks(video_id, ordering);
+CREATE INDEX idx_transcript_blocks_confidence ON transcript_blocks(video_id, confidence);
+
+-- Full-text index over transcript block text, kept in sync by triggers.
+-- Diacritics are ignored so "Sariputta" finds "Sāriputta".
+CREATE VIRTUAL TABLE transcript_blocks_fts USING fts5(
+    text,
+    content = 'transcript_blocks',
+    content_rowid = 'search_id',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+INSERT INTO transcript_blocks_fts (transcript_blocks_fts) VALUES ('rebuild');
+
+CREATE TRIGGER transcript_blocks_fts_insert AFTER INSERT ON transcript_blocks BEGIN
+    INSERT INTO transcript_blocks_fts (rowid, text) VALUES (new.search_id, new.text);
+END;
+
+CREATE TRIGGER transcript_blocks_fts_delete AFTER DELETE ON transcript_blocks BEGIN
+    INSERT INTO transcript_blocks_fts (transcript_blocks_fts, rowid, text) VALUES ('delete', old.search_id, old.text);
+END;
+
+CREATE TRIGGER transcript_blocks_fts_update AFTER UPDATE OF text ON transcript_blocks BEGIN
+    INSERT INTO transcript_blocks_fts (transcript_blocks_fts, rowid, text) VALUES ('delete', old.search_id, old.text);
+    INSERT INTO transcript_blocks_fts (rowid, text) VALUES (new.search_id, new.text);
+END;
@@ -0,0 +1,33 @@
+-- Every change to a transcript block, with the block's state after the change.
+-- Revisions recorded by one operation share a batch_id and are undone together.
+CREATE TABLE transcript_revisions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    batch_id TEXT NOT NULL,
+    video_id TEXT NOT NULL,
+    block_id TEXT NOT NULL,
+    action TEXT NOT NULL,
+    -- The block no longer exists after this change; its last state is kept below
+    deleted BOOLEAN NOT NULL DEFAULT 0,
+    author_id TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    undone_at TEXT,
+    start_time REAL NOT NULL,
+    end_time REAL NOT NULL,
+    text TEXT NOT NULL,
+    previous_text TEXT,
+    ordering INTEGER NOT NULL,
+    words TEXT NOT NULL DEFAULT '[]',
+    confidence REAL,
+    reviewed_at TEXT,
+    speaker_id TEXT
+);
+
+CREATE INDEX idx_transcript_revisions_video ON transcript_revisions(video_id, id);
+CREATE INDEX idx_transcript_revisions_block ON transcript_revisions(block_id, id);
+
+-- Existing blocks start their history here
+INSERT INTO transcript_revisions (batch_id, video_id, block_id, action, author_id, created_at, start_time, end_time, text, previous_text, ordering, words, confidence, reviewed_at, speaker_id)
+SELECT 'initial-' || b.video_id, b.video_id, b.id, 'create', v.user_id, b.updated_at, b.start_time, b.end_time, b.text, b.previous_text, b.ordering, b.words, b.confidence, b.reviewed_at, b.speaker_id
+FROM transcript_blocks b
+JOIN videos v ON v.id = b.video_id
+ORDER BY b.video_id, b.ordering;
@@ -0,0 +1,18 @@
+-- Background renders of a video with its captions burned in, one row per run
+CREATE TABLE caption_burn_jobs (
+    id TEXT PRIMARY KEY NOT NULL,
+    video_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    style TEXT NOT NULL, -- JSON-encoded BurnInStyle
+    file_id TEXT, -- Rendered video in the file store, once done
+    error TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_caption_burn_jobs_video ON caption_burn_jobs(video_id, created_at);
+CREATE INDEX idx_caption_burn_jobs_status ON caption_burn_jobs(status);
+-- At most one unfinished burn-in per video, so parallel starts can't both render
+CREATE UNIQUE INDEX idx_caption_burn_jobs_active ON caption_burn_jobs(video_id)
+    WHERE status NOT IN ('done', 'failed', 'cancelled');
@@ -143,7 +143,7 @@ pub async fn register(
     req.validate()?;
 
     // Check if user exists
-    if let Some(_) = state.db.get_user_by_email(&req.email).await? {
+    if state.db.get_user_by_email(&req.email).await?.is_some() {
         return Err(AppError::BadRequest("Email already registered".to_string()));
     }
 
@@ -1,9 +1,13 @@
 use gatha_transcribe::{
     create_router,
-    messages::{ClientMessage, SessionState, PlaybackUpdate, PlaybackSpeedUpdate, VolumeUpdate, ServerMessage},
+    messages::{ClientMessage, SessionState, PlaybackUpdate, PlaybackSpeedUpdate, VolumeUpdate, ServerMessage, ProgressEvent},
     db::Database,
+    events::EventBus,
     filestore::LocalFileStore,
+    jobs::JobRegistry,
+    language::MockLanguageDetector,
     session_store::InMemorySessionStore,
+    transcriber::MockTranscriber,
     upload::AppState,
 };
 use ts_rs::TS;
@@ -18,6 +22,7 @@ async fn main() {
     PlaybackSpeedUpdate::export().expect("Failed to export PlaybackSpeedUpdate");
     VolumeUpdate::export().expect("Failed to export VolumeUpdate");
     ClientMessage::export().expect("Failed to export ClientMessage");
+    ProgressEvent::export().expect("Failed to export ProgressEvent");
     println!("✓ TypeScript types generated in frontend/src/types/");
 
     println!("\nGenerating OpenAPI spec...");
@@ -37,6 +42,11 @@ async fn main() {
         db,
         filestore: Arc::new(filestore),
         session_store: Arc::new(session_store),
+        transcriber: Arc::new(MockTranscriber::new()),
+        language_detector: Arc::new(MockLanguageDetector::default()),
+        diarizer: None,
+        jobs: JobRegistry::new(),
+        events: EventBus::new(),
     });
 
     let (_router, api) = create_router(state, None);
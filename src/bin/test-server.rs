@@ -1,6 +1,7 @@
 use gatha_transcribe::{
-    create_router, db::Database, filestore::LocalFileStore,
-    session_store::InMemorySessionStore, test_data, upload::AppState,
+    create_router, db::Database, diarizer::MockDiarizer, events::EventBus, filestore::LocalFileStore,
+    jobs::JobRegistry, language::MockLanguageDetector, session_store::InMemorySessionStore, test_data,
+    transcriber::MockTranscriber, upload::AppState,
 };
 use std::{path::PathBuf, sync::Arc};
 use tokio::net::TcpListener;
@@ -37,6 +38,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         db,
         filestore: Arc::new(filestore),
         session_store: Arc::new(session_store),
+        transcriber: Arc::new(MockTranscriber::new()),
+        language_detector: Arc::new(MockLanguageDetector::default()),
+        diarizer: Some(Arc::new(MockDiarizer::new())),
+        jobs: JobRegistry::new(),
+        events: EventBus::new(),
     });
 
     // Get port from env or use 3000
@@ -0,0 +1,448 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::stream;
+use std::{path::Path as FsPath, sync::Arc};
+use tokio::process::Command;
+use tracing::{error, info, warn};
+
+use crate::{
+    auth::AuthUser,
+    db::{BurnInStyle, CaptionBurnJob, CaptionPosition, JobStatus},
+    error::AppError,
+    export::{build_cues, content_disposition, render_srt, CaptionOptions},
+    filestore::FileStoreError,
+    upload::{copy_to_temp_file, get_owned_video, parse_range_header, AppState},
+};
+
+/// Bytes read from the filestore at a time when sending a whole rendered video
+const DOWNLOAD_CHUNK_SIZE: u64 = 1024 * 1024;
+
+const MIN_FONT_SIZE: u32 = 8;
+const MAX_FONT_SIZE: u32 = 96;
+const MAX_OUTLINE: f64 = 10.0;
+const MAX_FONT_NAME_LENGTH: usize = 64;
+
+/// Check a style before it is handed to ffmpeg
+///
+/// Font names are limited to letters, digits, spaces, `-` and `_`, as they end up
+/// inside the filter graph.
+pub fn validate_style(style: &BurnInStyle) -> Result<(), AppError> {
+    let font = style.font.trim();
+    if font.is_empty() || font.chars().count() > MAX_FONT_NAME_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "Font name must be 1 to {} characters",
+            MAX_FONT_NAME_LENGTH
+        )));
+    }
+    if !font.chars().all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_')) {
+        return Err(AppError::BadRequest(
+            "Font name may only contain letters, digits, spaces, '-' and '_'".to_string(),
+        ));
+    }
+    if !(MIN_FONT_SIZE..=MAX_FONT_SIZE).contains(&style.font_size) {
+        return Err(AppError::BadRequest(format!(
+            "Font size must be between {} and {}",
+            MIN_FONT_SIZE, MAX_FONT_SIZE
+        )));
+    }
+    if !(0.0..=MAX_OUTLINE).contains(&style.outline) {
+        return Err(AppError::BadRequest(format!(
+            "Outline must be between 0 and {}",
+            MAX_OUTLINE
+        )));
+    }
+    Ok(())
+}
+
+/// Helper: The ASS style overrides for the `subtitles` filter
+///
+/// Alignment uses numpad positions: 2 is bottom center, 5 middle, 8 top.
+fn force_style(style: &BurnInStyle) -> String {
+    let alignment = match style.position {
+        CaptionPosition::Bottom => 2,
+        CaptionPosition::Middle => 5,
+        CaptionPosition::Top => 8,
+    };
+    format!(
+        "FontName={},FontSize={},Alignment={},BorderStyle=1,Outline={},Shadow=0",
+        style.font.trim(),
+        style.font_size,
+        alignment,
+        style.outline
+    )
+}
+
+/// Helper: The video filter burning in a subtitle file from ffmpeg's working directory
+fn subtitles_filter(subtitle_file: &str, style: &BurnInStyle) -> String {
+    format!("subtitles=filename={}:force_style='{}'", subtitle_file, force_style(style))
+}
+
+/// Helper: File store ID of a video's rendered copy; each new render replaces the last
+fn burned_file_id(video_id: &str) -> String {
+    format!("captioned/{}.mp4", video_id)
+}
+
+/// Render `input` to `output` with the captions of `subtitles` burned in using ffmpeg
+async fn burn_captions(input: &FsPath, subtitles: &FsPath, output: &FsPath, style: &BurnInStyle) -> Result<(), AppError> {
+    // The filter graph has its own quoting rules, so the subtitle file is passed by
+    // its (random, alphanumeric) name from within its directory instead of a full path
+    let (Some(directory), Some(file_name)) = (subtitles.parent(), subtitles.file_name().and_then(|n| n.to_str())) else {
+        return Err(AppError::Internal("Invalid subtitle file path".to_string()));
+    };
+
+    // -c:v libx264: the frames change, so video is re-encoded; -c:a aac keeps any audio playable in MP4
+    // -movflags +faststart: moov atom first, so the download plays while loading
+    let result = Command::new("ffmpeg")
+        .current_dir(directory)
+        .args(["-hide_banner", "-nostats", "-i"])
+        .arg(input)
+        .args(["-vf", &subtitles_filter(file_name, style)])
+        .args(["-c:v", "libx264", "-preset", "veryfast", "-crf", "20", "-c:a", "aac", "-b:a", "160k"])
+        .args(["-movflags", "+faststart", "-f", "mp4", "-y"])
+        .arg(output)
+        .output()
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to execute ffmpeg caption burn-in");
+            AppError::Internal(format!("Caption burn-in failed: {}", e))
+        })?;
+
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        warn!(
+            exit_code = ?result.status.code(),
+            stderr = %stderr,
+            "ffmpeg caption burn-in failed"
+        );
+        return Err(AppError::Internal("Caption burn-in failed".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Helper: Create a named temp file that is removed when the path is dropped
+fn temp_path(suffix: &str) -> Result<tempfile::TempPath, AppError> {
+    Ok(tempfile::Builder::new()
+        .prefix("gatha_burn_")
+        .suffix(suffix)
+        .tempfile()
+        .map_err(|e| AppError::Internal(format!("Failed to create temp file: {}", e)))?
+        .into_temp_path())
+}
+
+/// Render a video with its current transcript burned in and store the result
+async fn run_job(state: &AppState, job_id: &str) -> Result<(), AppError> {
+    let job = state
+        .db
+        .get_caption_burn_job(job_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Caption burn-in job {} not found", job_id)))?;
+
+    if job.status.is_finished() {
+        warn!(job_id = %job_id, status = ?job.status, "Skipping finished caption burn-in job");
+        return Ok(());
+    }
+
+    state
+        .db
+        .update_caption_burn_job_status(job_id, JobStatus::Running, None, None)
+        .await?;
+
+    let video = state
+        .db
+        .get_video(&job.video_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Video not found".to_string()))?;
+
+    // Captions are laid out as in the SRT export, from the transcript as it is now
+    let blocks = state.db.list_transcript_blocks(&video.id).await?;
+    let cues = build_cues(&blocks, &CaptionOptions::default());
+    if cues.is_empty() {
+        return Err(AppError::Internal("Transcript has no captions to burn in".to_string()));
+    }
+
+    let subtitles = temp_path(".srt")?;
+    tokio::fs::write(&subtitles, render_srt(&cues))
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to write subtitle file: {}", e)))?;
+
+    let input = copy_to_temp_file(&state.filestore, &video.file_path).await?;
+    let output = temp_path(".mp4")?;
+
+    info!(job_id = %job_id, video_id = %video.id, cues = cues.len(), "Burning in captions");
+    burn_captions(&input, &subtitles, &output, &job.style).await?;
+
+    let rendered = tokio::fs::File::open(&output)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read rendered video: {}", e)))?;
+    let file_id = burned_file_id(&video.id);
+    state.filestore.save_file(&file_id, Box::new(rendered)).await?;
+
+    state
+        .db
+        .update_caption_burn_job_status(job_id, JobStatus::Done, Some(&file_id), None)
+        .await?;
+
+    info!(job_id = %job_id, video_id = %video.id, file_id = %file_id, "Caption burn-in job complete");
+
+    Ok(())
+}
+
+/// Run a caption burn-in job in a background task, recording failures on the job
+pub fn spawn_burn_job(state: Arc<AppState>, job_id: String) {
+    tokio::spawn(async move {
+        if let Err(e) = run_job(&state, &job_id).await {
+            error!(job_id = %job_id, error = %e, "Caption burn-in job failed");
+            // Only the user-facing message is kept, as it is returned to the job's owner
+            if let Err(e) = state
+                .db
+                .update_caption_burn_job_status(&job_id, JobStatus::Failed, None, Some(&e.user_message()))
+                .await
+            {
+                error!(job_id = %job_id, error = %e, "Failed to mark caption burn-in job as failed");
+            }
+        }
+    });
+}
+
+/// Restart every caption burn-in job that was queued or running when the server stopped
+///
+/// Renders start over from the beginning. Returns the number of restarted jobs.
+pub async fn resume_unfinished_burn_jobs(state: Arc<AppState>) -> Result<usize, AppError> {
+    let jobs = state.db.list_unfinished_caption_burn_jobs().await?;
+
+    for job in &jobs {
+        info!(job_id = %job.id, video_id = %job.video_id, "Restarting caption burn-in job");
+        spawn_burn_job(state.clone(), job.id.clone());
+    }
+
+    Ok(jobs.len())
+}
+
+/// Helper: Get the latest caption burn-in job of a video, if any
+async fn latest_job(state: &AppState, video_id: &str) -> Result<CaptionBurnJob, AppError> {
+    state
+        .db
+        .get_latest_caption_burn_job(video_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No caption burn-in job for this video".to_string()))
+}
+
+/// Start rendering a copy of a video with its captions burned in
+///
+/// Captions come from the transcript at the time the render starts. Omitted style
+/// fields take their defaults, so `{}` renders white sans-serif captions at the bottom.
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/burn-in",
+    params(("id" = String, Path, description = "Video ID")),
+    request_body = BurnInStyle,
+    responses(
+        (status = 202, description = "Caption burn-in job queued", body = CaptionBurnJob),
+        (status = 400, description = "Invalid caption style"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 409, description = "The transcript is empty or a burn-in job is already queued or running"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "videos"
+)]
+pub async fn start_burn_in(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+    Json(style): Json<BurnInStyle>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+    validate_style(&style)?;
+
+    if state.db.list_transcript_blocks(&video_id).await?.is_empty() {
+        return Err(AppError::Conflict("Video has no transcript to burn in".to_string()));
+    }
+
+    let job = CaptionBurnJob::new(video_id.clone(), auth_user.user_id.clone(), style);
+    if !state.db.insert_caption_burn_job(&job).await? {
+        return Err(AppError::Conflict(
+            "A caption burn-in job is already in progress for this video".to_string(),
+        ));
+    }
+
+    info!(job_id = %job.id, video_id = %video_id, style = ?job.style.0, "Queued caption burn-in job");
+
+    spawn_burn_job(state.clone(), job.id.clone());
+
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// Get the state of a video's latest caption burn-in job
+#[utoipa::path(
+    get,
+    path = "/api/videos/{id}/burn-in",
+    params(("id" = String, Path, description = "Video ID")),
+    responses(
+        (status = 200, description = "Latest caption burn-in job", body = CaptionBurnJob),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found or captions never burned in"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "videos"
+)]
+pub async fn get_burn_in(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let job = latest_job(&state, &video_id).await?;
+
+    Ok((StatusCode::OK, Json(job)))
+}
+
+/// Download the video rendered by the latest caption burn-in job
+///
+/// Supports Range requests like video streaming; a whole-file download is sent
+/// in chunks rather than read into memory at once.
+#[utoipa::path(
+    get,
+    path = "/api/videos/{id}/burn-in/download",
+    params(("id" = String, Path, description = "Video ID")),
+    responses(
+        (status = 200, description = "Video with burned-in captions", content_type = "video/mp4"),
+        (status = 206, description = "Partial content (range request)", content_type = "video/mp4"),
+        (status = 400, description = "Range start exceeds the file size"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found or captions never burned in"),
+        (status = 409, description = "The latest burn-in job hasn't finished successfully"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "videos"
+)]
+pub async fn download_burn_in(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let video = get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let job = latest_job(&state, &video_id).await?;
+    let file_id = match (job.status, job.file_id) {
+        (JobStatus::Done, Some(file_id)) => file_id,
+        _ => {
+            return Err(AppError::Conflict(format!(
+                "Caption burn-in job {} hasn't finished successfully",
+                job.id
+            )));
+        }
+    };
+
+    let file_size = state.filestore.get_file_size(&file_id).await?;
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|h| h.to_str().ok())
+        .filter(|_| file_size > 0)
+        .and_then(|range| parse_range_header(range, file_size));
+
+    let response = Response::builder()
+        .header(header::CONTENT_TYPE, "video/mp4")
+        .header(
+            header::CONTENT_DISPOSITION,
+            content_disposition(&video.original_filename, "captioned.mp4"),
+        )
+        .header(header::ACCEPT_RANGES, "bytes");
+
+    if let Some((start, end)) = range {
+        if start >= file_size {
+            return Err(AppError::BadRequest(format!(
+                "Range start {} exceeds file size {}",
+                start, file_size
+            )));
+        }
+
+        let end = end.min(file_size - 1);
+        let slice = state.filestore.get_file_range(&file_id, start, end).await?;
+
+        info!(video_id = %video_id, job_id = %job.id, start, end, "Downloaded part of captioned video");
+
+        return Ok(response
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(header::CONTENT_LENGTH, end - start + 1)
+            .header(
+                header::CONTENT_RANGE,
+                format!("bytes {}-{}/{}", start, end, file_size),
+            )
+            .body(Body::from(slice))
+            .unwrap());
+    }
+
+    info!(video_id = %video_id, job_id = %job.id, bytes = file_size, "Downloaded captioned video");
+
+    let filestore = state.filestore.clone();
+    let chunks = stream::try_unfold(0u64, move |start| {
+        let filestore = filestore.clone();
+        let file_id = file_id.clone();
+        async move {
+            if start >= file_size {
+                return Ok(None);
+            }
+            let end = (start + DOWNLOAD_CHUNK_SIZE).min(file_size) - 1;
+            let chunk = filestore.get_file_range(&file_id, start, end).await?;
+            Ok::<_, FileStoreError>(Some((chunk, end + 1)))
+        }
+    });
+
+    Ok(response
+        .status(StatusCode::OK)
+        .header(header::CONTENT_LENGTH, file_size)
+        .body(Body::from_stream(chunks))
+        .unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subtitles_filter() {
+        let style = BurnInStyle::default();
+        assert_eq!(
+            subtitles_filter("gatha_burn_x1.srt", &style),
+            "subtitles=filename=gatha_burn_x1.srt:force_style='FontName=Sans,FontSize=18,Alignment=2,BorderStyle=1,Outline=1.5,Shadow=0'"
+        );
+
+        let style = BurnInStyle {
+            font: " Noto Sans ".to_string(),
+            font_size: 24,
+            position: CaptionPosition::Top,
+            outline: 0.0,
+        };
+        assert_eq!(
+            force_style(&style),
+            "FontName=Noto Sans,FontSize=24,Alignment=8,BorderStyle=1,Outline=0,Shadow=0"
+        );
+    }
+
+    #[test]
+    fn test_validate_style() {
+        assert!(validate_style(&BurnInStyle::default()).is_ok());
+
+        let with = |f: fn(&mut BurnInStyle)| {
+            let mut style = BurnInStyle::default();
+            f(&mut style);
+            validate_style(&style)
+        };
+        assert!(with(|s| s.font = "DejaVu Sans-Bold_2".to_string()).is_ok());
+        assert!(with(|s| s.font = "Sans':original_size=1x1".to_string()).is_err());
+        assert!(with(|s| s.font = "  ".to_string()).is_err());
+        assert!(with(|s| s.font_size = 4).is_err());
+        assert!(with(|s| s.font_size = 200).is_err());
+        assert!(with(|s| s.outline = -1.0).is_err());
+        assert!(with(|s| s.outline = f64::NAN).is_err());
+    }
+}
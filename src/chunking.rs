@@ -0,0 +1,320 @@
+use chrono::Utc;
+use std::path::Path;
+use tokio::process::Command;
+use tracing::{error, info, warn};
+
+use crate::{
+    db::{AudioChunk, SilenceAnalysis, SilenceInterval, Video},
+    error::AppError,
+    silence::{detect_silences, SilenceParams},
+    upload::{copy_to_temp_file, extract_metadata_from_file, AppState},
+};
+
+pub const MIN_CHUNK_SECONDS: f64 = 30.0;
+pub const MAX_CHUNK_SECONDS: f64 = 60.0;
+
+/// Sample rate expected by speech-to-text backends
+const CHUNK_SAMPLE_RATE: &str = "16000";
+
+/// A chunk boundary decided by the planner, before any audio is extracted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlannedChunk {
+    pub index: i64,
+    pub source_start: f64,
+    pub source_end: f64,
+    pub trimmed_start: f64,
+    pub trimmed_end: f64,
+}
+
+fn midpoint(silence: &SilenceInterval) -> f64 {
+    (silence.start_time + silence.end_time) / 2.0
+}
+
+fn length(silence: &SilenceInterval) -> f64 {
+    silence.end_time - silence.start_time
+}
+
+/// Plan chunks of `min_seconds..max_seconds` that split only in the middle of silences
+///
+/// Within the allowed window the longest silence wins, as it is the most likely
+/// sentence break. If the window contains no silence the chunk grows until the
+/// next one, so speech is never cut mid-word. Leading and trailing silence of each
+/// chunk is trimmed, and chunks that are silent throughout are dropped.
+pub fn plan_chunks(
+    duration: f64,
+    silences: &[SilenceInterval],
+    min_seconds: f64,
+    max_seconds: f64,
+) -> Vec<PlannedChunk> {
+    let mut silences = silences.to_vec();
+    silences.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+    let mut boundaries = vec![0.0];
+    let mut cursor = 0.0;
+
+    while duration - cursor > max_seconds {
+        let in_window = silences
+            .iter()
+            .filter(|s| {
+                let mid = midpoint(s);
+                mid >= cursor + min_seconds && mid <= cursor + max_seconds
+            })
+            .max_by(|a, b| {
+                length(a)
+                    .total_cmp(&length(b))
+                    .then(midpoint(a).total_cmp(&midpoint(b)))
+            })
+            .map(midpoint);
+
+        let split = in_window.or_else(|| {
+            silences
+                .iter()
+                .map(midpoint)
+                .find(|&mid| mid > cursor + max_seconds && mid < duration)
+        });
+
+        match split {
+            Some(split) => {
+                boundaries.push(split);
+                cursor = split;
+            }
+            // No silence left to split on: the rest becomes one chunk
+            None => break,
+        }
+    }
+    boundaries.push(duration);
+
+    let mut chunks = Vec::new();
+    for window in boundaries.windows(2) {
+        let (source_start, source_end) = (window[0], window[1]);
+
+        let trimmed_start = silences
+            .iter()
+            .find(|s| s.start_time <= source_start && s.end_time > source_start)
+            .map_or(source_start, |s| s.end_time.min(source_end));
+        let trimmed_end = silences
+            .iter()
+            .find(|s| s.start_time < source_end && s.end_time >= source_end)
+            .map_or(source_end, |s| s.start_time.max(trimmed_start));
+
+        if trimmed_end <= trimmed_start {
+            continue;
+        }
+
+        chunks.push(PlannedChunk {
+            index: chunks.len() as i64,
+            source_start,
+            source_end,
+            trimmed_start,
+            trimmed_end,
+        });
+    }
+
+    chunks
+}
+
+/// FileStore ID for a chunk's extracted audio
+fn chunk_file_id(video_id: &str, index: i64) -> String {
+    format!("chunks/{}/{:04}.wav", video_id, index)
+}
+
+/// Extract `start..end` of a local media file as 16kHz mono WAV using ffmpeg
+pub async fn extract_audio_span(input_path: &Path, start: f64, end: f64) -> Result<Vec<u8>, AppError> {
+    let output_path = tempfile::Builder::new()
+        .prefix("gatha_chunk_")
+        .suffix(".wav")
+        .tempfile()
+        .map_err(|e| AppError::Internal(format!("Failed to create temp file: {}", e)))?
+        .into_temp_path();
+
+    // -ss/-t before -i: seek in the input instead of decoding from the beginning
+    // -vn: drop video, -ac 1 -ar 16000: mono 16kHz as speech models expect
+    let output = Command::new("ffmpeg")
+        .args(["-hide_banner", "-nostats", "-ss", &format!("{:.3}", start), "-t", &format!("{:.3}", end - start), "-i"])
+        .arg(input_path)
+        .args(["-vn", "-ac", "1", "-ar", CHUNK_SAMPLE_RATE, "-c:a", "pcm_s16le", "-f", "wav", "-y"])
+        .arg(&output_path)
+        .output()
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to execute ffmpeg audio extraction");
+            AppError::Internal(format!("Audio extraction failed: {}", e))
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        warn!(
+            start = start,
+            end = end,
+            exit_code = ?output.status.code(),
+            stderr = %stderr,
+            "ffmpeg audio extraction failed"
+        );
+        return Err(AppError::Internal("Audio extraction failed".to_string()));
+    }
+
+    tokio::fs::read(&output_path)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to read extracted audio: {}", e)))
+}
+
+/// Plan, extract and persist the audio chunks of a video
+///
+/// Silences and duration are analyzed first if the upload pipeline didn't record them.
+/// Previously extracted chunks of the video are replaced.
+pub async fn prepare_chunks(state: &AppState, video: &Video) -> Result<Vec<AudioChunk>, AppError> {
+    let temp_video = copy_to_temp_file(&state.filestore, &video.file_path).await?;
+    let temp_video_path = temp_video.to_string_lossy().to_string();
+
+    let duration = match video.duration_seconds {
+        Some(duration) => duration,
+        None => extract_metadata_from_file(&temp_video_path)
+            .await?
+            .2
+            .ok_or_else(|| AppError::Internal("Video duration is unknown".to_string()))?,
+    };
+
+    let silences = match state.db.get_silence_analysis(&video.id).await? {
+        Some(_) => state.db.list_silences(&video.id).await?,
+        None => {
+            let params = SilenceParams::default();
+            let silences = detect_silences(&temp_video_path, params, Some(duration)).await?;
+            let analysis = SilenceAnalysis {
+                video_id: video.id.clone(),
+                noise_db: params.noise_db,
+                min_duration: params.min_duration,
+                analyzed_at: Utc::now(),
+            };
+            state.db.replace_silences(&analysis, &silences).await?;
+            silences
+        }
+    };
+
+    let planned = plan_chunks(duration, &silences, MIN_CHUNK_SECONDS, MAX_CHUNK_SECONDS);
+
+    info!(
+        video_id = %video.id,
+        duration = duration,
+        silences = silences.len(),
+        chunks = planned.len(),
+        "Planned audio chunks"
+    );
+
+    // Remove audio of any previous chunking
+    for old_chunk in state.db.list_audio_chunks(&video.id).await? {
+        let _ = state.filestore.delete_file(&old_chunk.file_id).await;
+    }
+
+    let mut chunks = Vec::with_capacity(planned.len());
+    for plan in planned {
+        let audio = extract_audio_span(temp_video.as_ref(), plan.trimmed_start, plan.trimmed_end).await?;
+
+        let file_id = chunk_file_id(&video.id, plan.index);
+        let reader: Box<dyn tokio::io::AsyncRead + Unpin + Send> = Box::new(std::io::Cursor::new(audio));
+        state.filestore.save_file(&file_id, reader).await?;
+
+        chunks.push(AudioChunk {
+            video_id: video.id.clone(),
+            chunk_index: plan.index,
+            source_start: plan.source_start,
+            source_end: plan.source_end,
+            trimmed_start: plan.trimmed_start,
+            trimmed_end: plan.trimmed_end,
+            file_id,
+            created_at: Utc::now(),
+        });
+    }
+
+    state.db.replace_audio_chunks(&video.id, &chunks).await?;
+
+    info!(video_id = %video.id, chunks = chunks.len(), "Extracted audio chunks");
+
+    Ok(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn silence(start_time: f64, end_time: f64) -> SilenceInterval {
+        SilenceInterval { start_time, end_time }
+    }
+
+    #[test]
+    fn test_short_video_is_single_chunk() {
+        let chunks = plan_chunks(45.0, &[silence(20.0, 21.0)], MIN_CHUNK_SECONDS, MAX_CHUNK_SECONDS);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].source_start, 0.0);
+        assert_eq!(chunks[0].source_end, 45.0);
+    }
+
+    #[test]
+    fn test_splits_on_longest_silence_in_window() {
+        let silences = [
+            silence(10.0, 11.0), // Too early for a 30s minimum
+            silence(34.0, 35.0),
+            silence(49.0, 52.0), // Longest in the 30-60s window
+            silence(75.0, 76.0), // Inside the final chunk, no split needed
+        ];
+
+        let chunks = plan_chunks(100.0, &silences, MIN_CHUNK_SECONDS, MAX_CHUNK_SECONDS);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].source_end, 50.5);
+        assert_eq!(chunks[1].source_start, 50.5);
+        assert_eq!(chunks[1].source_end, 100.0);
+
+        // Silence around the split is trimmed from both neighbours
+        assert_eq!(chunks[0].trimmed_end, 49.0);
+        assert_eq!(chunks[1].trimmed_start, 52.0);
+
+        // Every chunk stays within bounds
+        for chunk in &chunks {
+            let length = chunk.source_end - chunk.source_start;
+            assert!((0.0..=MAX_CHUNK_SECONDS).contains(&length));
+        }
+    }
+
+    #[test]
+    fn test_never_splits_without_silence() {
+        // No silence in the 30-60s window: the chunk extends to the next silence
+        let chunks = plan_chunks(150.0, &[silence(80.0, 81.0)], MIN_CHUNK_SECONDS, MAX_CHUNK_SECONDS);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].source_end, 80.5);
+
+        // No silence at all: one chunk for the whole video
+        let chunks = plan_chunks(150.0, &[], MIN_CHUNK_SECONDS, MAX_CHUNK_SECONDS);
+        assert_eq!(chunks.len(), 1);
+    }
+
+    #[test]
+    fn test_trims_leading_silence_and_drops_silent_chunks() {
+        let silences = [silence(0.0, 3.0), silence(40.0, 41.0), silence(41.0, 100.0)];
+
+        let chunks = plan_chunks(100.0, &silences, MIN_CHUNK_SECONDS, MAX_CHUNK_SECONDS);
+
+        // The trailing span is silent throughout and is dropped
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].index, 0);
+        assert_eq!(chunks[0].trimmed_start, 3.0);
+        assert_eq!(chunks[0].trimmed_end, 40.0);
+    }
+
+    #[test]
+    fn test_chunk_time_maps_to_source_timeline() {
+        let chunk = AudioChunk {
+            video_id: "video".to_string(),
+            chunk_index: 1,
+            source_start: 50.5,
+            source_end: 120.0,
+            trimmed_start: 52.0,
+            trimmed_end: 118.0,
+            file_id: chunk_file_id("video", 1),
+            created_at: Utc::now(),
+        };
+
+        assert_eq!(chunk.to_source_time(0.0), 52.0);
+        assert_eq!(chunk.to_source_time(10.5), 62.5);
+        assert_eq!(chunk.file_id, "chunks/video/0001.wav");
+    }
+}
@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool};
+use sqlx::{types::Json, FromRow, SqliteConnection, SqlitePool};
+use std::collections::HashMap;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
@@ -15,6 +16,8 @@ pub struct Video {
     pub width: Option<i64>,
     pub height: Option<i64>,
     pub duration_seconds: Option<f64>,
+    /// Spoken language code passed to transcription as a hint, or None to detect it
+    pub language: Option<String>,
 }
 
 impl Video {
@@ -28,6 +31,7 @@ impl Video {
             width: None,
             height: None,
             duration_seconds: None,
+            language: None,
         }
     }
 }
@@ -54,6 +58,512 @@ impl User {
     }
 }
 
+/// A single word of a transcript block with its timing on the video timeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TranscriptWord {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub text: String,
+    /// Engine confidence from 0 to 1, or None if the source didn't provide one
+    pub confidence: Option<f64>,
+}
+
+/// A timed block of transcript text belonging to a video
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TranscriptBlock {
+    pub id: String,
+    pub video_id: String,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub text: String,
+    /// Text before the block was last retranscribed
+    pub previous_text: Option<String>,
+    pub ordering: i64,
+    /// Word-level timings, empty if the source had none
+    #[schema(value_type = Vec<TranscriptWord>)]
+    pub words: Json<Vec<TranscriptWord>>,
+    /// Confidence of the block from 0 to 1, or None if unknown
+    pub confidence: Option<f64>,
+    /// When a reviewer confirmed the block, or None if it hasn't been reviewed
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub speaker_id: Option<String>,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTime<Utc>,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TranscriptBlock {
+    pub fn new(video_id: String, start_time: f64, end_time: f64, text: String, ordering: i64) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            video_id,
+            start_time,
+            end_time,
+            text,
+            previous_text: None,
+            ordering,
+            words: Json(Vec::new()),
+            confidence: None,
+            reviewed_at: None,
+            speaker_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Replace the block's words and derive its confidence from them
+    pub fn set_words(&mut self, words: Vec<TranscriptWord>) {
+        self.confidence = words_confidence(&words);
+        self.words = Json(words);
+    }
+}
+
+/// Mean confidence of the scored words, or None if no word has a confidence
+pub fn words_confidence(words: &[TranscriptWord]) -> Option<f64> {
+    let scores: Vec<f64> = words.iter().filter_map(|w| w.confidence).collect();
+    if scores.is_empty() {
+        return None;
+    }
+    Some(scores.iter().sum::<f64>() / scores.len() as f64)
+}
+
+/// A word below a confidence threshold, with the block it belongs to
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LowConfidenceWord {
+    pub block_id: String,
+    /// Position of the word within its block
+    pub word_index: i64,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub text: String,
+    pub confidence: f64,
+}
+
+/// A transcript block matching a full-text search
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TranscriptSearchHit {
+    pub video_id: String,
+    pub video_filename: String,
+    pub block_id: String,
+    pub start_time: f64,
+    pub end_time: f64,
+    /// Excerpt of the block text around the match
+    pub snippet: String,
+}
+
+/// Kind of change recorded in a transcript revision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum RevisionAction {
+    Create,
+    Update,
+    Delete,
+    Restore,
+    Undo,
+}
+
+/// A transcript block's state after one change to it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TranscriptRevision {
+    /// Increases with every change, across all videos
+    pub id: i64,
+    /// Shared by the revisions of one operation, which are undone together
+    pub batch_id: String,
+    pub video_id: String,
+    pub block_id: String,
+    pub action: RevisionAction,
+    /// The block no longer exists after this change; the fields below are its last state
+    pub deleted: bool,
+    pub author_id: String,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTime<Utc>,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub undone_at: Option<DateTime<Utc>>,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub text: String,
+    pub previous_text: Option<String>,
+    pub ordering: i64,
+    #[schema(value_type = Vec<TranscriptWord>)]
+    pub words: Json<Vec<TranscriptWord>>,
+    pub confidence: Option<f64>,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub speaker_id: Option<String>,
+}
+
+/// Changes made by one operation, recorded as revisions sharing a batch ID
+struct RevisionBatch<'a> {
+    id: String,
+    author_id: &'a str,
+    created_at: DateTime<Utc>,
+}
+
+impl<'a> RevisionBatch<'a> {
+    fn new(author_id: &'a str) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            author_id,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Record the current state of a block; for deletions, call this before deleting it
+    async fn record(
+        &self,
+        conn: &mut SqliteConnection,
+        block_id: &str,
+        action: RevisionAction,
+        deleted: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO transcript_revisions (batch_id, video_id, block_id, action, deleted, author_id, created_at, start_time, end_time, text, previous_text, ordering, words, confidence, reviewed_at, speaker_id) SELECT ?, video_id, id, ?, ?, ?, ?, start_time, end_time, text, previous_text, ordering, words, confidence, reviewed_at, speaker_id FROM transcript_blocks WHERE id = ?",
+            self.id,
+            action,
+            deleted,
+            self.author_id,
+            self.created_at,
+            block_id
+        )
+        .execute(conn)
+        .await?;
+        Ok(())
+    }
+}
+
+/// Helper: Write a revision's state back to its block, re-creating the block if it was deleted
+///
+/// A block that still exists keeps its current position, as splits shift later blocks
+/// without recording revisions. A speaker that has since been deleted is left unassigned.
+async fn write_block_state(
+    conn: &mut SqliteConnection,
+    revision: &TranscriptRevision,
+    now: DateTime<Utc>,
+) -> Result<(), sqlx::Error> {
+    let mut speaker_id = revision.speaker_id.clone();
+    if let Some(id) = &speaker_id {
+        let exists = sqlx::query_scalar!(r#"SELECT EXISTS(SELECT 1 FROM speakers WHERE id = ?) as "exists!: bool""#, id)
+            .fetch_one(&mut *conn)
+            .await?;
+        if !exists {
+            speaker_id = None;
+        }
+    }
+
+    sqlx::query!(
+        "INSERT INTO transcript_blocks (id, video_id, start_time, end_time, text, previous_text, ordering, words, confidence, reviewed_at, speaker_id, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) ON CONFLICT(id) DO UPDATE SET start_time = excluded.start_time, end_time = excluded.end_time, text = excluded.text, previous_text = excluded.previous_text, words = excluded.words, confidence = excluded.confidence, reviewed_at = excluded.reviewed_at, speaker_id = excluded.speaker_id, updated_at = excluded.updated_at",
+        revision.block_id,
+        revision.video_id,
+        revision.start_time,
+        revision.end_time,
+        revision.text,
+        revision.previous_text,
+        revision.ordering,
+        revision.words,
+        revision.confidence,
+        revision.reviewed_at,
+        speaker_id,
+        now,
+        now
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Helper: Record whether a video's speakers or their blocks were changed by hand
+async fn set_speakers_edited(conn: &mut SqliteConnection, video_id: &str, edited: bool) -> Result<(), sqlx::Error> {
+    sqlx::query!("UPDATE videos SET speakers_edited = ? WHERE id = ?", edited, video_id)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// Helper: Insert a block or overwrite every field of an existing one
+async fn write_block(conn: &mut SqliteConnection, block: &TranscriptBlock) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO transcript_blocks (id, video_id, start_time, end_time, text, previous_text, ordering, words, confidence, reviewed_at, speaker_id, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) ON CONFLICT(id) DO UPDATE SET start_time = excluded.start_time, end_time = excluded.end_time, text = excluded.text, previous_text = excluded.previous_text, ordering = excluded.ordering, words = excluded.words, confidence = excluded.confidence, reviewed_at = excluded.reviewed_at, speaker_id = excluded.speaker_id, updated_at = excluded.updated_at",
+        block.id,
+        block.video_id,
+        block.start_time,
+        block.end_time,
+        block.text,
+        block.previous_text,
+        block.ordering,
+        block.words,
+        block.confidence,
+        block.reviewed_at,
+        block.speaker_id,
+        block.created_at,
+        block.updated_at
+    )
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// A named voice in a video's transcript
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Speaker {
+    pub id: String,
+    pub video_id: String,
+    pub name: String,
+    /// CSS color used to tell speakers apart in the editor (e.g. "#4e79a7")
+    pub color: String,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl Speaker {
+    pub fn new(video_id: String, name: String, color: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            video_id,
+            name,
+            color,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// A domain term in a user's glossary with its preferred spelling
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct GlossaryTerm {
+    pub id: String,
+    pub user_id: String,
+    /// Preferred spelling (e.g. "Sāriputta")
+    pub term: String,
+    /// Known misspellings that are always replaced with the term
+    #[schema(value_type = Vec<String>)]
+    pub variants: Json<Vec<String>>,
+    /// Also correct near misses of the term, not just exact spellings and variants
+    pub fuzzy: bool,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTime<Utc>,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl GlossaryTerm {
+    pub fn new(user_id: String, term: String, variants: Vec<String>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            user_id,
+            term,
+            variants: Json(variants),
+            fuzzy: false,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// A span of a video's audio attributed to a speaker by diarization
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct SpeakerSpan {
+    pub speaker_id: String,
+    pub start_time: f64,
+    pub end_time: f64,
+}
+
+/// The speaker whose turns overlap `start..end` the most, if any overlap
+pub fn speaker_for_span(spans: &[SpeakerSpan], start: f64, end: f64) -> Option<String> {
+    let mut overlaps: HashMap<&str, f64> = HashMap::new();
+    for span in spans {
+        let overlap = end.min(span.end_time) - start.max(span.start_time);
+        if overlap > 0.0 {
+            *overlaps.entry(span.speaker_id.as_str()).or_default() += overlap;
+        }
+    }
+
+    overlaps
+        .into_iter()
+        .max_by(|(a_id, a), (b_id, b)| a.total_cmp(b).then_with(|| b_id.cmp(a_id)))
+        .map(|(speaker_id, _)| speaker_id.to_string())
+}
+
+/// A silent interval of a video's audio track, in seconds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SilenceInterval {
+    pub start_time: f64,
+    pub end_time: f64,
+}
+
+/// Parameters and time of the most recent silence analysis of a video
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SilenceAnalysis {
+    pub video_id: String,
+    pub noise_db: f64,
+    pub min_duration: f64,
+    #[schema(value_type = String, format = DateTime)]
+    pub analyzed_at: DateTime<Utc>,
+}
+
+/// An extracted audio chunk of a video, with its position on the video timeline
+///
+/// `source_start..source_end` is the span the chunk covers; leading and trailing
+/// silence is trimmed so the stored audio covers `trimmed_start..trimmed_end`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AudioChunk {
+    pub video_id: String,
+    pub chunk_index: i64,
+    pub source_start: f64,
+    pub source_end: f64,
+    pub trimmed_start: f64,
+    pub trimmed_end: f64,
+    /// FileStore ID of the extracted audio
+    pub file_id: String,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl AudioChunk {
+    /// Map a timestamp relative to the chunk's audio onto the video timeline
+    pub fn to_source_time(&self, chunk_time: f64) -> f64 {
+        (self.trimmed_start + chunk_time).min(self.trimmed_end)
+    }
+}
+
+/// Lifecycle state of a transcription job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Cancelled,
+    Failed,
+    Done,
+}
+
+impl JobStatus {
+    /// Whether the job has stopped and won't be resumed
+    pub fn is_finished(self) -> bool {
+        matches!(self, JobStatus::Cancelled | JobStatus::Failed | JobStatus::Done)
+    }
+}
+
+/// A background transcription run over a video
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TranscriptionJob {
+    pub id: String,
+    pub video_id: String,
+    pub user_id: String,
+    pub status: JobStatus,
+    pub error: Option<String>,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTime<Utc>,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TranscriptionJob {
+    pub fn new(video_id: String, user_id: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            video_id,
+            user_id,
+            status: JobStatus::Queued,
+            error: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// Where burned-in captions are placed on the frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptionPosition {
+    #[default]
+    Bottom,
+    Middle,
+    Top,
+}
+
+/// Look of the captions burned into a video
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct BurnInStyle {
+    /// Font family name, as known to fontconfig
+    pub font: String,
+    /// Font size in points, relative to a 288 pixel high frame
+    pub font_size: u32,
+    pub position: CaptionPosition,
+    /// Width of the outline around the text in pixels, 0 for none
+    pub outline: f64,
+}
+
+impl Default for BurnInStyle {
+    fn default() -> Self {
+        Self {
+            font: "Sans".to_string(),
+            font_size: 18,
+            position: CaptionPosition::Bottom,
+            outline: 1.5,
+        }
+    }
+}
+
+/// A background render of a video with its captions burned in
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct CaptionBurnJob {
+    pub id: String,
+    pub video_id: String,
+    pub user_id: String,
+    pub status: JobStatus,
+    #[schema(value_type = BurnInStyle)]
+    pub style: Json<BurnInStyle>,
+    /// Rendered video in the file store, set once the job is done
+    #[serde(skip_serializing)]
+    pub file_id: Option<String>,
+    pub error: Option<String>,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTime<Utc>,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CaptionBurnJob {
+    pub fn new(video_id: String, user_id: String, style: BurnInStyle) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            video_id,
+            user_id,
+            status: JobStatus::Queued,
+            style: Json(style),
+            file_id: None,
+            error: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+/// Progress of a single audio chunk within a transcription job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum ChunkStatus {
+    Pending,
+    Done,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct JobChunk {
+    pub job_id: String,
+    pub chunk_index: i64,
+    pub status: ChunkStatus,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
 /// Database connection and operations
 pub struct Database {
     pool: SqlitePool,
@@ -80,7 +590,7 @@ impl Database {
     /// Insert a new video record
     pub async fn insert_video(&self, video: &Video) -> Result<(), sqlx::Error> {
         sqlx::query!(
-            "INSERT INTO videos (id, file_path, original_filename, user_id, uploaded_at, width, height, duration_seconds) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO videos (id, file_path, original_filename, user_id, uploaded_at, width, height, duration_seconds, language) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
             video.id,
             video.file_path,
             video.original_filename,
@@ -88,7 +598,8 @@ impl Database {
             video.uploaded_at,
             video.width,
             video.height,
-            video.duration_seconds
+            video.duration_seconds,
+            video.language
         )
         .execute(&self.pool)
         .await?;
@@ -99,7 +610,7 @@ impl Database {
     pub async fn get_video(&self, id: &str) -> Result<Option<Video>, sqlx::Error> {
         let video = sqlx::query_as!(
             Video,
-            r#"SELECT id, file_path, original_filename, user_id, uploaded_at as "uploaded_at: _", width, height, duration_seconds FROM videos WHERE id = ?"#,
+            r#"SELECT id, file_path, original_filename, user_id, uploaded_at as "uploaded_at: _", width, height, duration_seconds, language FROM videos WHERE id = ?"#,
             id
         )
         .fetch_optional(&self.pool)
@@ -111,7 +622,7 @@ impl Database {
     pub async fn list_videos(&self) -> Result<Vec<Video>, sqlx::Error> {
         let videos = sqlx::query_as!(
             Video,
-            r#"SELECT id, file_path, original_filename, user_id, uploaded_at as "uploaded_at: _", width, height, duration_seconds FROM videos ORDER BY uploaded_at DESC"#
+            r#"SELECT id, file_path, original_filename, user_id, uploaded_at as "uploaded_at: _", width, height, duration_seconds, language FROM videos ORDER BY uploaded_at DESC"#
         )
         .fetch_all(&self.pool)
         .await?;
@@ -122,7 +633,7 @@ impl Database {
     pub async fn get_videos_by_user(&self, user_id: &str) -> Result<Vec<Video>, sqlx::Error> {
         let videos = sqlx::query_as!(
             Video,
-            r#"SELECT id, file_path, original_filename, user_id, uploaded_at as "uploaded_at: _", width, height, duration_seconds FROM videos WHERE user_id = ? ORDER BY uploaded_at DESC"#,
+            r#"SELECT id, file_path, original_filename, user_id, uploaded_at as "uploaded_at: _", width, height, duration_seconds, language FROM videos WHERE user_id = ? ORDER BY uploaded_at DESC"#,
             user_id
         )
         .fetch_all(&self.pool)
@@ -130,6 +641,14 @@ impl Database {
         Ok(videos)
     }
 
+    /// Set or clear the language of a video
+    pub async fn update_video_language(&self, id: &str, language: Option<&str>) -> Result<(), sqlx::Error> {
+        sqlx::query!("UPDATE videos SET language = ? WHERE id = ?", language, id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
     /// Delete a video by ID
     pub async fn delete_video(&self, id: &str) -> Result<(), sqlx::Error> {
         sqlx::query!("DELETE FROM videos WHERE id = ?", id)
@@ -270,4 +789,1304 @@ impl Database {
         tx.commit().await?;
         Ok(())
     }
+
+    /// List all transcript blocks for a video in display order
+    pub async fn list_transcript_blocks(&self, video_id: &str) -> Result<Vec<TranscriptBlock>, sqlx::Error> {
+        let blocks = sqlx::query_as!(
+            TranscriptBlock,
+            r#"SELECT id, video_id, start_time, end_time, text, previous_text, ordering, words as "words: Json<Vec<TranscriptWord>>", confidence, reviewed_at as "reviewed_at: _", speaker_id, created_at as "created_at: _", updated_at as "updated_at: _" FROM transcript_blocks WHERE video_id = ? ORDER BY ordering, start_time"#,
+            video_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(blocks)
+    }
+
+    /// Get a transcript block by ID
+    pub async fn get_transcript_block(&self, id: &str) -> Result<Option<TranscriptBlock>, sqlx::Error> {
+        let block = sqlx::query_as!(
+            TranscriptBlock,
+            r#"SELECT id, video_id, start_time, end_time, text, previous_text, ordering, words as "words: Json<Vec<TranscriptWord>>", confidence, reviewed_at as "reviewed_at: _", speaker_id, created_at as "created_at: _", updated_at as "updated_at: _" FROM transcript_blocks WHERE id = ?"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(block)
+    }
+
+    /// Get the ordering value that places a new block after all existing blocks
+    pub async fn next_transcript_block_ordering(&self, video_id: &str) -> Result<i64, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT COALESCE(MAX(ordering) + 1, 0) as "next!: i64" FROM transcript_blocks WHERE video_id = ?"#,
+            video_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.next)
+    }
+
+    /// Insert a new transcript block, recording the change as made by `author_id`
+    pub async fn insert_transcript_block(&self, block: &TranscriptBlock, author_id: &str) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+
+        sqlx::query!(
+            "INSERT INTO transcript_blocks (id, video_id, start_time, end_time, text, ordering, words, confidence, speaker_id, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            block.id,
+            block.video_id,
+            block.start_time,
+            block.end_time,
+            block.text,
+            block.ordering,
+            block.words,
+            block.confidence,
+            block.speaker_id,
+            block.created_at,
+            block.updated_at
+        )
+        .execute(&mut *tx)
+        .await?;
+        batch.record(&mut tx, &block.id, RevisionAction::Create, false).await?;
+        if block.speaker_id.is_some() {
+            set_speakers_edited(&mut tx, &block.video_id, true).await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Update the timing, text, ordering, words, confidence and speaker of an existing transcript block
+    pub async fn update_transcript_block(&self, block: &TranscriptBlock, author_id: &str) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+
+        sqlx::query!(
+            "UPDATE transcript_blocks SET start_time = ?, end_time = ?, text = ?, ordering = ?, words = ?, confidence = ?, speaker_id = ?, updated_at = ? WHERE id = ?",
+            block.start_time,
+            block.end_time,
+            block.text,
+            block.ordering,
+            block.words,
+            block.confidence,
+            block.speaker_id,
+            block.updated_at,
+            block.id
+        )
+        .execute(&mut *tx)
+        .await?;
+        batch.record(&mut tx, &block.id, RevisionAction::Update, false).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Split a block in two: `first` keeps the block's ID and `second` is inserted right after it
+    ///
+    /// Blocks after the split move down one place. Both blocks are saved in a single transaction.
+    pub async fn split_transcript_block(
+        &self,
+        first: &TranscriptBlock,
+        second: &TranscriptBlock,
+        author_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+
+        // Only positions shift, so these moves aren't recorded as revisions
+        sqlx::query!(
+            "UPDATE transcript_blocks SET ordering = ordering + 1 WHERE video_id = ? AND ordering >= ? AND id != ?",
+            first.video_id,
+            second.ordering,
+            first.id
+        )
+        .execute(&mut *tx)
+        .await?;
+        write_block(&mut tx, first).await?;
+        batch.record(&mut tx, &first.id, RevisionAction::Update, false).await?;
+        write_block(&mut tx, second).await?;
+        batch.record(&mut tx, &second.id, RevisionAction::Create, false).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Merge blocks into `merged`, deleting the blocks with the `removed` IDs, in a single transaction
+    pub async fn merge_transcript_blocks(
+        &self,
+        merged: &TranscriptBlock,
+        removed: &[String],
+        author_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+
+        write_block(&mut tx, merged).await?;
+        batch.record(&mut tx, &merged.id, RevisionAction::Update, false).await?;
+        for id in removed {
+            batch.record(&mut tx, id, RevisionAction::Delete, true).await?;
+            sqlx::query!("DELETE FROM transcript_blocks WHERE id = ?", id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Replace all of a video's transcript blocks with `blocks` in a single transaction
+    ///
+    /// The old blocks stay in the revision history, so the whole replacement can be undone.
+    /// The chunk progress of the video's transcription jobs is dropped, so a cancelled or
+    /// failed job isn't resumed on top of the new blocks.
+    pub async fn replace_transcript_blocks(
+        &self,
+        video_id: &str,
+        blocks: &[TranscriptBlock],
+        author_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+
+        let old_ids = sqlx::query_scalar!("SELECT id FROM transcript_blocks WHERE video_id = ?", video_id)
+            .fetch_all(&mut *tx)
+            .await?;
+        for id in &old_ids {
+            batch.record(&mut tx, id, RevisionAction::Delete, true).await?;
+        }
+        sqlx::query!("DELETE FROM transcript_blocks WHERE video_id = ?", video_id)
+            .execute(&mut *tx)
+            .await?;
+        for block in blocks {
+            write_block(&mut tx, block).await?;
+            batch.record(&mut tx, &block.id, RevisionAction::Create, false).await?;
+        }
+        sqlx::query!(
+            "DELETE FROM transcription_job_chunks WHERE job_id IN (SELECT id FROM transcription_jobs WHERE video_id = ?)",
+            video_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Replace the text and words of several blocks in a single transaction, keeping each block's previous text
+    ///
+    /// The blocks' confidence is recomputed from the new words and they return to the review queue.
+    /// Each block must still have the `updated_at` it was read with; if any was changed or deleted
+    /// in the meantime, nothing is written and false is returned.
+    pub async fn replace_transcript_block_texts(
+        &self,
+        blocks: &[TranscriptBlock],
+        author_id: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+        let now = Utc::now();
+
+        for block in blocks {
+            let confidence = words_confidence(&block.words);
+            let result = sqlx::query!(
+                "UPDATE transcript_blocks SET previous_text = text, text = ?, words = ?, confidence = ?, reviewed_at = NULL, updated_at = ? WHERE id = ? AND updated_at = ?",
+                block.text,
+                block.words,
+                confidence,
+                now,
+                block.id,
+                block.updated_at
+            )
+            .execute(&mut *tx)
+            .await?;
+            if result.rows_affected() == 0 {
+                tx.rollback().await?;
+                return Ok(false);
+            }
+            batch.record(&mut tx, &block.id, RevisionAction::Update, false).await?;
+        }
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Rewrite the text and words of several blocks in a single transaction
+    ///
+    /// For edits that keep the spoken words (e.g. respelling them), so confidence, review marks and
+    /// `previous_text` stay. Each block must still have the `updated_at` it was read with; if any
+    /// was changed or deleted in the meantime, nothing is written and false is returned.
+    pub async fn rewrite_transcript_block_texts(
+        &self,
+        blocks: &[TranscriptBlock],
+        author_id: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+        let now = Utc::now();
+
+        for block in blocks {
+            let result = sqlx::query!(
+                "UPDATE transcript_blocks SET text = ?, words = ?, updated_at = ? WHERE id = ? AND updated_at = ?",
+                block.text,
+                block.words,
+                now,
+                block.id,
+                block.updated_at
+            )
+            .execute(&mut *tx)
+            .await?;
+            if result.rows_affected() == 0 {
+                tx.rollback().await?;
+                return Ok(false);
+            }
+            batch.record(&mut tx, &block.id, RevisionAction::Update, false).await?;
+        }
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// List the words of a video's transcript with a confidence below `max_confidence`, least confident first
+    ///
+    /// Words without a confidence score are never returned.
+    pub async fn list_low_confidence_words(
+        &self,
+        video_id: &str,
+        max_confidence: f64,
+    ) -> Result<Vec<LowConfidenceWord>, sqlx::Error> {
+        let words = sqlx::query_as!(
+            LowConfidenceWord,
+            r#"
+            SELECT
+                b.id as "block_id!",
+                CAST(w.key AS INTEGER) as "word_index!: i64",
+                json_extract(w.value, '$.start_time') as "start_time!: f64",
+                json_extract(w.value, '$.end_time') as "end_time!: f64",
+                json_extract(w.value, '$.text') as "text!: String",
+                json_extract(w.value, '$.confidence') as "confidence!: f64"
+            FROM transcript_blocks b, json_each(b.words) w
+            WHERE b.video_id = ? AND json_extract(w.value, '$.confidence') < ?
+            ORDER BY json_extract(w.value, '$.confidence'), b.ordering, w.key
+            "#,
+            video_id,
+            max_confidence
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(words)
+    }
+
+    /// List a video's scored blocks below `max_confidence`, least confident first
+    pub async fn list_review_queue(
+        &self,
+        video_id: &str,
+        max_confidence: f64,
+        include_reviewed: bool,
+        limit: i64,
+    ) -> Result<Vec<TranscriptBlock>, sqlx::Error> {
+        let blocks = sqlx::query_as!(
+            TranscriptBlock,
+            r#"SELECT id, video_id, start_time, end_time, text, previous_text, ordering, words as "words: Json<Vec<TranscriptWord>>", confidence, reviewed_at as "reviewed_at: _", speaker_id, created_at as "created_at: _", updated_at as "updated_at: _" FROM transcript_blocks WHERE video_id = ? AND confidence < ? AND (? OR reviewed_at IS NULL) ORDER BY confidence, ordering LIMIT ?"#,
+            video_id,
+            max_confidence,
+            include_reviewed,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(blocks)
+    }
+
+    /// Mark a transcript block as reviewed at the given time, or clear the mark with None
+    pub async fn set_transcript_block_reviewed(
+        &self,
+        id: &str,
+        reviewed_at: Option<DateTime<Utc>>,
+        updated_at: DateTime<Utc>,
+        author_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+
+        sqlx::query!(
+            "UPDATE transcript_blocks SET reviewed_at = ?, updated_at = ? WHERE id = ?",
+            reviewed_at,
+            updated_at,
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+        batch.record(&mut tx, id, RevisionAction::Update, false).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Delete a transcript block by ID, keeping its last state in the revision history
+    pub async fn delete_transcript_block(&self, id: &str, author_id: &str) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+
+        batch.record(&mut tx, id, RevisionAction::Delete, true).await?;
+        sqlx::query!("DELETE FROM transcript_blocks WHERE id = ?", id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// List the revisions of a video's transcript, newest first
+    ///
+    /// With `before`, only revisions with a smaller ID are returned, for paging.
+    pub async fn list_transcript_revisions(
+        &self,
+        video_id: &str,
+        before: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<TranscriptRevision>, sqlx::Error> {
+        let before = before.unwrap_or(i64::MAX);
+        let revisions = sqlx::query_as!(
+            TranscriptRevision,
+            r#"SELECT id as "id!", batch_id, video_id, block_id, action as "action: RevisionAction", deleted, author_id, created_at as "created_at: _", undone_at as "undone_at: _", start_time, end_time, text, previous_text, ordering, words as "words: Json<Vec<TranscriptWord>>", confidence, reviewed_at as "reviewed_at: _", speaker_id FROM transcript_revisions WHERE video_id = ? AND id < ? ORDER BY id DESC LIMIT ?"#,
+            video_id,
+            before,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(revisions)
+    }
+
+    /// List the revisions of one block, newest first (including those of a deleted block)
+    pub async fn list_block_revisions(&self, block_id: &str) -> Result<Vec<TranscriptRevision>, sqlx::Error> {
+        let revisions = sqlx::query_as!(
+            TranscriptRevision,
+            r#"SELECT id as "id!", batch_id, video_id, block_id, action as "action: RevisionAction", deleted, author_id, created_at as "created_at: _", undone_at as "undone_at: _", start_time, end_time, text, previous_text, ordering, words as "words: Json<Vec<TranscriptWord>>", confidence, reviewed_at as "reviewed_at: _", speaker_id FROM transcript_revisions WHERE block_id = ? ORDER BY id DESC"#,
+            block_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(revisions)
+    }
+
+    /// Get a transcript revision by ID
+    pub async fn get_transcript_revision(&self, id: i64) -> Result<Option<TranscriptRevision>, sqlx::Error> {
+        let revision = sqlx::query_as!(
+            TranscriptRevision,
+            r#"SELECT id as "id!", batch_id, video_id, block_id, action as "action: RevisionAction", deleted, author_id, created_at as "created_at: _", undone_at as "undone_at: _", start_time, end_time, text, previous_text, ordering, words as "words: Json<Vec<TranscriptWord>>", confidence, reviewed_at as "reviewed_at: _", speaker_id FROM transcript_revisions WHERE id = ?"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(revision)
+    }
+
+    /// Get the ID of the latest revision of a video's transcript, or None if it was never changed
+    pub async fn latest_transcript_revision_id(&self, video_id: &str) -> Result<Option<i64>, sqlx::Error> {
+        let id = sqlx::query_scalar!(
+            r#"SELECT MAX(id) as "id: i64" FROM transcript_revisions WHERE video_id = ?"#,
+            video_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(id)
+    }
+
+    /// Bring a block back to the state recorded in a revision in a single transaction
+    ///
+    /// A deleted block is re-created. The restore is itself recorded as a revision.
+    pub async fn restore_transcript_revision(
+        &self,
+        revision: &TranscriptRevision,
+        author_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+
+        write_block_state(&mut tx, revision, batch.created_at).await?;
+        batch.record(&mut tx, &revision.block_id, RevisionAction::Restore, false).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Undo the latest change to a video's transcript that hasn't been undone yet, in a single transaction
+    ///
+    /// Every block the change touched returns to its state before it, recorded as
+    /// `undo` revisions; undos themselves are skipped, so repeated calls step further
+    /// back. Returns the undo revisions, or None if there is nothing to undo.
+    pub async fn undo_transcript_change(
+        &self,
+        video_id: &str,
+        author_id: &str,
+    ) -> Result<Option<Vec<TranscriptRevision>>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+
+        let target = sqlx::query_scalar!(
+            "SELECT batch_id FROM transcript_revisions WHERE video_id = ? AND action != ? AND undone_at IS NULL ORDER BY id DESC LIMIT 1",
+            video_id,
+            RevisionAction::Undo
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(target) = target else {
+            return Ok(None);
+        };
+
+        let revisions = sqlx::query_as!(
+            TranscriptRevision,
+            r#"SELECT id as "id!", batch_id, video_id, block_id, action as "action: RevisionAction", deleted, author_id, created_at as "created_at: _", undone_at as "undone_at: _", start_time, end_time, text, previous_text, ordering, words as "words: Json<Vec<TranscriptWord>>", confidence, reviewed_at as "reviewed_at: _", speaker_id FROM transcript_revisions WHERE video_id = ? AND batch_id = ? ORDER BY id DESC"#,
+            video_id,
+            target
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for revision in &revisions {
+            let prior = sqlx::query_as!(
+                TranscriptRevision,
+                r#"SELECT id as "id!", batch_id, video_id, block_id, action as "action: RevisionAction", deleted, author_id, created_at as "created_at: _", undone_at as "undone_at: _", start_time, end_time, text, previous_text, ordering, words as "words: Json<Vec<TranscriptWord>>", confidence, reviewed_at as "reviewed_at: _", speaker_id FROM transcript_revisions WHERE block_id = ? AND id < ? ORDER BY id DESC LIMIT 1"#,
+                revision.block_id,
+                revision.id
+            )
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            match prior {
+                Some(prior) if !prior.deleted => {
+                    write_block_state(&mut tx, &prior, batch.created_at).await?;
+                    batch.record(&mut tx, &revision.block_id, RevisionAction::Undo, false).await?;
+                }
+                // The block didn't exist before the change
+                _ => {
+                    batch.record(&mut tx, &revision.block_id, RevisionAction::Undo, true).await?;
+                    sqlx::query!("DELETE FROM transcript_blocks WHERE id = ?", revision.block_id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+            }
+        }
+
+        sqlx::query!(
+            "UPDATE transcript_revisions SET undone_at = ? WHERE video_id = ? AND batch_id = ?",
+            batch.created_at,
+            video_id,
+            target
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let undone = sqlx::query_as!(
+            TranscriptRevision,
+            r#"SELECT id as "id!", batch_id, video_id, block_id, action as "action: RevisionAction", deleted, author_id, created_at as "created_at: _", undone_at as "undone_at: _", start_time, end_time, text, previous_text, ordering, words as "words: Json<Vec<TranscriptWord>>", confidence, reviewed_at as "reviewed_at: _", speaker_id FROM transcript_revisions WHERE batch_id = ? ORDER BY id"#,
+            batch.id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(undone))
+    }
+
+    /// Full-text search the transcripts of a user's videos, best matches first
+    ///
+    /// `query` uses FTS5 query syntax. Matches in the snippet are wrapped in
+    /// `highlight` start and end markers.
+    pub async fn search_transcripts(
+        &self,
+        user_id: &str,
+        query: &str,
+        highlight: (&str, &str),
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<TranscriptSearchHit>, sqlx::Error> {
+        let (open, close) = highlight;
+        let hits = sqlx::query_as!(
+            TranscriptSearchHit,
+            r#"
+            SELECT
+                b.video_id as "video_id!",
+                v.original_filename as "video_filename!",
+                b.id as "block_id!",
+                b.start_time as "start_time!: f64",
+                b.end_time as "end_time!: f64",
+                snippet(transcript_blocks_fts, 0, ?, ?, '…', 24) as "snippet!: String"
+            FROM transcript_blocks_fts
+            JOIN transcript_blocks b ON b.search_id = transcript_blocks_fts.rowid
+            JOIN videos v ON v.id = b.video_id
+            WHERE transcript_blocks_fts MATCH ? AND v.user_id = ?
+            ORDER BY bm25(transcript_blocks_fts), v.uploaded_at DESC, b.ordering
+            LIMIT ? OFFSET ?
+            "#,
+            open,
+            close,
+            query,
+            user_id,
+            limit,
+            offset
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(hits)
+    }
+
+    /// Rebuild the full-text index from the stored transcript blocks
+    ///
+    /// Triggers keep the index in sync, so this is only for repairing an index that has drifted.
+    pub async fn rebuild_transcript_search(&self) -> Result<(), sqlx::Error> {
+        sqlx::query!("INSERT INTO transcript_blocks_fts (transcript_blocks_fts) VALUES ('rebuild')")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Insert a speaker added by hand
+    pub async fn insert_speaker(&self, speaker: &Speaker) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            "INSERT INTO speakers (id, video_id, name, color, created_at) VALUES (?, ?, ?, ?, ?)",
+            speaker.id,
+            speaker.video_id,
+            speaker.name,
+            speaker.color,
+            speaker.created_at
+        )
+        .execute(&mut *tx)
+        .await?;
+        set_speakers_edited(&mut tx, &speaker.video_id, true).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Get a speaker by ID
+    pub async fn get_speaker(&self, id: &str) -> Result<Option<Speaker>, sqlx::Error> {
+        let speaker = sqlx::query_as!(
+            Speaker,
+            r#"SELECT id, video_id, name, color, created_at as "created_at: _" FROM speakers WHERE id = ?"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(speaker)
+    }
+
+    /// List the speakers of a video in creation order
+    pub async fn list_speakers(&self, video_id: &str) -> Result<Vec<Speaker>, sqlx::Error> {
+        let speakers = sqlx::query_as!(
+            Speaker,
+            r#"SELECT id, video_id, name, color, created_at as "created_at: _" FROM speakers WHERE video_id = ? ORDER BY created_at, name"#,
+            video_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(speakers)
+    }
+
+    /// Update the name and color of a speaker by hand
+    pub async fn update_speaker(&self, speaker: &Speaker) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            "UPDATE speakers SET name = ?, color = ? WHERE id = ?",
+            speaker.name,
+            speaker.color,
+            speaker.id
+        )
+        .execute(&mut *tx)
+        .await?;
+        set_speakers_edited(&mut tx, &speaker.video_id, true).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Delete a speaker of a video, unassigning its blocks, in a single transaction
+    ///
+    /// Deleting the video's last speaker clears its hand-edited mark, so it can be diarized again.
+    pub async fn delete_speaker(&self, video_id: &str, id: &str, author_id: &str) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+
+        let block_ids = sqlx::query_scalar!("SELECT id FROM transcript_blocks WHERE speaker_id = ?", id)
+            .fetch_all(&mut *tx)
+            .await?;
+        sqlx::query!("UPDATE transcript_blocks SET speaker_id = NULL WHERE speaker_id = ?", id)
+            .execute(&mut *tx)
+            .await?;
+        for block_id in &block_ids {
+            batch.record(&mut tx, block_id, RevisionAction::Update, false).await?;
+        }
+        sqlx::query!("DELETE FROM speaker_turns WHERE speaker_id = ?", id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query!("DELETE FROM speakers WHERE id = ?", id)
+            .execute(&mut *tx)
+            .await?;
+        let remaining = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM speakers WHERE video_id = ?) as "remaining!: bool""#,
+            video_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        set_speakers_edited(&mut tx, video_id, remaining).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Move the blocks and turns of `source_ids` to `target_id` and delete the sources, in a single transaction
+    pub async fn merge_speakers(
+        &self,
+        video_id: &str,
+        target_id: &str,
+        source_ids: &[String],
+        author_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+
+        for source_id in source_ids {
+            let block_ids = sqlx::query_scalar!("SELECT id FROM transcript_blocks WHERE speaker_id = ?", source_id)
+                .fetch_all(&mut *tx)
+                .await?;
+            sqlx::query!(
+                "UPDATE transcript_blocks SET speaker_id = ? WHERE speaker_id = ?",
+                target_id,
+                source_id
+            )
+            .execute(&mut *tx)
+            .await?;
+            for block_id in &block_ids {
+                batch.record(&mut tx, block_id, RevisionAction::Update, false).await?;
+            }
+            sqlx::query!(
+                "UPDATE speaker_turns SET speaker_id = ? WHERE speaker_id = ?",
+                target_id,
+                source_id
+            )
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query!("DELETE FROM speakers WHERE id = ?", source_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        set_speakers_edited(&mut tx, video_id, true).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Set the speaker of several blocks of a video in a single transaction
+    ///
+    /// Returns the number of blocks updated; IDs of other videos' blocks are ignored.
+    pub async fn assign_block_speakers(
+        &self,
+        video_id: &str,
+        assignments: &[(String, Option<String>)],
+        author_id: &str,
+    ) -> Result<u64, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+        let mut updated = 0;
+
+        for (block_id, speaker_id) in assignments {
+            let affected = sqlx::query!(
+                "UPDATE transcript_blocks SET speaker_id = ? WHERE id = ? AND video_id = ?",
+                speaker_id,
+                block_id,
+                video_id
+            )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+            if affected > 0 {
+                batch.record(&mut tx, block_id, RevisionAction::Update, false).await?;
+            }
+            updated += affected;
+        }
+        if updated > 0 {
+            set_speakers_edited(&mut tx, video_id, true).await?;
+        }
+
+        tx.commit().await?;
+        Ok(updated)
+    }
+
+    /// Replace a video's speakers and speaker turns with a new diarization, in a single transaction
+    ///
+    /// Every block is re-assigned to the speaker whose new turns overlap it most, as one
+    /// revision batch. Returns the number of blocks that were given a speaker, or None
+    /// without changing anything if the video's speakers were edited by hand.
+    pub async fn replace_diarization(
+        &self,
+        video_id: &str,
+        speakers: &[Speaker],
+        spans: &[SpeakerSpan],
+        author_id: &str,
+    ) -> Result<Option<u64>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+
+        let edited = sqlx::query_scalar!("SELECT speakers_edited FROM videos WHERE id = ?", video_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if edited == Some(true) {
+            return Ok(None);
+        }
+
+        let blocks = sqlx::query!(
+            r#"SELECT id as "id!", start_time, end_time, speaker_id FROM transcript_blocks WHERE video_id = ?"#,
+            video_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        let mut assigned = 0;
+        for block in blocks {
+            let speaker_id = speaker_for_span(spans, block.start_time, block.end_time);
+            assigned += u64::from(speaker_id.is_some());
+            if speaker_id == block.speaker_id {
+                continue;
+            }
+            sqlx::query!("UPDATE transcript_blocks SET speaker_id = ? WHERE id = ?", speaker_id, block.id)
+                .execute(&mut *tx)
+                .await?;
+            batch.record(&mut tx, &block.id, RevisionAction::Update, false).await?;
+        }
+
+        sqlx::query!("DELETE FROM speaker_turns WHERE video_id = ?", video_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query!("DELETE FROM speakers WHERE video_id = ?", video_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for speaker in speakers {
+            sqlx::query!(
+                "INSERT INTO speakers (id, video_id, name, color, created_at) VALUES (?, ?, ?, ?, ?)",
+                speaker.id,
+                speaker.video_id,
+                speaker.name,
+                speaker.color,
+                speaker.created_at
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for span in spans {
+            sqlx::query!(
+                "INSERT INTO speaker_turns (video_id, speaker_id, start_time, end_time) VALUES (?, ?, ?, ?)",
+                video_id,
+                span.speaker_id,
+                span.start_time,
+                span.end_time
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(Some(assigned))
+    }
+
+    /// List the diarized speaker turns of a video in timeline order
+    pub async fn list_speaker_spans(&self, video_id: &str) -> Result<Vec<SpeakerSpan>, sqlx::Error> {
+        let spans = sqlx::query_as!(
+            SpeakerSpan,
+            "SELECT speaker_id, start_time, end_time FROM speaker_turns WHERE video_id = ? ORDER BY start_time",
+            video_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(spans)
+    }
+
+    /// Insert a new glossary term
+    ///
+    /// Returns false without inserting if the user already has a term with this spelling.
+    pub async fn insert_glossary_term(&self, term: &GlossaryTerm) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "INSERT INTO glossary_terms (id, user_id, term, variants, fuzzy, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+            term.id,
+            term.user_id,
+            term.term,
+            term.variants,
+            term.fuzzy,
+            term.created_at,
+            term.updated_at
+        )
+        .execute(&self.pool)
+        .await;
+        match result {
+            Ok(_) => Ok(true),
+            Err(e) if e.as_database_error().is_some_and(|e| e.is_unique_violation()) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get a glossary term by ID
+    pub async fn get_glossary_term(&self, id: &str) -> Result<Option<GlossaryTerm>, sqlx::Error> {
+        let term = sqlx::query_as!(
+            GlossaryTerm,
+            r#"SELECT id, user_id, term, variants as "variants: Json<Vec<String>>", fuzzy, created_at as "created_at: _", updated_at as "updated_at: _" FROM glossary_terms WHERE id = ?"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(term)
+    }
+
+    /// Find a user's glossary term by its spelling, ignoring case
+    pub async fn find_glossary_term(&self, user_id: &str, term: &str) -> Result<Option<GlossaryTerm>, sqlx::Error> {
+        let term = sqlx::query_as!(
+            GlossaryTerm,
+            r#"SELECT id, user_id, term, variants as "variants: Json<Vec<String>>", fuzzy, created_at as "created_at: _", updated_at as "updated_at: _" FROM glossary_terms WHERE user_id = ? AND term = ? COLLATE NOCASE"#,
+            user_id,
+            term
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(term)
+    }
+
+    /// List a user's glossary terms alphabetically
+    pub async fn list_glossary_terms(&self, user_id: &str) -> Result<Vec<GlossaryTerm>, sqlx::Error> {
+        let terms = sqlx::query_as!(
+            GlossaryTerm,
+            r#"SELECT id, user_id, term, variants as "variants: Json<Vec<String>>", fuzzy, created_at as "created_at: _", updated_at as "updated_at: _" FROM glossary_terms WHERE user_id = ? ORDER BY term COLLATE NOCASE"#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(terms)
+    }
+
+    /// Update the spelling, variants and fuzzy matching of a glossary term
+    ///
+    /// Returns false without updating if the user already has another term with the new spelling.
+    pub async fn update_glossary_term(&self, term: &GlossaryTerm) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE glossary_terms SET term = ?, variants = ?, fuzzy = ?, updated_at = ? WHERE id = ?",
+            term.term,
+            term.variants,
+            term.fuzzy,
+            term.updated_at,
+            term.id
+        )
+        .execute(&self.pool)
+        .await;
+        match result {
+            Ok(_) => Ok(true),
+            Err(e) if e.as_database_error().is_some_and(|e| e.is_unique_violation()) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Delete a glossary term by ID
+    pub async fn delete_glossary_term(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!("DELETE FROM glossary_terms WHERE id = ?", id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Replace the stored silence analysis of a video in a single transaction
+    pub async fn replace_silences(
+        &self,
+        analysis: &SilenceAnalysis,
+        intervals: &[SilenceInterval],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!("DELETE FROM silence_intervals WHERE video_id = ?", analysis.video_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO silence_analyses (video_id, noise_db, min_duration, analyzed_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(video_id) DO UPDATE SET
+                noise_db = excluded.noise_db,
+                min_duration = excluded.min_duration,
+                analyzed_at = excluded.analyzed_at
+            "#,
+            analysis.video_id,
+            analysis.noise_db,
+            analysis.min_duration,
+            analysis.analyzed_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        for interval in intervals {
+            sqlx::query!(
+                "INSERT INTO silence_intervals (video_id, start_time, end_time) VALUES (?, ?, ?)",
+                analysis.video_id,
+                interval.start_time,
+                interval.end_time
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Get the most recent silence analysis of a video
+    pub async fn get_silence_analysis(&self, video_id: &str) -> Result<Option<SilenceAnalysis>, sqlx::Error> {
+        let analysis = sqlx::query_as!(
+            SilenceAnalysis,
+            r#"SELECT video_id, noise_db, min_duration, analyzed_at as "analyzed_at: _" FROM silence_analyses WHERE video_id = ?"#,
+            video_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(analysis)
+    }
+
+    /// List the silent intervals of a video in timeline order
+    pub async fn list_silences(&self, video_id: &str) -> Result<Vec<SilenceInterval>, sqlx::Error> {
+        let intervals = sqlx::query_as!(
+            SilenceInterval,
+            "SELECT start_time, end_time FROM silence_intervals WHERE video_id = ? ORDER BY start_time",
+            video_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(intervals)
+    }
+
+    /// Replace all audio chunks of a video in a single transaction
+    pub async fn replace_audio_chunks(&self, video_id: &str, chunks: &[AudioChunk]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!("DELETE FROM audio_chunks WHERE video_id = ?", video_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for chunk in chunks {
+            sqlx::query!(
+                "INSERT INTO audio_chunks (video_id, chunk_index, source_start, source_end, trimmed_start, trimmed_end, file_id, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                chunk.video_id,
+                chunk.chunk_index,
+                chunk.source_start,
+                chunk.source_end,
+                chunk.trimmed_start,
+                chunk.trimmed_end,
+                chunk.file_id,
+                chunk.created_at
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// List the audio chunks of a video in timeline order
+    pub async fn list_audio_chunks(&self, video_id: &str) -> Result<Vec<AudioChunk>, sqlx::Error> {
+        let chunks = sqlx::query_as!(
+            AudioChunk,
+            r#"SELECT video_id, chunk_index, source_start, source_end, trimmed_start, trimmed_end, file_id, created_at as "created_at: _" FROM audio_chunks WHERE video_id = ? ORDER BY chunk_index"#,
+            video_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(chunks)
+    }
+
+    /// Insert a new transcription job
+    ///
+    /// Returns false without inserting if the video already has an unfinished job.
+    pub async fn insert_transcription_job(&self, job: &TranscriptionJob) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "INSERT INTO transcription_jobs (id, video_id, user_id, status, error, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+            job.id,
+            job.video_id,
+            job.user_id,
+            job.status,
+            job.error,
+            job.created_at,
+            job.updated_at
+        )
+        .execute(&self.pool)
+        .await;
+        match result {
+            Ok(_) => Ok(true),
+            Err(e) if e.as_database_error().is_some_and(|e| e.is_unique_violation()) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get a transcription job by ID
+    pub async fn get_transcription_job(&self, id: &str) -> Result<Option<TranscriptionJob>, sqlx::Error> {
+        let job = sqlx::query_as!(
+            TranscriptionJob,
+            r#"SELECT id, video_id, user_id, status as "status: JobStatus", error, created_at as "created_at: _", updated_at as "updated_at: _" FROM transcription_jobs WHERE id = ?"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(job)
+    }
+
+    /// Get the most recently created transcription job of a video
+    pub async fn get_latest_transcription_job(&self, video_id: &str) -> Result<Option<TranscriptionJob>, sqlx::Error> {
+        let job = sqlx::query_as!(
+            TranscriptionJob,
+            r#"SELECT id, video_id, user_id, status as "status: JobStatus", error, created_at as "created_at: _", updated_at as "updated_at: _" FROM transcription_jobs WHERE video_id = ? ORDER BY created_at DESC LIMIT 1"#,
+            video_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(job)
+    }
+
+    /// List jobs that were queued or running (for resuming on startup)
+    pub async fn list_unfinished_transcription_jobs(&self) -> Result<Vec<TranscriptionJob>, sqlx::Error> {
+        let jobs = sqlx::query_as!(
+            TranscriptionJob,
+            r#"SELECT id, video_id, user_id, status as "status: JobStatus", error, created_at as "created_at: _", updated_at as "updated_at: _" FROM transcription_jobs WHERE status IN ('queued', 'running') ORDER BY created_at"#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(jobs)
+    }
+
+    /// Update the status (and failure message) of a transcription job that hasn't finished
+    ///
+    /// Returns false if the job had already been cancelled, failed or completed, in
+    /// which case it is left as it was.
+    pub async fn update_transcription_job_status(
+        &self,
+        id: &str,
+        status: JobStatus,
+        error: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let now = Utc::now();
+        let result = sqlx::query!(
+            "UPDATE transcription_jobs SET status = ?, error = ?, updated_at = ? WHERE id = ? AND status NOT IN ('cancelled', 'done', 'failed')",
+            status,
+            error,
+            now,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Insert a new caption burn-in job
+    ///
+    /// Returns false without inserting if the video already has an unfinished job.
+    pub async fn insert_caption_burn_job(&self, job: &CaptionBurnJob) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            "INSERT INTO caption_burn_jobs (id, video_id, user_id, status, style, file_id, error, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            job.id,
+            job.video_id,
+            job.user_id,
+            job.status,
+            job.style,
+            job.file_id,
+            job.error,
+            job.created_at,
+            job.updated_at
+        )
+        .execute(&self.pool)
+        .await;
+        match result {
+            Ok(_) => Ok(true),
+            Err(e) if e.as_database_error().is_some_and(|e| e.is_unique_violation()) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get a caption burn-in job by ID
+    pub async fn get_caption_burn_job(&self, id: &str) -> Result<Option<CaptionBurnJob>, sqlx::Error> {
+        let job = sqlx::query_as!(
+            CaptionBurnJob,
+            r#"SELECT id, video_id, user_id, status as "status: JobStatus", style as "style: Json<BurnInStyle>", file_id, error, created_at as "created_at: _", updated_at as "updated_at: _" FROM caption_burn_jobs WHERE id = ?"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(job)
+    }
+
+    /// Get the most recently created caption burn-in job of a video
+    pub async fn get_latest_caption_burn_job(&self, video_id: &str) -> Result<Option<CaptionBurnJob>, sqlx::Error> {
+        let job = sqlx::query_as!(
+            CaptionBurnJob,
+            r#"SELECT id, video_id, user_id, status as "status: JobStatus", style as "style: Json<BurnInStyle>", file_id, error, created_at as "created_at: _", updated_at as "updated_at: _" FROM caption_burn_jobs WHERE video_id = ? ORDER BY created_at DESC LIMIT 1"#,
+            video_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(job)
+    }
+
+    /// List caption burn-in jobs that were queued or running (for resuming on startup)
+    pub async fn list_unfinished_caption_burn_jobs(&self) -> Result<Vec<CaptionBurnJob>, sqlx::Error> {
+        let jobs = sqlx::query_as!(
+            CaptionBurnJob,
+            r#"SELECT id, video_id, user_id, status as "status: JobStatus", style as "style: Json<BurnInStyle>", file_id, error, created_at as "created_at: _", updated_at as "updated_at: _" FROM caption_burn_jobs WHERE status IN ('queued', 'running') ORDER BY created_at"#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(jobs)
+    }
+
+    /// Update the status, rendered file and failure message of a caption burn-in job
+    pub async fn update_caption_burn_job_status(
+        &self,
+        id: &str,
+        status: JobStatus,
+        file_id: Option<&str>,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query!(
+            "UPDATE caption_burn_jobs SET status = ?, file_id = ?, error = ?, updated_at = ? WHERE id = ?",
+            status,
+            file_id,
+            error,
+            now,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record the chunks a job has to transcribe
+    ///
+    /// Chunks listed in `done_indexes` are recorded as already done; the rest are pending.
+    pub async fn insert_job_chunks(
+        &self,
+        job_id: &str,
+        chunk_indexes: &[i64],
+        done_indexes: &[i64],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let now = Utc::now();
+
+        for chunk_index in chunk_indexes {
+            let (status, completed_at) = if done_indexes.contains(chunk_index) {
+                (ChunkStatus::Done, Some(now))
+            } else {
+                (ChunkStatus::Pending, None)
+            };
+            sqlx::query!(
+                "INSERT INTO transcription_job_chunks (job_id, chunk_index, status, completed_at) VALUES (?, ?, ?, ?)",
+                job_id,
+                chunk_index,
+                status,
+                completed_at
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// List the chunks of a job in order
+    pub async fn list_job_chunks(&self, job_id: &str) -> Result<Vec<JobChunk>, sqlx::Error> {
+        let chunks = sqlx::query_as!(
+            JobChunk,
+            r#"SELECT job_id, chunk_index, status as "status: ChunkStatus", completed_at as "completed_at: _" FROM transcription_job_chunks WHERE job_id = ? ORDER BY chunk_index"#,
+            job_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(chunks)
+    }
+
+    /// List the chunk indexes of a video that any of its jobs finished transcribing
+    pub async fn list_transcribed_chunk_indexes(&self, video_id: &str) -> Result<Vec<i64>, sqlx::Error> {
+        let indexes = sqlx::query_scalar!(
+            r#"SELECT DISTINCT c.chunk_index as "chunk_index!: i64" FROM transcription_job_chunks c JOIN transcription_jobs j ON j.id = c.job_id WHERE j.video_id = ? AND c.status = ? ORDER BY c.chunk_index"#,
+            video_id,
+            ChunkStatus::Done
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(indexes)
+    }
+
+    /// Count the chunks of a job as (total, completed)
+    pub async fn count_job_chunks(&self, job_id: &str) -> Result<(i64, i64), sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT COUNT(*) as "total!: i64", COUNT(completed_at) as "completed!: i64" FROM transcription_job_chunks WHERE job_id = ?"#,
+            job_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok((row.total, row.completed))
+    }
+
+    /// Store a chunk's transcript blocks and mark the chunk done in a single transaction
+    ///
+    /// Blocks are appended after the video's existing blocks; their ordering is assigned here.
+    pub async fn complete_job_chunk(
+        &self,
+        job_id: &str,
+        chunk_index: i64,
+        mut blocks: Vec<TranscriptBlock>,
+        author_id: &str,
+    ) -> Result<Vec<TranscriptBlock>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let batch = RevisionBatch::new(author_id);
+
+        if let Some(first) = blocks.first() {
+            let row = sqlx::query!(
+                r#"SELECT COALESCE(MAX(ordering) + 1, 0) as "next!: i64" FROM transcript_blocks WHERE video_id = ?"#,
+                first.video_id
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            for (offset, block) in blocks.iter_mut().enumerate() {
+                block.ordering = row.next + offset as i64;
+                sqlx::query!(
+                    "INSERT INTO transcript_blocks (id, video_id, start_time, end_time, text, ordering, words, confidence, speaker_id, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                    block.id,
+                    block.video_id,
+                    block.start_time,
+                    block.end_time,
+                    block.text,
+                    block.ordering,
+                    block.words,
+                    block.confidence,
+                    block.speaker_id,
+                    block.created_at,
+                    block.updated_at
+                )
+                .execute(&mut *tx)
+                .await?;
+                batch.record(&mut tx, &block.id, RevisionAction::Create, false).await?;
+            }
+        }
+
+        let now = Utc::now();
+        sqlx::query!(
+            "UPDATE transcription_job_chunks SET status = ?, completed_at = ? WHERE job_id = ? AND chunk_index = ?",
+            ChunkStatus::Done,
+            now,
+            job_id,
+            chunk_index
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(blocks)
+    }
 }
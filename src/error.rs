@@ -6,6 +6,8 @@ use axum::{
 use serde::Serialize;
 use thiserror::Error;
 
+use crate::{diarizer::DiarizerError, transcriber::TranscriberError};
+
 /// Application-wide error type
 #[derive(Error, Debug)]
 pub enum AppError {
@@ -19,6 +21,9 @@ pub enum AppError {
     #[error("Not found: {0}")]
     NotFound(String),
 
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     #[error("Validation failed: {0}")]
     Validation(#[from] validator::ValidationErrors),
 
@@ -32,6 +37,12 @@ pub enum AppError {
     #[error("Session store error: {0}")]
     SessionStore(#[from] crate::session_store::SessionStoreError),
 
+    #[error("Transcription error: {0}")]
+    Transcriber(#[from] TranscriberError),
+
+    #[error("Diarization error: {0}")]
+    Diarizer(#[from] DiarizerError),
+
     #[error("JWT error: {0}")]
     Jwt(#[from] jsonwebtoken::errors::Error),
 
@@ -49,10 +60,16 @@ impl AppError {
             AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
             AppError::Unauthorized(_) | AppError::Jwt(_) => StatusCode::UNAUTHORIZED,
             AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
             AppError::Validation(_) => StatusCode::BAD_REQUEST,
+            AppError::Transcriber(
+                TranscriberError::BinaryNotFound(_) | TranscriberError::ModelNotFound(_),
+            ) => StatusCode::SERVICE_UNAVAILABLE,
             AppError::Database(_)
             | AppError::FileStore(_)
             | AppError::SessionStore(_)
+            | AppError::Transcriber(_)
+            | AppError::Diarizer(_)
             | AppError::Bcrypt(_)
             | AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
@@ -64,14 +81,22 @@ impl AppError {
             // Client errors: show full details
             AppError::BadRequest(msg)
             | AppError::Unauthorized(msg)
-            | AppError::NotFound(msg) => msg.clone(),
+            | AppError::NotFound(msg)
+            | AppError::Conflict(msg) => msg.clone(),
 
             AppError::Validation(e) => format!("Validation error: {}", e),
 
+            // Missing engine is a deployment problem the user should hear about
+            AppError::Transcriber(
+                TranscriberError::BinaryNotFound(_) | TranscriberError::ModelNotFound(_),
+            ) => "Transcription engine is not installed on the server".to_string(),
+
             // Server errors: hide details for security
             AppError::Database(_)
             | AppError::FileStore(_)
             | AppError::SessionStore(_)
+            | AppError::Transcriber(_)
+            | AppError::Diarizer(_)
             | AppError::Jwt(_)
             | AppError::Bcrypt(_)
             | AppError::Internal(_) => "Internal server error".to_string(),
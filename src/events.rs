@@ -0,0 +1,199 @@
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use chrono::Utc;
+use futures_util::stream::{self, Stream, StreamExt};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{info, warn};
+
+use crate::{
+    auth::AuthUser,
+    messages::{ProgressEvent, ServerMessage},
+    upload::AppState,
+};
+
+/// Number of events a slow subscriber may fall behind before it starts missing them
+const EVENT_CAPACITY: usize = 1024;
+
+/// Number of recent progress events kept for replay on reconnect
+const PROGRESS_HISTORY: usize = 1024;
+
+/// A server message addressed to every client watching a video
+#[derive(Debug, Clone)]
+pub struct VideoEvent {
+    pub video_id: String,
+    pub message: ServerMessage,
+}
+
+/// A progress event addressed to one user, numbered for `Last-Event-ID` replay
+#[derive(Debug, Clone)]
+pub struct UserEvent {
+    pub id: u64,
+    pub user_id: String,
+    pub event: ProgressEvent,
+}
+
+struct ProgressLog {
+    next_id: u64,
+    recent: VecDeque<UserEvent>,
+}
+
+/// Fan-out of background task events to connected clients
+///
+/// Publishers don't know who is listening; each WebSocket subscribes and keeps
+/// the events of the video it is connected to, and each SSE feed keeps the
+/// progress events of its user.
+pub struct EventBus {
+    sender: broadcast::Sender<VideoEvent>,
+    progress: broadcast::Sender<UserEvent>,
+    log: Mutex<ProgressLog>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CAPACITY);
+        let (progress, _) = broadcast::channel(EVENT_CAPACITY);
+
+        // IDs start at the current time so IDs a client saw before a restart are
+        // older than every event of this process
+        let log = ProgressLog {
+            next_id: Utc::now().timestamp_millis() as u64,
+            recent: VecDeque::with_capacity(PROGRESS_HISTORY),
+        };
+
+        Self {
+            sender,
+            progress,
+            log: Mutex::new(log),
+        }
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send a message to every subscriber of the video. Dropped if nobody is listening.
+    pub fn publish(&self, video_id: &str, message: ServerMessage) {
+        let _ = self.sender.send(VideoEvent {
+            video_id: video_id.to_string(),
+            message,
+        });
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<VideoEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Send a progress event to the user's feeds and keep it for replay
+    pub fn publish_progress(&self, user_id: &str, event: ProgressEvent) {
+        let mut log = self.log.lock().unwrap();
+
+        let event = UserEvent {
+            id: log.next_id,
+            user_id: user_id.to_string(),
+            event,
+        };
+        log.next_id += 1;
+
+        if log.recent.len() == PROGRESS_HISTORY {
+            log.recent.pop_front();
+        }
+        log.recent.push_back(event.clone());
+
+        // Sent under the lock so a concurrent subscriber gets each event exactly once
+        let _ = self.progress.send(event);
+    }
+
+    /// Subscribe to progress events, returning the user's events after `last_event_id`
+    /// that are still in the history
+    pub fn subscribe_progress(
+        &self,
+        user_id: &str,
+        last_event_id: Option<u64>,
+    ) -> (Vec<UserEvent>, broadcast::Receiver<UserEvent>) {
+        let log = self.log.lock().unwrap();
+
+        let missed = match last_event_id {
+            Some(last_id) => log
+                .recent
+                .iter()
+                .filter(|e| e.id > last_id && e.user_id == user_id)
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+
+        (missed, self.progress.subscribe())
+    }
+}
+
+fn to_sse_event(event: &UserEvent) -> Result<Event, axum::Error> {
+    Event::default().id(event.id.to_string()).json_data(&event.event)
+}
+
+/// Stream progress of uploads and transcriptions across all of the user's videos
+///
+/// Each event's `data` is a JSON `ProgressEvent`. Reconnecting with `Last-Event-ID`
+/// replays the recent events the client missed.
+#[utoipa::path(
+    get,
+    path = "/api/events",
+    params(("Last-Event-ID" = Option<String>, Header, description = "ID of the last event received before reconnecting")),
+    responses(
+        (status = 200, description = "Server-sent event stream of ProgressEvent JSON", body = String, content_type = "text/event-stream"),
+        (status = 401, description = "Unauthorized - authentication required")
+    ),
+    tag = "events"
+)]
+pub async fn progress_events(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    headers: HeaderMap,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok());
+
+    let (missed, receiver) = state
+        .events
+        .subscribe_progress(&auth_user.user_id, last_event_id);
+
+    info!(
+        user_id = %auth_user.user_id,
+        last_event_id = ?last_event_id,
+        replayed = missed.len(),
+        "Progress feed connected"
+    );
+
+    let user_id = auth_user.user_id;
+    let live = stream::unfold(receiver, move |mut receiver| {
+        let user_id = user_id.clone();
+        async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) if event.user_id == user_id => return Some((event, receiver)),
+                    Ok(_) => {} // Event of another user
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(user_id = %user_id, skipped = skipped, "Progress feed fell behind, events dropped");
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }
+    });
+
+    let events = stream::iter(missed)
+        .chain(live)
+        .map(|event| to_sse_event(&event));
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
@@ -0,0 +1,703 @@
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    io::{Cursor, Write},
+    sync::Arc,
+};
+use tracing::{info, warn};
+use utoipa::{IntoParams, ToSchema};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+    auth::AuthUser,
+    db::TranscriptBlock,
+    error::AppError,
+    upload::{get_owned_video, AppState},
+};
+
+/// File format of a transcript export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// SubRip captions
+    Srt,
+    /// WebVTT captions
+    Vtt,
+    /// Plain text paragraphs
+    Txt,
+    /// Markdown with speaker headings
+    Md,
+    /// Word document
+    Docx,
+}
+
+impl ExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Srt => "application/x-subrip; charset=utf-8",
+            ExportFormat::Vtt => "text/vtt; charset=utf-8",
+            ExportFormat::Txt => "text/plain; charset=utf-8",
+            ExportFormat::Md => "text/markdown; charset=utf-8",
+            ExportFormat::Docx => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Srt => "srt",
+            ExportFormat::Vtt => "vtt",
+            ExportFormat::Txt => "txt",
+            ExportFormat::Md => "md",
+            ExportFormat::Docx => "docx",
+        }
+    }
+}
+
+/// A pause between blocks at least this long (in seconds) starts a new paragraph
+const PARAGRAPH_GAP: f64 = 2.0;
+
+/// How transcript text is laid out into caption cues
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaptionOptions {
+    /// Maximum characters per line; longer words get a line of their own
+    pub max_line_length: usize,
+    /// Maximum lines per cue; longer blocks are split into several cues
+    pub max_lines: usize,
+    /// Shortest time in seconds a cue stays on screen, unless the next cue starts sooner
+    pub min_duration: f64,
+}
+
+impl Default for CaptionOptions {
+    fn default() -> Self {
+        Self {
+            max_line_length: 42,
+            max_lines: 2,
+            min_duration: 1.0,
+        }
+    }
+}
+
+/// A caption shown on screen between two times
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub lines: Vec<String>,
+}
+
+/// Helper: Group words into lines of at most `max_len` characters, returning word index ranges
+fn wrap_words(words: &[&str], max_len: usize) -> Vec<(usize, usize)> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut len = 0;
+    for (index, word) in words.iter().enumerate() {
+        let word_len = word.chars().count();
+        if index > start && len + 1 + word_len > max_len {
+            lines.push((start, index));
+            start = index;
+            len = word_len;
+        } else {
+            len += if index > start { 1 + word_len } else { word_len };
+        }
+    }
+    if start < words.len() {
+        lines.push((start, words.len()));
+    }
+    lines
+}
+
+/// Lay out transcript blocks as caption cues
+///
+/// Blocks too long for one cue are split at line breaks. The cues' times come from
+/// the word timings where the words match the text, otherwise they are shared out
+/// in proportion to the characters of each cue.
+pub fn build_cues(blocks: &[TranscriptBlock], options: &CaptionOptions) -> Vec<Cue> {
+    let mut cues = Vec::new();
+    for block in blocks {
+        let words: Vec<&str> = block.text.split_whitespace().collect();
+        if words.is_empty() {
+            continue;
+        }
+        let timed = block.words.len() == words.len()
+            && block.words.iter().zip(&words).all(|(timing, word)| timing.text.trim() == *word);
+
+        // Character position of each word, counting one space between words
+        let mut offsets = Vec::with_capacity(words.len() + 1);
+        let mut total = 0;
+        for word in &words {
+            offsets.push(total);
+            total += word.chars().count() + 1;
+        }
+        offsets.push(total);
+        let time_at = |index: usize| {
+            block.start_time + (block.end_time - block.start_time) * offsets[index] as f64 / total as f64
+        };
+
+        let lines = wrap_words(&words, options.max_line_length);
+        let groups: Vec<&[(usize, usize)]> = lines.chunks(options.max_lines).collect();
+        for (index, group) in groups.iter().enumerate() {
+            let first = group[0].0;
+            let last = group[group.len() - 1].1;
+            let start_time = if index == 0 {
+                block.start_time
+            } else if timed {
+                block.words[first].start_time
+            } else {
+                time_at(first)
+            };
+            let end_time = if index == groups.len() - 1 {
+                block.end_time
+            } else if timed {
+                block.words[last - 1].end_time
+            } else {
+                time_at(last)
+            };
+            cues.push(Cue {
+                start_time,
+                end_time: end_time.max(start_time),
+                lines: group.iter().map(|&(from, to)| words[from..to].join(" ")).collect(),
+            });
+        }
+    }
+
+    // Hold short cues on screen longer, without running into the next one
+    for index in 0..cues.len() {
+        let wanted = cues[index].start_time + options.min_duration;
+        if cues[index].end_time < wanted {
+            let limit = cues.get(index + 1).map_or(f64::INFINITY, |next| next.start_time);
+            cues[index].end_time = wanted.min(limit).max(cues[index].end_time);
+        }
+    }
+
+    cues
+}
+
+/// Helper: Format seconds as HH:MM:SS with milliseconds after `separator`
+fn format_timestamp(seconds: f64, separator: char) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        separator,
+        millis % 1000
+    )
+}
+
+/// Helper: Neutralise SRT text players would read as tags, override codes or a timing line
+///
+/// SRT has no escapes, so look-alike characters are used instead.
+fn escape_srt(line: &str) -> String {
+    let mut line = line.replace('<', "‹").replace('{', "(").replace('}', ")");
+    while line.contains("-->") {
+        line = line.replace("-->", "->");
+    }
+    line
+}
+
+/// Render cues as a SubRip file, neutralising text that would be read as markup
+pub fn render_srt(cues: &[Cue]) -> String {
+    let mut output = String::new();
+    for (index, cue) in cues.iter().enumerate() {
+        output.push_str(&format!(
+            "{}\r\n{} --> {}\r\n",
+            index + 1,
+            format_timestamp(cue.start_time, ','),
+            format_timestamp(cue.end_time, ',')
+        ));
+        for line in &cue.lines {
+            output.push_str(&escape_srt(line));
+            output.push_str("\r\n");
+        }
+        output.push_str("\r\n");
+    }
+    output
+}
+
+/// Render cues as a WebVTT file, escaping text that would be read as markup
+pub fn render_vtt(cues: &[Cue]) -> String {
+    let mut output = String::from("WEBVTT\n\n");
+    for cue in cues {
+        output.push_str(&format!(
+            "{} --> {}\n",
+            format_timestamp(cue.start_time, '.'),
+            format_timestamp(cue.end_time, '.')
+        ));
+        for line in &cue.lines {
+            let line = line.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+            output.push_str(&line);
+            output.push('\n');
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// A run of blocks read as one paragraph in a document export
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paragraph {
+    pub start_time: f64,
+    /// Name of the speaker, shown as a heading where the speaker changes
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+/// Group blocks into paragraphs, breaking where the speaker changes or at a long pause
+pub fn build_paragraphs(blocks: &[TranscriptBlock], speaker_names: &HashMap<String, String>) -> Vec<Paragraph> {
+    let mut paragraphs: Vec<Paragraph> = Vec::new();
+    let mut previous: Option<&TranscriptBlock> = None;
+    for block in blocks {
+        let text = block.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() {
+            continue;
+        }
+        let continues = previous.is_some_and(|prev| {
+            prev.speaker_id == block.speaker_id && block.start_time - prev.end_time < PARAGRAPH_GAP
+        });
+        match paragraphs.last_mut() {
+            Some(paragraph) if continues => {
+                paragraph.text.push(' ');
+                paragraph.text.push_str(&text);
+            }
+            _ => paragraphs.push(Paragraph {
+                start_time: block.start_time,
+                speaker: block.speaker_id.as_ref().and_then(|id| speaker_names.get(id).cloned()),
+                text,
+            }),
+        }
+        previous = Some(block);
+    }
+    paragraphs
+}
+
+/// Helper: The speaker heading to show before each paragraph, if the speaker changed
+fn speaker_headings(paragraphs: &[Paragraph]) -> impl Iterator<Item = (Option<&str>, &Paragraph)> {
+    paragraphs.iter().enumerate().map(|(index, paragraph)| {
+        let changed = index == 0 || paragraphs[index - 1].speaker != paragraph.speaker;
+        (paragraph.speaker.as_deref().filter(|_| changed), paragraph)
+    })
+}
+
+/// Render paragraphs as plain text, optionally starting each with an `[hh:mm:ss]` timestamp
+pub fn render_text(paragraphs: &[Paragraph], timestamps: bool) -> String {
+    let mut output = String::new();
+    for (heading, paragraph) in speaker_headings(paragraphs) {
+        if let Some(speaker) = heading {
+            output.push_str(&format!("{}:\n", speaker));
+        }
+        if timestamps {
+            let seconds = paragraph.start_time.max(0.0) as u64;
+            output.push_str(&format!(
+                "[{:02}:{:02}:{:02}] ",
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            ));
+        }
+        output.push_str(&paragraph.text);
+        output.push_str("\n\n");
+    }
+    output
+}
+
+/// Helper: Escape characters that Markdown would read as formatting
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Render paragraphs as Markdown under a title, with a heading wherever the speaker changes
+pub fn render_markdown(title: &str, paragraphs: &[Paragraph]) -> String {
+    let mut output = format!("# {}\n\n", escape_markdown(title));
+    for (heading, paragraph) in speaker_headings(paragraphs) {
+        if let Some(speaker) = heading {
+            output.push_str(&format!("## {}\n\n", escape_markdown(speaker)));
+        }
+        output.push_str(&escape_markdown(&paragraph.text));
+        output.push_str("\n\n");
+    }
+    output
+}
+
+/// Helper: Escape text for an XML element or attribute
+///
+/// Characters XML 1.0 doesn't allow, such as most control characters, are dropped.
+fn escape_xml(text: &str) -> String {
+    text.chars()
+        .filter(|c| !matches!(c, '\u{0}'..='\u{8}' | '\u{b}' | '\u{c}' | '\u{e}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}'))
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Helper: A WordprocessingML paragraph with a single run of text
+fn docx_paragraph(text: &str, run_properties: &str) -> String {
+    format!(
+        "<w:p><w:r>{}<w:t xml:space=\"preserve\">{}</w:t></w:r></w:p>",
+        run_properties,
+        escape_xml(text)
+    )
+}
+
+const DOCX_CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/></Types>"#;
+
+const DOCX_RELATIONSHIPS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/></Relationships>"#;
+
+/// Render paragraphs as a minimal Word document: a title, bold speaker names and plain paragraphs
+pub fn render_docx(title: &str, paragraphs: &[Paragraph]) -> Result<Vec<u8>, AppError> {
+    let mut body = docx_paragraph(title, "<w:rPr><w:b/><w:sz w:val=\"32\"/></w:rPr>");
+    for (heading, paragraph) in speaker_headings(paragraphs) {
+        if let Some(speaker) = heading {
+            body.push_str(&docx_paragraph(speaker, "<w:rPr><w:b/></w:rPr>"));
+        }
+        body.push_str(&docx_paragraph(&paragraph.text, ""));
+    }
+    let document = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<w:document xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\"><w:body>{}</w:body></w:document>",
+        body
+    );
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, contents) in [
+        ("[Content_Types].xml", DOCX_CONTENT_TYPES),
+        ("_rels/.rels", DOCX_RELATIONSHIPS),
+        ("word/document.xml", document.as_str()),
+    ] {
+        zip.start_file(name, options)
+            .and_then(|_| zip.write_all(contents.as_bytes()).map_err(Into::into))
+            .map_err(|e| AppError::Internal(format!("Failed to write DOCX: {}", e)))?;
+    }
+    let cursor = zip
+        .finish()
+        .map_err(|e| AppError::Internal(format!("Failed to write DOCX: {}", e)))?;
+    Ok(cursor.into_inner())
+}
+
+/// Helper: The name of a video without its extension, used for titles and download names
+fn file_stem(original_filename: &str) -> &str {
+    let stem = match original_filename.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem,
+        _ => original_filename,
+    };
+    match stem.trim() {
+        "" => "transcript",
+        stem => stem,
+    }
+}
+
+/// Build a `Content-Disposition` header that downloads as the video's name with a new extension
+///
+/// The plain `filename` is an ASCII fallback; `filename*` keeps the full name for
+/// clients that support it (RFC 6266).
+pub fn content_disposition(original_filename: &str, extension: &str) -> String {
+    let filename = format!("{}.{}", file_stem(original_filename), extension);
+    let fallback: String = filename
+        .chars()
+        .map(|c| if (c.is_ascii_graphic() && c != '"' && c != '\\') || c == ' ' { c } else { '_' })
+        .collect();
+    let mut encoded = String::new();
+    for byte in filename.bytes() {
+        if byte.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    format!("attachment; filename=\"{}\"; filename*=UTF-8''{}", fallback, encoded)
+}
+
+/// Helper: Group a video's blocks into paragraphs, naming their speakers
+async fn load_paragraphs(
+    state: &AppState,
+    video_id: &str,
+    blocks: &[TranscriptBlock],
+) -> Result<Vec<Paragraph>, AppError> {
+    let speaker_names: HashMap<String, String> = state
+        .db
+        .list_speakers(video_id)
+        .await?
+        .into_iter()
+        .map(|speaker| (speaker.id, speaker.name))
+        .collect();
+    Ok(build_paragraphs(blocks, &speaker_names))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ExportQuery {
+    #[param(inline)]
+    pub format: ExportFormat,
+    /// Maximum characters per caption line (default 42)
+    pub max_line_length: Option<usize>,
+    /// Maximum lines per caption (default 2)
+    pub max_lines: Option<usize>,
+    /// Minimum time in seconds a caption stays on screen (default 1)
+    pub min_duration: Option<f64>,
+    /// Start each paragraph of a plain text export with an [hh:mm:ss] timestamp
+    #[serde(default)]
+    pub timestamps: bool,
+}
+
+/// Export a video's transcript as captions or a document
+///
+/// The file downloads under the video's original name with the format's extension.
+/// Caption layout options apply to SRT and WebVTT; document formats group blocks
+/// into paragraphs by speaker and pauses.
+#[utoipa::path(
+    get,
+    path = "/api/videos/{id}/transcript/export",
+    params(
+        ("id" = String, Path, description = "Video ID"),
+        ExportQuery
+    ),
+    responses(
+        (status = 200, description = "Exported transcript file", body = String, content_type = "application/octet-stream"),
+        (status = 400, description = "Invalid format or layout options"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn export_transcript(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let video = get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let defaults = CaptionOptions::default();
+    let options = CaptionOptions {
+        max_line_length: query.max_line_length.unwrap_or(defaults.max_line_length),
+        max_lines: query.max_lines.unwrap_or(defaults.max_lines),
+        min_duration: query.min_duration.unwrap_or(defaults.min_duration),
+    };
+    if options.max_line_length == 0 || options.max_lines == 0 {
+        return Err(AppError::BadRequest("max_line_length and max_lines must be at least 1".to_string()));
+    }
+    if !options.min_duration.is_finite() || options.min_duration < 0.0 {
+        return Err(AppError::BadRequest("min_duration must be a non-negative number".to_string()));
+    }
+
+    let blocks = state.db.list_transcript_blocks(&video_id).await?;
+    let title = file_stem(&video.original_filename);
+    let body = match query.format {
+        ExportFormat::Srt => render_srt(&build_cues(&blocks, &options)).into_bytes(),
+        ExportFormat::Vtt => render_vtt(&build_cues(&blocks, &options)).into_bytes(),
+        ExportFormat::Txt => render_text(&load_paragraphs(&state, &video_id, &blocks).await?, query.timestamps).into_bytes(),
+        ExportFormat::Md => render_markdown(title, &load_paragraphs(&state, &video_id, &blocks).await?).into_bytes(),
+        ExportFormat::Docx => render_docx(title, &load_paragraphs(&state, &video_id, &blocks).await?)?,
+    };
+
+    info!(video_id = %video_id, format = ?query.format, bytes = body.len(), "Exported transcript");
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, query.format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                content_disposition(&video.original_filename, query.format.extension()),
+            ),
+        ],
+        body,
+    ))
+}
+
+/// Serve the current transcript as a WebVTT track for the video player's `<track>` element
+///
+/// Like the stream, it's addressed by video ID alone. The ETag follows the latest
+/// transcript revision, so players revalidate and get a fresh track after every edit.
+#[utoipa::path(
+    get,
+    path = "/api/videos/{id}/captions.vtt",
+    params(("id" = String, Path, description = "Video ID")),
+    responses(
+        (status = 200, description = "WebVTT captions", body = String, content_type = "text/vtt"),
+        (status = 304, description = "Captions unchanged since the given ETag"),
+        (status = 404, description = "Video not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "videos"
+)]
+pub async fn caption_track(
+    Path(video_id): Path<String>,
+    headers: HeaderMap,
+    State(state): State<Arc<AppState>>,
+) -> Result<Response, AppError> {
+    state.db.get_video(&video_id).await?.ok_or_else(|| {
+        warn!(video_id = %video_id, "Video not found");
+        AppError::NotFound("Video not found".to_string())
+    })?;
+
+    let revision = state.db.latest_transcript_revision_id(&video_id).await?.unwrap_or(0);
+    let etag = format!("\"{}-{}\"", video_id, revision);
+    let unchanged = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|h| h.to_str().ok())
+        .is_some_and(|tags| tags.split(',').any(|tag| tag.trim().trim_start_matches("W/") == etag || tag.trim() == "*"));
+    if unchanged {
+        return Ok(Response::builder()
+            .status(StatusCode::NOT_MODIFIED)
+            .header(header::CACHE_CONTROL, "no-cache")
+            .header(header::ETAG, etag)
+            .body(Body::empty())
+            .unwrap());
+    }
+
+    let blocks = state.db.list_transcript_blocks(&video_id).await?;
+    let body = render_vtt(&build_cues(&blocks, &CaptionOptions::default()));
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, ExportFormat::Vtt.content_type())
+        .header(header::CACHE_CONTROL, "no-cache")
+        .header(header::ETAG, etag)
+        .body(Body::from(body))
+        .unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::TranscriptWord;
+
+    fn block(start_time: f64, end_time: f64, text: &str) -> TranscriptBlock {
+        TranscriptBlock::new("video".to_string(), start_time, end_time, text.to_string(), 0)
+    }
+
+    fn options(max_line_length: usize, max_lines: usize, min_duration: f64) -> CaptionOptions {
+        CaptionOptions {
+            max_line_length,
+            max_lines,
+            min_duration,
+        }
+    }
+
+    #[test]
+    fn test_wrap_words() {
+        let words = ["Evam", "me", "sutam,", "ekam", "samayam"];
+        assert_eq!(wrap_words(&words, 14), vec![(0, 3), (3, 5)]);
+        // A word longer than a line gets its own line
+        assert_eq!(wrap_words(&words, 3), vec![(0, 1), (1, 2), (2, 3), (3, 4), (4, 5)]);
+    }
+
+    #[test]
+    fn test_long_block_uses_word_timings() {
+        let mut block = block(10.0, 16.0, "one two three four");
+        block.set_words(
+            ["one", "two", "three", "four"]
+                .iter()
+                .enumerate()
+                .map(|(i, text)| TranscriptWord {
+                    start_time: 10.5 + i as f64,
+                    end_time: 11.0 + i as f64,
+                    text: text.to_string(),
+                    confidence: None,
+                })
+                .collect(),
+        );
+
+        let cues = build_cues(&[block], &options(10, 1, 0.0));
+        let times: Vec<(f64, f64)> = cues.iter().map(|c| (c.start_time, c.end_time)).collect();
+        assert_eq!(times, vec![(10.0, 12.0), (12.5, 16.0)]);
+        assert_eq!(cues[0].lines, vec!["one two"]);
+        assert_eq!(cues[1].lines, vec!["three four"]);
+    }
+
+    #[test]
+    fn test_untimed_block_is_shared_by_characters() {
+        // "aaa bbb " and "ccc ddd " are half the characters each
+        let cues = build_cues(&[block(0.0, 4.0, "aaa bbb ccc ddd")], &options(7, 1, 0.0));
+        assert_eq!((cues[0].end_time, cues[1].start_time), (2.0, 2.0));
+    }
+
+    #[test]
+    fn test_min_duration_stops_at_next_cue() {
+        let blocks = [block(0.0, 0.2, "Hi."), block(0.5, 0.6, "Hello."), block(3.0, 3.1, "Bye.")];
+        let cues = build_cues(&blocks, &options(42, 2, 1.0));
+        let ends: Vec<f64> = cues.iter().map(|c| c.end_time).collect();
+        assert_eq!(ends, vec![0.5, 1.5, 4.0]);
+    }
+
+    #[test]
+    fn test_paragraphs() {
+        let mut blocks = vec![
+            block(0.0, 2.0, "Good evening."),
+            block(2.5, 4.0, "Let us begin."),
+            block(9.0, 10.0, "After a pause."),
+            block(10.0, 11.0, "A question?"),
+        ];
+        blocks[3].speaker_id = Some("s2".to_string());
+        let names = HashMap::from([("s2".to_string(), "Guest".to_string())]);
+
+        let paragraphs = build_paragraphs(&blocks, &names);
+        let texts: Vec<&str> = paragraphs.iter().map(|p| p.text.as_str()).collect();
+        assert_eq!(texts, vec!["Good evening. Let us begin.", "After a pause.", "A question?"]);
+        assert_eq!(paragraphs[2].speaker.as_deref(), Some("Guest"));
+
+        assert_eq!(
+            render_text(&paragraphs, true),
+            "[00:00:00] Good evening. Let us begin.\n\n[00:00:09] After a pause.\n\nGuest:\n[00:00:10] A question?\n\n"
+        );
+        assert_eq!(
+            render_markdown("Talk_1", &paragraphs[2..]),
+            "# Talk\\_1\n\n## Guest\n\nA question?\n\n"
+        );
+    }
+
+    #[test]
+    fn test_content_disposition() {
+        assert_eq!(
+            content_disposition("Evening talk.mp4", "srt"),
+            "attachment; filename=\"Evening talk.srt\"; filename*=UTF-8''Evening%20talk.srt"
+        );
+        assert_eq!(
+            content_disposition("Dhammapāda \"1\".mov", "md"),
+            "attachment; filename=\"Dhammap_da _1_.md\"; filename*=UTF-8''Dhammap%C4%81da%20%221%22.md"
+        );
+        assert_eq!(content_disposition("", "txt"), "attachment; filename=\"transcript.txt\"; filename*=UTF-8''transcript.txt");
+    }
+
+    #[test]
+    fn test_render() {
+        let cues = vec![Cue {
+            start_time: 3661.5,
+            end_time: 3663.0,
+            lines: vec!["A <b> & --> C".to_string(), "{\\an8}D --->".to_string()],
+        }];
+        assert_eq!(
+            render_srt(&cues),
+            "1\r\n01:01:01,500 --> 01:01:03,000\r\nA ‹b> & -> C\r\n(\\an8)D ->\r\n\r\n"
+        );
+        assert_eq!(
+            render_vtt(&cues),
+            "WEBVTT\n\n01:01:01.500 --> 01:01:03.000\nA &lt;b&gt; &amp; --&gt; C\n{\\an8}D ---&gt;\n\n"
+        );
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(escape_xml("<a & \"b\">"), "&lt;a &amp; &quot;b&quot;&gt;");
+        assert_eq!(escape_xml("tab\there\r\nbell\u{7}\u{b}\u{1f}end"), "tab\there\r\nbellend");
+    }
+}
@@ -0,0 +1,688 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json as SqlJson;
+use std::sync::Arc;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::{
+    auth::AuthUser,
+    db::{GlossaryTerm, TranscriptWord},
+    error::AppError,
+    transcriber::TranscriptSegment,
+    upload::AppState,
+};
+
+/// Longest prompt handed to engines; whisper only reads the last ~224 tokens of its prompt
+const MAX_PROMPT_CHARS: usize = 600;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateGlossaryTermRequest {
+    pub term: String,
+    /// Known misspellings that should always become the term
+    #[serde(default)]
+    pub variants: Vec<String>,
+    /// Also correct near misses of terms longer than five letters
+    #[serde(default)]
+    pub fuzzy: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateGlossaryTermRequest {
+    pub term: Option<String>,
+    pub variants: Option<Vec<String>>,
+    pub fuzzy: Option<bool>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GlossaryPromptResponse {
+    /// Prompt passed to transcription engines, or null if the glossary is empty
+    pub prompt: Option<String>,
+}
+
+/// Build the vocabulary prompt for a glossary, e.g. "Glossary: Ajahn Chah, Mettā."
+///
+/// Terms that would push the prompt past `MAX_PROMPT_CHARS` are left out.
+pub fn glossary_prompt(terms: &[GlossaryTerm]) -> Option<String> {
+    let mut prompt = String::from("Glossary:");
+    let mut included = 0;
+    for term in terms {
+        if prompt.chars().count() + term.term.chars().count() + 3 > MAX_PROMPT_CHARS {
+            break;
+        }
+        if included > 0 {
+            prompt.push(',');
+        }
+        prompt.push(' ');
+        prompt.push_str(&term.term);
+        included += 1;
+    }
+
+    if included == 0 {
+        return None;
+    }
+    prompt.push('.');
+    Some(prompt)
+}
+
+/// Helper: Lowercase a character and drop its diacritics (IAST and common Latin accents)
+fn fold_char(c: char) -> char {
+    match c.to_lowercase().next().unwrap_or(c) {
+        'ā' | 'á' | 'à' | 'â' | 'ä' => 'a',
+        'ī' | 'í' | 'ì' | 'î' | 'ï' => 'i',
+        'ū' | 'ú' | 'ù' | 'û' | 'ü' => 'u',
+        'ē' | 'é' | 'è' | 'ê' | 'ë' => 'e',
+        'ō' | 'ó' | 'ò' | 'ô' | 'ö' => 'o',
+        'ṛ' | 'ṝ' => 'r',
+        'ḷ' | 'ḹ' => 'l',
+        'ṃ' | 'ṁ' => 'm',
+        'ṅ' | 'ñ' | 'ṇ' => 'n',
+        'ṭ' => 't',
+        'ḍ' => 'd',
+        'ś' | 'ṣ' => 's',
+        'ḥ' => 'h',
+        c => c,
+    }
+}
+
+/// Helper: Fold a phrase for comparison, collapsing whitespace
+fn fold(text: &str) -> Vec<char> {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .map(fold_char)
+        .collect()
+}
+
+/// Helper: Levenshtein distance between two folded phrases
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Helper: Edits tolerated for a term of `len` characters; short terms must match exactly
+fn allowed_edits(len: usize) -> usize {
+    match len {
+        0..=5 => 0,
+        6..=8 => 1,
+        _ => 2,
+    }
+}
+
+/// One way a glossary term may be written in a transcript: the term itself or a variant
+struct Spelling {
+    folded: Vec<char>,
+    word_count: usize,
+    /// Whether near misses count too
+    fuzzy: bool,
+}
+
+impl Spelling {
+    fn new(text: &str, fuzzy: bool) -> Self {
+        Self {
+            folded: fold(text),
+            word_count: text.split_whitespace().count(),
+            fuzzy,
+        }
+    }
+
+    /// Whether a folded phrase from the transcript is this spelling
+    fn matches(&self, candidate: &[char]) -> bool {
+        if candidate == self.folded.as_slice() {
+            return true;
+        }
+        // Near misses are opt-in and must agree on the first letter to keep ordinary words intact
+        self.fuzzy
+            && candidate.first() == self.folded.first()
+            && edit_distance(candidate, &self.folded) <= allowed_edits(self.folded.len())
+    }
+}
+
+struct GlossaryEntry {
+    term: String,
+    spellings: Vec<Spelling>,
+}
+
+/// A user's glossary prepared for normalizing transcripts
+pub struct Glossary {
+    entries: Vec<GlossaryEntry>,
+    /// Word counts of all spellings, longest first, so "Ajahn Chah" wins over "Chah"
+    word_counts: Vec<usize>,
+}
+
+/// A whitespace-separated token of the text being normalized, as byte ranges
+struct Token {
+    start: usize,
+    end: usize,
+    /// The token without surrounding punctuation
+    core_start: usize,
+    core_end: usize,
+}
+
+impl Glossary {
+    pub fn new(terms: &[GlossaryTerm]) -> Self {
+        let entries: Vec<GlossaryEntry> = terms
+            .iter()
+            .filter(|t| !t.term.trim().is_empty())
+            .map(|t| {
+                // Only the term itself is matched loosely; variants are exact misspellings
+                let mut spellings = vec![Spelling::new(&t.term, t.fuzzy)];
+                spellings.extend(
+                    t.variants
+                        .iter()
+                        .map(|v| Spelling::new(v, false))
+                        .filter(|v| v.word_count > 0),
+                );
+                GlossaryEntry {
+                    term: t.term.trim().to_string(),
+                    spellings,
+                }
+            })
+            .collect();
+
+        let mut word_counts: Vec<usize> = entries
+            .iter()
+            .flat_map(|e| e.spellings.iter().map(|s| s.word_count))
+            .collect();
+        word_counts.sort_unstable_by(|a, b| b.cmp(a));
+        word_counts.dedup();
+
+        Self { entries, word_counts }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Replace near-miss spellings of glossary terms with the preferred form
+    ///
+    /// Matching ignores case and diacritics and tolerates small typos in longer
+    /// terms. Punctuation around the matched words and the spacing between
+    /// other words are kept.
+    pub fn normalize(&self, text: &str) -> String {
+        if self.entries.is_empty() {
+            return text.to_string();
+        }
+
+        let tokens = tokenize(text);
+        let mut output = String::with_capacity(text.len());
+        let mut copied = 0;
+        let mut i = 0;
+        while i < tokens.len() {
+            match self.match_at(text, &tokens[i..]) {
+                Some((entry, count)) => {
+                    let first = &tokens[i];
+                    let last = &tokens[i + count - 1];
+                    output.push_str(&text[copied..first.core_start]);
+                    output.push_str(&entry.term);
+                    copied = last.core_end;
+                    i += count;
+                }
+                None => i += 1,
+            }
+        }
+        output.push_str(&text[copied..]);
+        output
+    }
+
+    /// Normalize a segment's text and its words
+    ///
+    /// Terms are matched across consecutive words, so the words keep spelling out the text.
+    pub fn normalize_segment(&self, segment: &mut TranscriptSegment) {
+        if self.entries.is_empty() {
+            return;
+        }
+        segment.text = self.normalize(&segment.text);
+        segment.words = self.normalize_words(&segment.words);
+    }
+
+    /// Helper: Normalize timed words, replacing the words of each match with one word per word of the term
+    ///
+    /// Matched words keep their own timings when the counts agree; otherwise the time they
+    /// span is shared out by length and they get their lowest confidence.
+    fn normalize_words(&self, words: &[TranscriptWord]) -> Vec<TranscriptWord> {
+        // Lay the words out as one text with a token per word
+        let mut text = String::new();
+        let mut tokens = Vec::with_capacity(words.len());
+        for word in words {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            let start = text.len();
+            text.push_str(word.text.trim());
+            tokens.push(token(&text, start, text.len()));
+        }
+
+        let mut output = Vec::with_capacity(words.len());
+        let mut i = 0;
+        while i < words.len() {
+            let Some((entry, count)) = self.match_at(&text, &tokens[i..]) else {
+                output.push(words[i].clone());
+                i += 1;
+                continue;
+            };
+
+            let matched = &words[i..i + count];
+            let prefix = &text[tokens[i].start..tokens[i].core_start];
+            let suffix = &text[tokens[i + count - 1].core_end..tokens[i + count - 1].end];
+            let term_words: Vec<&str> = entry.term.split_whitespace().collect();
+            let (start, end) = (matched[0].start_time, matched[count - 1].end_time);
+            let total_chars: usize = term_words.iter().map(|w| w.chars().count()).sum();
+            let confidence = matched.iter().filter_map(|w| w.confidence).reduce(f64::min);
+
+            let mut chars_before = 0;
+            for (index, term_word) in term_words.iter().enumerate() {
+                let mut word = if term_words.len() == count {
+                    matched[index].clone()
+                } else {
+                    let word_start = start + (end - start) * chars_before as f64 / total_chars as f64;
+                    chars_before += term_word.chars().count();
+                    TranscriptWord {
+                        start_time: word_start,
+                        end_time: start + (end - start) * chars_before as f64 / total_chars as f64,
+                        text: String::new(),
+                        confidence,
+                    }
+                };
+                // Leading whitespace some engines put on words is kept
+                let original = &matched[index.min(count - 1)].text;
+                let mut spelled = original[..original.len() - original.trim_start().len()].to_string();
+                if index == 0 {
+                    spelled.push_str(prefix);
+                }
+                spelled.push_str(term_word);
+                if index == term_words.len() - 1 {
+                    spelled.push_str(suffix);
+                }
+                word.text = spelled;
+                output.push(word);
+            }
+            i += count;
+        }
+        output
+    }
+
+    /// Helper: The entry with the longest spelling matching the tokens at the start of `tokens`, with its token count
+    fn match_at(&self, text: &str, tokens: &[Token]) -> Option<(&GlossaryEntry, usize)> {
+        self.word_counts.iter().find_map(|&count| {
+            let candidate = tokens.get(..count)?;
+            // Punctuation inside a phrase ("Ajahn, Chah") breaks it up
+            let inner_punctuation = candidate.windows(2).any(|pair| {
+                pair[0].core_end != pair[0].end || pair[1].core_start != pair[1].start
+            });
+            if inner_punctuation || candidate.iter().any(|t| t.core_start == t.core_end) {
+                return None;
+            }
+
+            let folded = fold(&text[candidate[0].core_start..candidate[count - 1].core_end]);
+            self.entries
+                .iter()
+                .find(|entry| {
+                    entry
+                        .spellings
+                        .iter()
+                        .any(|s| s.word_count == count && s.matches(&folded))
+                })
+                .map(|entry| (entry, count))
+        })
+    }
+}
+
+/// Helper: A token spanning `start..end` of `text`, with its punctuation-free core
+fn token(text: &str, start: usize, end: usize) -> Token {
+    let token = &text[start..end];
+    let core_start = token
+        .char_indices()
+        .find(|(_, c)| c.is_alphanumeric())
+        .map_or(end, |(i, _)| start + i);
+    let core_end = token
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_alphanumeric())
+        .map_or(end, |(i, c)| start + i + c.len_utf8());
+    Token {
+        start,
+        end,
+        core_start,
+        core_end,
+    }
+}
+
+/// Helper: Split text into whitespace-separated tokens with their punctuation-free cores
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (index, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(index),
+            (Some(token_start), true) => {
+                tokens.push(token(text, token_start, index));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    tokens
+}
+
+/// Load the glossary of a user for normalizing transcripts, with its engine prompt
+pub async fn load_glossary(state: &AppState, user_id: &str) -> Result<(Glossary, Option<String>), AppError> {
+    let terms = state.db.list_glossary_terms(user_id).await?;
+    Ok((Glossary::new(&terms), glossary_prompt(&terms)))
+}
+
+/// Helper: Trim a term and its variants, rejecting blank terms
+fn clean_term(term: &str, variants: Vec<String>) -> Result<(String, Vec<String>), AppError> {
+    let term = term.split_whitespace().collect::<Vec<_>>().join(" ");
+    if term.is_empty() {
+        return Err(AppError::BadRequest("Glossary term must not be empty".to_string()));
+    }
+    let variants = variants
+        .iter()
+        .map(|v| v.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|v| !v.is_empty())
+        .collect();
+    Ok((term, variants))
+}
+
+/// Helper: Reject a spelling the user already has in their glossary under another entry
+async fn check_unique(state: &AppState, user_id: &str, term: &str, id: Option<&str>) -> Result<(), AppError> {
+    match state.db.find_glossary_term(user_id, term).await? {
+        Some(existing) if Some(existing.id.as_str()) != id => Err(AppError::Conflict(format!(
+            "'{}' is already in the glossary",
+            existing.term
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Helper: Load a glossary term and verify it belongs to the user
+async fn get_owned_term(state: &AppState, term_id: &str, auth_user: &AuthUser) -> Result<GlossaryTerm, AppError> {
+    match state.db.get_glossary_term(term_id).await? {
+        Some(term) if term.user_id == auth_user.user_id => Ok(term),
+        _ => {
+            warn!(user_id = %auth_user.user_id, term_id = %term_id, "Glossary term not found");
+            Err(AppError::NotFound("Glossary term not found".to_string()))
+        }
+    }
+}
+
+/// List the user's glossary terms
+#[utoipa::path(
+    get,
+    path = "/api/glossary",
+    responses(
+        (status = 200, description = "Glossary terms in alphabetical order", body = Vec<GlossaryTerm>),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "glossary"
+)]
+pub async fn list_terms(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    let terms = state.db.list_glossary_terms(&auth_user.user_id).await?;
+
+    Ok((StatusCode::OK, Json(terms)))
+}
+
+/// Add a term to the user's glossary
+#[utoipa::path(
+    post,
+    path = "/api/glossary",
+    request_body = CreateGlossaryTermRequest,
+    responses(
+        (status = 201, description = "Term added", body = GlossaryTerm),
+        (status = 400, description = "Empty term"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 409, description = "Term already in the glossary"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "glossary"
+)]
+pub async fn create_term(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Json(req): Json<CreateGlossaryTermRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let (term, variants) = clean_term(&req.term, req.variants)?;
+    check_unique(&state, &auth_user.user_id, &term, None).await?;
+
+    let mut term = GlossaryTerm::new(auth_user.user_id.clone(), term, variants);
+    term.fuzzy = req.fuzzy;
+    // The unique index settles a race with another request adding the same spelling
+    if !state.db.insert_glossary_term(&term).await? {
+        return Err(AppError::Conflict(format!("'{}' is already in the glossary", term.term)));
+    }
+
+    info!(user_id = %auth_user.user_id, term_id = %term.id, "Added glossary term");
+
+    Ok((StatusCode::CREATED, Json(term)))
+}
+
+/// Change the spelling, variants or fuzzy matching of a glossary term
+#[utoipa::path(
+    patch,
+    path = "/api/glossary/{term_id}",
+    params(("term_id" = String, Path, description = "Glossary term ID")),
+    request_body = UpdateGlossaryTermRequest,
+    responses(
+        (status = 200, description = "Term updated", body = GlossaryTerm),
+        (status = 400, description = "Empty term"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Term not found"),
+        (status = 409, description = "Term already in the glossary"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "glossary"
+)]
+pub async fn update_term(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(term_id): Path<String>,
+    Json(req): Json<UpdateGlossaryTermRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut term = get_owned_term(&state, &term_id, &auth_user).await?;
+
+    let (spelling, variants) = clean_term(
+        req.term.as_deref().unwrap_or(&term.term),
+        req.variants.unwrap_or_else(|| term.variants.0.clone()),
+    )?;
+    check_unique(&state, &auth_user.user_id, &spelling, Some(&term.id)).await?;
+
+    term.term = spelling;
+    term.variants = SqlJson(variants);
+    if let Some(fuzzy) = req.fuzzy {
+        term.fuzzy = fuzzy;
+    }
+    term.updated_at = Utc::now();
+    if !state.db.update_glossary_term(&term).await? {
+        return Err(AppError::Conflict(format!("'{}' is already in the glossary", term.term)));
+    }
+
+    info!(user_id = %auth_user.user_id, term_id = %term_id, "Updated glossary term");
+
+    Ok((StatusCode::OK, Json(term)))
+}
+
+/// Remove a term from the user's glossary
+#[utoipa::path(
+    delete,
+    path = "/api/glossary/{term_id}",
+    params(("term_id" = String, Path, description = "Glossary term ID")),
+    responses(
+        (status = 204, description = "Term deleted"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Term not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "glossary"
+)]
+pub async fn delete_term(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(term_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_term(&state, &term_id, &auth_user).await?;
+
+    state.db.delete_glossary_term(&term_id).await?;
+
+    info!(user_id = %auth_user.user_id, term_id = %term_id, "Deleted glossary term");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get the vocabulary prompt built from the user's glossary
+///
+/// This is the prompt passed to transcription engines to bias them towards the
+/// glossary spellings.
+#[utoipa::path(
+    get,
+    path = "/api/glossary/prompt",
+    responses(
+        (status = 200, description = "Engine prompt", body = GlossaryPromptResponse),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "glossary"
+)]
+pub async fn get_prompt(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+) -> Result<impl IntoResponse, AppError> {
+    let terms = state.db.list_glossary_terms(&auth_user.user_id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(GlossaryPromptResponse {
+            prompt: glossary_prompt(&terms),
+        }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glossary(terms: &[(&str, &[&str], bool)]) -> Glossary {
+        let terms: Vec<GlossaryTerm> = terms
+            .iter()
+            .map(|(term, variants, fuzzy)| {
+                let mut term = GlossaryTerm::new(
+                    "user".to_string(),
+                    term.to_string(),
+                    variants.iter().map(|v| v.to_string()).collect(),
+                );
+                term.fuzzy = *fuzzy;
+                term
+            })
+            .collect();
+        Glossary::new(&terms)
+    }
+
+    #[test]
+    fn test_normalize_near_misses() {
+        let glossary = glossary(&[("Sāriputta", &[], true), ("Mettā", &[], true), ("Ajahn Chah", &["Ajan Char"], false)]);
+
+        assert_eq!(
+            glossary.normalize("Then sariputa asked about metta."),
+            "Then Sāriputta asked about Mettā."
+        );
+        assert_eq!(glossary.normalize("As ajan char said,  \"peace\""), "As Ajahn Chah said,  \"peace\"");
+        // Punctuation splits phrases, and unrelated words stay put
+        assert_eq!(glossary.normalize("Ajahn, Chah"), "Ajahn, Chah");
+        assert_eq!(glossary.normalize("meat and matter"), "meat and matter");
+    }
+
+    #[test]
+    fn test_short_terms_need_exact_match() {
+        let glossary = glossary(&[("Chah", &[], true), ("Mettā", &[], true)]);
+
+        assert_eq!(glossary.normalize("chah"), "Chah");
+        assert_eq!(glossary.normalize("a chat"), "a chat");
+        assert_eq!(glossary.normalize("the meta discussion"), "the meta discussion");
+    }
+
+    #[test]
+    fn test_near_misses_are_opt_in() {
+        let glossary = glossary(&[("Sāriputta", &[], false), ("Ajahn Chah", &["Ajan Char"], false)]);
+
+        // Exact spellings and listed variants still apply, near misses don't
+        assert_eq!(glossary.normalize("sariputta and sariputa"), "Sāriputta and sariputa");
+        assert_eq!(glossary.normalize("ajan char"), "Ajahn Chah");
+        assert_eq!(glossary.normalize("ajahn chan"), "ajahn chan");
+    }
+
+    #[test]
+    fn test_variants_with_other_word_counts() {
+        let glossary = glossary(&[("Ajahn Chah", &["Ajahnchah"], false), ("Sāriputta", &["Sari putta"], false)]);
+
+        assert_eq!(glossary.normalize("Ajahnchah taught"), "Ajahn Chah taught");
+        assert_eq!(glossary.normalize("Venerable sari putta."), "Venerable Sāriputta.");
+    }
+
+    #[test]
+    fn test_normalize_segment_words() {
+        let glossary = glossary(&[("Ajahn Chah", &["Ajan Cha"], false), ("Sāriputta", &["Sari putta"], false)]);
+        let word = |start_time: f64, end_time: f64, text: &str, confidence: f64| TranscriptWord {
+            start_time,
+            end_time,
+            text: text.to_string(),
+            confidence: Some(confidence),
+        };
+        let mut segment = TranscriptSegment {
+            start: 0.0,
+            end: 4.0,
+            text: "Ajan Cha, and Sari putta".to_string(),
+            words: vec![
+                word(0.0, 0.5, " Ajan", 0.9),
+                word(0.5, 1.0, " Cha,", 0.4),
+                word(1.0, 2.0, " and", 0.8),
+                word(2.0, 2.5, " Sari", 0.7),
+                word(2.5, 4.0, " putta", 0.6),
+            ],
+        };
+        glossary.normalize_segment(&mut segment);
+
+        assert_eq!(segment.text, "Ajahn Chah, and Sāriputta");
+        // Same word count: each word keeps its timing and confidence
+        assert_eq!(segment.words[0], word(0.0, 0.5, " Ajahn", 0.9));
+        assert_eq!(segment.words[1], word(0.5, 1.0, " Chah,", 0.4));
+        assert_eq!(segment.words[2], word(1.0, 2.0, " and", 0.8));
+        // Two words spelling one term become one word over their span
+        assert_eq!(segment.words[3], word(2.0, 4.0, " Sāriputta", 0.6));
+        assert_eq!(segment.words.len(), 4);
+    }
+
+    #[test]
+    fn test_glossary_prompt() {
+        let terms = vec![
+            GlossaryTerm::new("user".to_string(), "Ajahn Chah".to_string(), vec![]),
+            GlossaryTerm::new("user".to_string(), "Mettā".to_string(), vec![]),
+        ];
+        assert_eq!(glossary_prompt(&terms).as_deref(), Some("Glossary: Ajahn Chah, Mettā."));
+        assert_eq!(glossary_prompt(&[]), None);
+
+        let many: Vec<GlossaryTerm> = (0..200)
+            .map(|i| GlossaryTerm::new("user".to_string(), format!("Term{}", i), vec![]))
+            .collect();
+        assert!(glossary_prompt(&many).unwrap().chars().count() <= MAX_PROMPT_CHARS);
+    }
+}
@@ -0,0 +1,508 @@
+use axum::{
+    extract::{Multipart, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::{fmt, sync::Arc};
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::{
+    auth::AuthUser,
+    db::{TranscriptBlock, TranscriptWord},
+    error::AppError,
+    upload::{get_owned_video, AppState},
+};
+
+/// Most cue errors listed in one response
+const MAX_REPORTED_ERRORS: usize = 20;
+
+/// Largest import request accepted; caption files are a few megabytes at most
+pub const MAX_IMPORT_BYTES: usize = 16 * 1024 * 1024;
+
+/// File format of a transcript import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    /// SubRip captions
+    Srt,
+    /// WebVTT captions
+    Vtt,
+    /// Blocks in the `TranscriptImport` schema
+    Json,
+}
+
+impl ImportFormat {
+    /// Parse a format name as sent in the `format` field
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "srt" => Some(ImportFormat::Srt),
+            "vtt" | "webvtt" => Some(ImportFormat::Vtt),
+            "json" => Some(ImportFormat::Json),
+            _ => None,
+        }
+    }
+
+    /// Name of the format for error messages
+    pub fn name(self) -> &'static str {
+        match self {
+            ImportFormat::Srt => "SRT",
+            ImportFormat::Vtt => "WebVTT",
+            ImportFormat::Json => "JSON",
+        }
+    }
+
+    /// Guess the format from the file name, or from its contents if the extension is unknown
+    pub fn detect(filename: &str, contents: &str) -> Self {
+        let extension = filename.rsplit_once('.').map(|(_, ext)| ext).unwrap_or("");
+        if let Some(format) = Self::from_name(extension) {
+            return format;
+        }
+        let start = contents.trim_start_matches('\u{feff}').trim_start();
+        if start.starts_with("WEBVTT") {
+            ImportFormat::Vtt
+        } else if start.starts_with('{') {
+            ImportFormat::Json
+        } else {
+            ImportFormat::Srt
+        }
+    }
+}
+
+/// A transcript block in a JSON import
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportedBlock {
+    /// Start in seconds (`startTime` is accepted too)
+    #[serde(alias = "startTime")]
+    pub start_time: f64,
+    /// End in seconds (`endTime` is accepted too)
+    #[serde(alias = "endTime")]
+    pub end_time: f64,
+    pub text: String,
+    /// Word-level timings, if known
+    #[serde(default)]
+    pub words: Vec<TranscriptWord>,
+}
+
+/// JSON transcript import: timed blocks in reading order
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TranscriptImport {
+    pub blocks: Vec<ImportedBlock>,
+}
+
+/// A timed piece of text read from an imported file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedCue {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub text: String,
+    pub words: Vec<TranscriptWord>,
+}
+
+/// Why one cue of an imported file was rejected
+#[derive(Debug, Clone, PartialEq)]
+pub struct CueError {
+    /// Position of the cue in the file, from 1; 0 for problems with the file as a whole
+    pub cue: usize,
+    /// Line of the file the problem is on, if known
+    pub line: Option<usize>,
+    pub reason: String,
+}
+
+impl fmt::Display for CueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.cue, self.line) {
+            (0, Some(line)) => write!(f, "line {}: {}", line, self.reason),
+            (0, None) => write!(f, "{}", self.reason),
+            (cue, Some(line)) => write!(f, "cue {} (line {}): {}", cue, line, self.reason),
+            (cue, None) => write!(f, "cue {}: {}", cue, self.reason),
+        }
+    }
+}
+
+/// Helper: Parse a caption timestamp such as `01:02:03,456`, `01:02:03.456` or `02:03.456`
+fn parse_timestamp(value: &str) -> Option<f64> {
+    let parts: Vec<&str> = value.trim().split(':').collect();
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [hours, minutes, seconds] => (hours.parse::<u64>().ok()?, minutes.parse::<u64>().ok()?, *seconds),
+        [minutes, seconds] => (0, minutes.parse::<u64>().ok()?, *seconds),
+        _ => return None,
+    };
+    let (whole, fraction) = seconds.split_once([',', '.']).unwrap_or((seconds, "0"));
+    let whole = whole.parse::<u64>().ok()?;
+    if minutes >= 60 || whole >= 60 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let fraction: f64 = format!("0.{}", fraction).parse().ok()?;
+    let whole = hours.checked_mul(3600)?.checked_add(minutes * 60 + whole)?;
+    Some(whole as f64 + fraction)
+}
+
+/// Helper: Parse a `start --> end` timing line, ignoring WebVTT cue settings after the end time
+fn parse_timing(line: &str) -> Result<(f64, f64), String> {
+    let Some((start, rest)) = line.split_once("-->") else {
+        return Err("expected a timing line like 00:00:01,000 --> 00:00:02,000".to_string());
+    };
+    let end = rest.split_whitespace().next().unwrap_or("");
+    let start_time = parse_timestamp(start).ok_or_else(|| format!("invalid start time '{}'", start.trim()))?;
+    let end_time = parse_timestamp(end).ok_or_else(|| format!("invalid end time '{}'", end))?;
+    if end_time < start_time {
+        return Err("end time is before start time".to_string());
+    }
+    Ok((start_time, end_time))
+}
+
+/// Helper: Remove markup tags (e.g. `<i>`, `<v Speaker>`, `{\an8}`) and decode entities from cue text
+///
+/// A `<` or `{` without its closing bracket is ordinary text and is kept.
+fn clean_cue_text(lines: &[&str]) -> String {
+    let joined = lines.join(" ");
+    let mut text = String::new();
+    let mut rest = joined.as_str();
+    while let Some(open) = rest.find(['<', '{']) {
+        text.push_str(&rest[..open]);
+        let (opener, close) = if rest[open..].starts_with('<') { ('<', '>') } else { ('{', '}') };
+        let tag = &rest[open + 1..];
+        match tag.find(close) {
+            Some(end) if !tag[..end].contains(opener) => rest = &tag[end + 1..],
+            _ => {
+                text.push(opener);
+                rest = tag;
+            }
+        }
+    }
+    text.push_str(rest);
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parse SubRip or WebVTT captions, collecting an error for every malformed cue
+pub fn parse_captions(contents: &str, format: ImportFormat) -> Result<Vec<ParsedCue>, Vec<CueError>> {
+    let contents = contents.trim_start_matches('\u{feff}');
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut cues = Vec::new();
+    let mut errors = Vec::new();
+
+    // Runs of non-blank lines, with the (1-based) line number they start on
+    let mut chunks: Vec<(usize, Vec<&str>)> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match chunks.last_mut() {
+            Some((start, chunk)) if *start + chunk.len() == index + 1 => chunk.push(line),
+            _ => chunks.push((index + 1, vec![line])),
+        }
+    }
+
+    if format == ImportFormat::Vtt {
+        match chunks.first() {
+            Some((_, header)) if header[0].starts_with("WEBVTT") => {
+                chunks.remove(0);
+            }
+            _ => {
+                return Err(vec![CueError {
+                    cue: 0,
+                    line: Some(1),
+                    reason: "WebVTT files must start with WEBVTT".to_string(),
+                }])
+            }
+        }
+        chunks.retain(|(_, chunk)| {
+            let first = chunk[0];
+            !(first.starts_with("NOTE") || first.starts_with("STYLE") || first.starts_with("REGION"))
+        });
+    }
+
+    for (number, (start_line, chunk)) in chunks.iter().enumerate() {
+        let cue = number + 1;
+        // An identifier (SRT's cue number) may come before the timing line
+        let timing_index = if chunk[0].contains("-->") { 0 } else { 1 };
+        let Some(timing) = chunk.get(timing_index) else {
+            errors.push(CueError {
+                cue,
+                line: Some(*start_line),
+                reason: "missing timing line".to_string(),
+            });
+            continue;
+        };
+        match parse_timing(timing) {
+            Ok((start_time, end_time)) => {
+                let text = clean_cue_text(&chunk[timing_index + 1..]);
+                if text.is_empty() {
+                    continue;
+                }
+                cues.push(ParsedCue {
+                    start_time,
+                    end_time,
+                    text,
+                    words: Vec::new(),
+                });
+            }
+            Err(reason) => errors.push(CueError {
+                cue,
+                line: Some(start_line + timing_index),
+                reason,
+            }),
+        }
+    }
+
+    if errors.is_empty() { Ok(cues) } else { Err(errors) }
+}
+
+/// Parse a JSON import, collecting an error for every invalid block
+pub fn parse_json(contents: &str) -> Result<Vec<ParsedCue>, Vec<CueError>> {
+    let import: TranscriptImport = serde_json::from_str(contents.trim_start_matches('\u{feff}')).map_err(|e| {
+        vec![CueError {
+            cue: 0,
+            line: Some(e.line()),
+            reason: e.to_string(),
+        }]
+    })?;
+
+    let timed = |start: f64, end: f64| start.is_finite() && end.is_finite() && start >= 0.0 && end >= start;
+    let mut cues = Vec::new();
+    let mut errors = Vec::new();
+    for (index, block) in import.blocks.into_iter().enumerate() {
+        let reason = if !timed(block.start_time, block.end_time) {
+            Some("times must be non-negative numbers with end_time not before start_time")
+        } else if !block.words.iter().all(|w| timed(w.start_time, w.end_time)) {
+            Some("word times must be non-negative numbers with end_time not before start_time")
+        } else if block.words.iter().any(|w| w.confidence.is_some_and(|c| !(0.0..=1.0).contains(&c))) {
+            Some("word confidence must be between 0 and 1")
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => errors.push(CueError {
+                cue: index + 1,
+                line: None,
+                reason: reason.to_string(),
+            }),
+            None if block.text.trim().is_empty() => {}
+            None => cues.push(ParsedCue {
+                start_time: block.start_time,
+                end_time: block.end_time,
+                text: block.text.trim().to_string(),
+                words: block.words,
+            }),
+        }
+    }
+
+    if errors.is_empty() { Ok(cues) } else { Err(errors) }
+}
+
+/// Helper: Summarize cue errors for a 400 response
+fn describe_errors(format: ImportFormat, errors: &[CueError]) -> String {
+    let mut message = format!(
+        "Invalid {} file: {}",
+        format.name(),
+        errors
+            .iter()
+            .take(MAX_REPORTED_ERRORS)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ")
+    );
+    if errors.len() > MAX_REPORTED_ERRORS {
+        message.push_str(&format!(" (and {} more)", errors.len() - MAX_REPORTED_ERRORS));
+    }
+    message
+}
+
+/// Import an existing SRT, WebVTT or JSON transcript, replacing the video's transcript blocks
+///
+/// Send the file in a `file` field. Its format comes from an optional `format` field
+/// (`srt`, `vtt` or `json`), else from the file name or contents. Nothing is saved
+/// unless every cue is valid; the replacement can be undone like any other change.
+/// Imports are refused while the video is being transcribed, and a cancelled or failed
+/// transcription is not resumed after one.
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/transcript/import",
+    params(("id" = String, Path, description = "Video ID")),
+    request_body(content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "The imported transcript blocks", body = Vec<TranscriptBlock>),
+        (status = 400, description = "Missing or oversized file, unknown format, or invalid cues with their line numbers"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 409, description = "A transcription job is queued or running"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn import_transcript(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    // The job would keep appending its blocks after the imported ones
+    let latest = state.db.get_latest_transcription_job(&video_id).await?;
+    if latest.is_some_and(|job| !job.status.is_finished()) {
+        return Err(AppError::Conflict(
+            "Video is being transcribed; cancel the transcription before importing".to_string(),
+        ));
+    }
+
+    let mut format = None;
+    let mut file = None;
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        warn!(error = %e, "Failed to read multipart field");
+        AppError::BadRequest(format!("Failed to read multipart: {}", e))
+    })? {
+        match field.name().unwrap_or("") {
+            "format" => {
+                let value = field
+                    .text()
+                    .await
+                    .map_err(|e| AppError::BadRequest(format!("Failed to read format: {}", e)))?;
+                format = Some(
+                    ImportFormat::from_name(&value)
+                        .ok_or_else(|| AppError::BadRequest(format!("Unknown import format '{}'", value.trim())))?,
+                );
+            }
+            "file" => {
+                let filename = field.file_name().unwrap_or("").to_string();
+                let bytes = field
+                    .bytes()
+                    .await
+                    .map_err(|e| AppError::BadRequest(format!("Failed to read file: {}", e)))?;
+                let contents = String::from_utf8(bytes.into())
+                    .map_err(|_| AppError::BadRequest("Transcript file must be UTF-8 text".to_string()))?;
+                file = Some((filename, contents));
+            }
+            _ => {}
+        }
+    }
+    let Some((filename, contents)) = file else {
+        return Err(AppError::BadRequest("No transcript file provided".to_string()));
+    };
+    let format = format.unwrap_or_else(|| ImportFormat::detect(&filename, &contents));
+
+    let parsed = match format {
+        ImportFormat::Srt | ImportFormat::Vtt => parse_captions(&contents, format),
+        ImportFormat::Json => parse_json(&contents),
+    };
+    let cues = parsed.map_err(|errors| {
+        warn!(video_id = %video_id, errors = errors.len(), "Rejected transcript import");
+        AppError::BadRequest(describe_errors(format, &errors))
+    })?;
+
+    let blocks: Vec<TranscriptBlock> = cues
+        .into_iter()
+        .enumerate()
+        .map(|(ordering, cue)| {
+            let mut block = TranscriptBlock::new(video_id.clone(), cue.start_time, cue.end_time, cue.text, ordering as i64);
+            block.set_words(cue.words);
+            block
+        })
+        .collect();
+    state
+        .db
+        .replace_transcript_blocks(&video_id, &blocks, &auth_user.user_id)
+        .await?;
+
+    info!(
+        video_id = %video_id,
+        format = ?format,
+        filename = %filename,
+        blocks = blocks.len(),
+        "Imported transcript"
+    );
+
+    Ok((StatusCode::OK, Json(blocks)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("01:02:03,456"), Some(3723.456));
+        assert_eq!(parse_timestamp("02:03.5"), Some(123.5));
+        assert_eq!(parse_timestamp("00:00:07"), Some(7.0));
+        assert_eq!(parse_timestamp("00:61:00,000"), None);
+        assert_eq!(parse_timestamp("00:00:0x,000"), None);
+        assert_eq!(parse_timestamp("12"), None);
+        assert_eq!(parse_timestamp("99999999999999999:00:00,000"), None);
+    }
+
+    #[test]
+    fn test_clean_cue_text() {
+        assert_eq!(clean_cue_text(&["<b>a</b> < b", "{laughs"]), "a < b {laughs");
+        assert_eq!(clean_cue_text(&["x <i>y", "{\\an8}z > 1"]), "x y z > 1");
+        assert_eq!(clean_cue_text(&["a < b <i>c</i>"]), "a < b c");
+    }
+
+    #[test]
+    fn test_parse_srt() {
+        let srt = "\u{feff}1\r\n00:00:01,000 --> 00:00:02,500\r\n<i>Namo</i> tassa\r\nbhagavato\r\n\r\n2\r\n00:00:03,000 --> 00:00:04,000\r\n{\\an8}Arahato &amp; more\r\n";
+        let cues = parse_captions(srt, ImportFormat::Srt).unwrap();
+        assert_eq!(cues.len(), 2);
+        assert_eq!((cues[0].start_time, cues[0].end_time), (1.0, 2.5));
+        assert_eq!(cues[0].text, "Namo tassa bhagavato");
+        assert_eq!(cues[1].text, "Arahato & more");
+    }
+
+    #[test]
+    fn test_parse_vtt() {
+        let vtt = "WEBVTT - talk\n\nNOTE rough captions\n\nintro\n00:01.000 --> 00:02.000 align:start\n<v Ajahn>Welcome</v>\n\n00:00:03.000 --> 00:00:04.000\nBack.\n";
+        let cues = parse_captions(vtt, ImportFormat::Vtt).unwrap();
+        let texts: Vec<&str> = cues.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, vec!["Welcome", "Back."]);
+
+        let errors = parse_captions("00:01.000 --> 00:02.000\nHi\n", ImportFormat::Vtt).unwrap_err();
+        assert_eq!(errors[0].line, Some(1));
+    }
+
+    #[test]
+    fn test_errors_name_cue_and_line() {
+        let srt = "1\n00:00:01,000 --> 00:00:02,000\nFine\n\n2\n00:00:05,000 --> 00:00:04,000\nBackwards\n\n3\nNo timing here\n\n4\n00:00:0x,000 --> 00:00:09,000\nBad\n";
+        let errors = parse_captions(srt, ImportFormat::Srt).unwrap_err();
+        let described: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            described,
+            vec![
+                "cue 2 (line 6): end time is before start time",
+                "cue 3 (line 10): expected a timing line like 00:00:01,000 --> 00:00:02,000",
+                "cue 4 (line 13): invalid start time '00:00:0x,000'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let json = r#"{"blocks": [
+            {"start_time": 0.0, "end_time": 1.5, "text": "Evam me sutam", "words": [{"start_time": 0.0, "end_time": 0.5, "text": "Evam", "confidence": 0.9}]},
+            {"startTime": 2.0, "endTime": 3.0, "text": "ekam samayam"}
+        ]}"#;
+        let cues = parse_json(json).unwrap();
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].words.len(), 1);
+        assert_eq!(cues[1].start_time, 2.0);
+
+        let errors = parse_json(r#"{"blocks": [{"start_time": 3.0, "end_time": 1.0, "text": "x"}]}"#).unwrap_err();
+        assert_eq!(errors[0].to_string(), "cue 1: times must be non-negative numbers with end_time not before start_time");
+        let errors = parse_json("{\n\"blocks\": [\n{\"start_time\": \"soon\"}]}").unwrap_err();
+        assert_eq!(errors[0].line, Some(3));
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(ImportFormat::detect("talk.VTT", ""), ImportFormat::Vtt);
+        assert_eq!(ImportFormat::detect("talk.txt", "WEBVTT\n"), ImportFormat::Vtt);
+        assert_eq!(ImportFormat::detect("", " {\"blocks\": []}"), ImportFormat::Json);
+        assert_eq!(ImportFormat::detect("captions", "1\n00:00:01,000 --> 00:00:02,000\n"), ImportFormat::Srt);
+    }
+}
@@ -0,0 +1,524 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use futures_util::TryStreamExt;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+
+use crate::{
+    auth::AuthUser,
+    chunking::prepare_chunks,
+    db::{ChunkStatus, JobStatus, TranscriptBlock, TranscriptionJob},
+    error::AppError,
+    glossary::load_glossary,
+    language::detect_video_language,
+    messages::{ProgressEvent, ServerMessage},
+    speakers::assign_from_spans,
+    transcriber::{TranscriberError, TranscriptionRequest},
+    upload::{copy_to_temp_file, get_owned_video, AppState},
+};
+
+/// Cancellation handles of the jobs running in this process
+#[derive(Default)]
+pub struct JobRegistry {
+    tokens: Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl JobRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn register(&self, job_id: &str) -> CancellationToken {
+        let token = CancellationToken::new();
+        self.tokens
+            .lock()
+            .unwrap()
+            .insert(job_id.to_string(), token.clone());
+        token
+    }
+
+    fn remove(&self, job_id: &str) {
+        self.tokens.lock().unwrap().remove(job_id);
+    }
+
+    /// Ask a running job to stop after its current chunk. Returns false if it isn't running here.
+    pub fn cancel(&self, job_id: &str) -> bool {
+        match self.tokens.lock().unwrap().get(job_id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Create a queued transcription job for a video and start it in the background
+///
+/// Fails with a conflict if the video already has a queued or running job.
+pub async fn start_job(
+    state: Arc<AppState>,
+    video_id: &str,
+    user_id: &str,
+) -> Result<TranscriptionJob, AppError> {
+    let job = TranscriptionJob::new(video_id.to_string(), user_id.to_string());
+    if !state.db.insert_transcription_job(&job).await? {
+        return Err(AppError::Conflict(
+            "A transcription job is already in progress for this video".to_string(),
+        ));
+    }
+
+    info!(job_id = %job.id, video_id = %video_id, "Queued transcription job");
+
+    spawn_job(state, job.id.clone());
+    Ok(job)
+}
+
+/// Run a job in a background task, recording failures on the job
+pub fn spawn_job(state: Arc<AppState>, job_id: String) {
+    let cancel = state.jobs.register(&job_id);
+
+    tokio::spawn(async move {
+        let result = run_job(&state, &job_id, &cancel).await;
+        state.jobs.remove(&job_id);
+
+        match result {
+            Ok(()) => {}
+            // Errors from work abandoned by a cancel don't make the job fail
+            Err(e) if cancel.is_cancelled() => {
+                info!(job_id = %job_id, error = %e, "Cancelled transcription job stopped with an error");
+            }
+            Err(e) => {
+                error!(job_id = %job_id, error = %e, "Transcription job failed");
+                // Only the user-facing message is kept, as it is shown in the UI and the progress feed
+                if let Err(e) = state
+                    .db
+                    .update_transcription_job_status(&job_id, JobStatus::Failed, Some(&e.user_message()))
+                    .await
+                {
+                    error!(job_id = %job_id, error = %e, "Failed to mark job as failed");
+                }
+                publish_job_progress(&state, &job_id).await;
+            }
+        }
+    });
+}
+
+/// Restart every job that was queued or running when the server stopped
+///
+/// Chunks finished before the restart are skipped, so jobs continue from the
+/// first unfinished chunk. Returns the number of resumed jobs.
+pub async fn resume_unfinished_jobs(state: Arc<AppState>) -> Result<usize, AppError> {
+    let jobs = state.db.list_unfinished_transcription_jobs().await?;
+
+    for job in &jobs {
+        info!(job_id = %job.id, video_id = %job.video_id, "Resuming transcription job");
+        spawn_job(state.clone(), job.id.clone());
+    }
+
+    Ok(jobs.len())
+}
+
+/// Cancel a job: it is marked cancelled right away and stops before its next chunk
+///
+/// Blocks of chunks that already finished are kept.
+pub async fn cancel_job(state: &AppState, job_id: &str) -> Result<(), AppError> {
+    if !state
+        .db
+        .update_transcription_job_status(job_id, JobStatus::Cancelled, None)
+        .await?
+    {
+        return Err(AppError::Conflict(format!(
+            "Transcription job {} already finished",
+            job_id
+        )));
+    }
+
+    if !state.jobs.cancel(job_id) {
+        info!(job_id = %job_id, "Cancelled job that was not running in this process");
+    }
+    publish_job_progress(state, job_id).await;
+
+    Ok(())
+}
+
+/// Publish a job's status and chunk progress to its owner's progress feed
+///
+/// Progress is best effort: lookup errors are logged and don't affect the job.
+async fn publish_job_progress(state: &AppState, job_id: &str) {
+    let progress = async {
+        let job = state.db.get_transcription_job(job_id).await?;
+        let counts = state.db.count_job_chunks(job_id).await?;
+        Ok::<_, sqlx::Error>(job.map(|job| (job, counts)))
+    };
+
+    let (job, (chunks_total, chunks_completed)) = match progress.await {
+        Ok(Some(progress)) => progress,
+        Ok(None) => return,
+        Err(e) => {
+            warn!(job_id = %job_id, error = %e, "Failed to load job progress");
+            return;
+        }
+    };
+
+    let event = match job.status {
+        JobStatus::Failed => ProgressEvent::TranscriptionFailed {
+            video_id: job.video_id,
+            job_id: job.id,
+            error: job.error.unwrap_or_default(),
+        },
+        status => ProgressEvent::TranscriptionProgress {
+            video_id: job.video_id,
+            job_id: job.id,
+            status,
+            chunks_completed,
+            chunks_total,
+            percent: if chunks_total > 0 {
+                chunks_completed as f64 * 100.0 / chunks_total as f64
+            } else {
+                0.0
+            },
+        },
+    };
+
+    state.events.publish_progress(&job.user_id, event);
+}
+
+/// Transcribe a video chunk by chunk, persisting each chunk's blocks as it completes
+///
+/// Cancellation is checked between chunks; an in-flight chunk is abandoned without
+/// storing its blocks.
+async fn run_job(state: &Arc<AppState>, job_id: &str, cancel: &CancellationToken) -> Result<(), AppError> {
+    let job = state
+        .db
+        .get_transcription_job(job_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Transcription job {} not found", job_id)))?;
+
+    if job.status.is_finished() {
+        warn!(job_id = %job_id, status = ?job.status, "Skipping finished transcription job");
+        return Ok(());
+    }
+
+    // A cancel may have landed since the job was read
+    if !state
+        .db
+        .update_transcription_job_status(job_id, JobStatus::Running, None)
+        .await?
+    {
+        info!(job_id = %job_id, "Transcription job finished before it started running");
+        return Ok(());
+    }
+
+    let video = state
+        .db
+        .get_video(&job.video_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Video not found".to_string()))?;
+
+    // Chunks are extracted once per video and reused by later jobs
+    let mut audio_chunks = state.db.list_audio_chunks(&video.id).await?;
+    let mut job_chunks = state.db.list_job_chunks(job_id).await?;
+
+    if job_chunks.is_empty() {
+        if audio_chunks.is_empty() {
+            audio_chunks = prepare_chunks(state, &video).await?;
+        }
+        // Chunks transcribed by an earlier cancelled or failed job already have their blocks
+        let transcribed = state.db.list_transcribed_chunk_indexes(&video.id).await?;
+        let indexes: Vec<i64> = audio_chunks.iter().map(|c| c.chunk_index).collect();
+        state.db.insert_job_chunks(job_id, &indexes, &transcribed).await?;
+        job_chunks = state.db.list_job_chunks(job_id).await?;
+    }
+
+    publish_job_progress(state, job_id).await;
+
+    let audio_chunks: HashMap<i64, _> = audio_chunks
+        .into_iter()
+        .map(|chunk| (chunk.chunk_index, chunk))
+        .collect();
+
+    // The video's language is passed to the engine; without one it is detected on the first chunk
+    let language = match (&video.language, audio_chunks.values().min_by_key(|c| c.chunk_index)) {
+        (Some(language), _) => Some(language.clone()),
+        (None, Some(first_chunk)) => detect_video_language(state, &video, first_chunk).await,
+        (None, None) => None,
+    };
+
+    // The owner's glossary biases the engine and fixes near-miss spellings in its output
+    let (glossary, prompt) = load_glossary(state, &video.user_id).await?;
+
+    let total = job_chunks.len();
+    let pending: Vec<_> = job_chunks
+        .into_iter()
+        .filter(|c| c.status == ChunkStatus::Pending)
+        .collect();
+
+    info!(
+        job_id = %job_id,
+        video_id = %video.id,
+        total_chunks = total,
+        pending_chunks = pending.len(),
+        "Running transcription job"
+    );
+
+    for job_chunk in pending {
+        let chunk = audio_chunks.get(&job_chunk.chunk_index).ok_or_else(|| {
+            AppError::Internal(format!("Audio chunk {} is missing", job_chunk.chunk_index))
+        })?;
+
+        if cancel.is_cancelled() {
+            info!(job_id = %job_id, chunk_index = chunk.chunk_index, "Transcription job cancelled");
+            return Ok(());
+        }
+
+        state.events.publish(
+            &video.id,
+            ServerMessage::ChunkStarted {
+                chunk_index: chunk.chunk_index,
+                start: chunk.trimmed_start,
+                end: chunk.trimmed_end,
+            },
+        );
+
+        let audio = copy_to_temp_file(&state.filestore, &chunk.file_id).await?;
+
+        // Blocks get their IDs as segments arrive, so clients can show the text
+        // before the chunk is stored
+        let transcription = async {
+            let mut segments = state
+                .transcriber
+                .transcribe(TranscriptionRequest {
+                    audio_path: audio.to_path_buf(),
+                    time_offset: chunk.trimmed_start,
+                    language: language.clone(),
+                    prompt: prompt.clone(),
+                })
+                .await?;
+
+            let mut blocks = Vec::new();
+            while let Some(mut segment) = segments.try_next().await? {
+                glossary.normalize_segment(&mut segment);
+                let mut block = TranscriptBlock::new(video.id.clone(), segment.start, segment.end, segment.text, 0);
+                block.set_words(segment.words);
+                state.events.publish(
+                    &video.id,
+                    ServerMessage::BlockTextDelta {
+                        block_id: block.id.clone(),
+                        chunk_index: chunk.chunk_index,
+                        start: block.start_time,
+                        end: block.end_time,
+                        text: block.text.clone(),
+                    },
+                );
+                blocks.push(block);
+            }
+            Ok::<_, TranscriberError>(blocks)
+        };
+
+        let mut blocks = tokio::select! {
+            blocks = transcription => blocks?,
+            _ = cancel.cancelled() => {
+                info!(job_id = %job_id, chunk_index = chunk.chunk_index, "Transcription job cancelled mid-chunk");
+                return Ok(());
+            }
+        };
+
+        // Speaker turns are read per chunk, as diarization may finish while the job runs
+        let speaker_spans = state.db.list_speaker_spans(&video.id).await?;
+        assign_from_spans(&speaker_spans, &mut blocks);
+        let blocks = state
+            .db
+            .complete_job_chunk(job_id, chunk.chunk_index, blocks, &job.user_id)
+            .await?;
+
+        let (chunks_total, chunks_completed) = state.db.count_job_chunks(job_id).await?;
+        state.events.publish(
+            &video.id,
+            ServerMessage::ChunkCompleted {
+                chunk_index: chunk.chunk_index,
+                chunks_completed,
+                chunks_total,
+            },
+        );
+        publish_job_progress(state, job_id).await;
+
+        info!(
+            job_id = %job_id,
+            chunk_index = chunk.chunk_index,
+            blocks = blocks.len(),
+            "Transcribed chunk"
+        );
+    }
+
+    if cancel.is_cancelled() {
+        return Ok(());
+    }
+
+    if !state
+        .db
+        .update_transcription_job_status(job_id, JobStatus::Done, None)
+        .await?
+    {
+        return Ok(());
+    }
+    publish_job_progress(state, job_id).await;
+
+    info!(job_id = %job_id, video_id = %video.id, "Transcription job complete");
+
+    Ok(())
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TranscriptionStatusResponse {
+    pub job_id: String,
+    pub video_id: String,
+    pub status: JobStatus,
+    pub chunks_total: i64,
+    pub chunks_completed: i64,
+    pub error: Option<String>,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: DateTime<Utc>,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Helper: Build the status response of a job with its chunk progress
+async fn job_status(state: &AppState, job: TranscriptionJob) -> Result<TranscriptionStatusResponse, AppError> {
+    let (chunks_total, chunks_completed) = state.db.count_job_chunks(&job.id).await?;
+
+    Ok(TranscriptionStatusResponse {
+        job_id: job.id,
+        video_id: job.video_id,
+        status: job.status,
+        chunks_total,
+        chunks_completed,
+        error: job.error,
+        created_at: job.created_at,
+        updated_at: job.updated_at,
+    })
+}
+
+/// Helper: Get the latest job of a video, if any
+async fn latest_job(state: &AppState, video_id: &str) -> Result<TranscriptionJob, AppError> {
+    state
+        .db
+        .get_latest_transcription_job(video_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No transcription job for this video".to_string()))
+}
+
+/// Start transcribing a video in the background
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/transcription",
+    params(("id" = String, Path, description = "Video ID")),
+    responses(
+        (status = 202, description = "Transcription job queued", body = TranscriptionStatusResponse),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 409, description = "A transcription job is already queued or running, or the video was already fully transcribed or has an imported transcript"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcription"
+)]
+pub async fn start_transcription(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    // A new run would append a second copy of every block. Cancelled and failed
+    // runs are continued instead, from the chunks they didn't finish, unless their
+    // blocks were since replaced by an import.
+    let latest = state.db.get_latest_transcription_job(&video_id).await?;
+    let resumable = latest
+        .as_ref()
+        .is_some_and(|job| matches!(job.status, JobStatus::Cancelled | JobStatus::Failed))
+        && !state.db.list_transcribed_chunk_indexes(&video_id).await?.is_empty();
+    if !resumable && !state.db.list_transcript_blocks(&video_id).await?.is_empty() {
+        return Err(AppError::Conflict(
+            "Video already has a transcript; retranscribe its blocks instead".to_string(),
+        ));
+    }
+
+    let job = start_job(state.clone(), &video_id, &auth_user.user_id).await?;
+
+    Ok((StatusCode::ACCEPTED, Json(job_status(&state, job).await?)))
+}
+
+/// Get the state and chunk progress of a video's latest transcription job
+#[utoipa::path(
+    get,
+    path = "/api/videos/{id}/transcription",
+    params(("id" = String, Path, description = "Video ID")),
+    responses(
+        (status = 200, description = "Latest transcription job", body = TranscriptionStatusResponse),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found or never transcribed"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcription"
+)]
+pub async fn get_transcription(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let job = latest_job(&state, &video_id).await?;
+
+    Ok((StatusCode::OK, Json(job_status(&state, job).await?)))
+}
+
+/// Cancel a video's queued or running transcription job
+///
+/// Blocks from chunks that already finished stay in the transcript.
+#[utoipa::path(
+    delete,
+    path = "/api/videos/{id}/transcription",
+    params(("id" = String, Path, description = "Video ID")),
+    responses(
+        (status = 200, description = "Transcription job cancelled", body = TranscriptionStatusResponse),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found or never transcribed"),
+        (status = 409, description = "Transcription job already finished"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcription"
+)]
+pub async fn cancel_transcription(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let job = latest_job(&state, &video_id).await?;
+    if job.status.is_finished() {
+        return Err(AppError::Conflict(format!(
+            "Transcription job {} already finished",
+            job.id
+        )));
+    }
+
+    cancel_job(&state, &job.id).await?;
+
+    info!(job_id = %job.id, video_id = %video_id, "Cancelled transcription job");
+
+    let job = latest_job(&state, &video_id).await?;
+    Ok((StatusCode::OK, Json(job_status(&state, job).await?)))
+}
@@ -0,0 +1,144 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::{
+    auth::AuthUser,
+    db::{AudioChunk, Video},
+    error::AppError,
+    transcriber::Result,
+    upload::{copy_to_temp_file, get_owned_video, AppState},
+};
+
+/// Trait for spoken-language detection backends
+#[async_trait::async_trait]
+pub trait LanguageDetector: Send + Sync {
+    /// Detect the language of an audio file as a code like "en", or None if undecided
+    async fn detect_language(&self, audio_path: &std::path::Path) -> Result<Option<String>>;
+}
+
+/// Detector that reports the same language for every file
+pub struct MockLanguageDetector {
+    language: String,
+}
+
+impl MockLanguageDetector {
+    pub fn new(language: &str) -> Self {
+        Self {
+            language: language.to_string(),
+        }
+    }
+}
+
+impl Default for MockLanguageDetector {
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+#[async_trait::async_trait]
+impl LanguageDetector for MockLanguageDetector {
+    async fn detect_language(&self, _audio_path: &std::path::Path) -> Result<Option<String>> {
+        Ok(Some(self.language.clone()))
+    }
+}
+
+/// Normalize a language code to lowercase, rejecting anything that isn't 2-3 letters
+pub fn normalize_language(language: &str) -> std::result::Result<String, AppError> {
+    let language = language.trim().to_ascii_lowercase();
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_lowercase()) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid language code '{}', expected a code like \"en\" or \"hi\"",
+            language
+        )));
+    }
+    Ok(language)
+}
+
+/// Detect a video's language from its first audio chunk and store it
+///
+/// Chunks are at most a minute long, so this listens to roughly the first minute.
+/// Detection is best effort: failures are logged and transcription falls back to
+/// the engine's own detection.
+pub async fn detect_video_language(state: &AppState, video: &Video, first_chunk: &AudioChunk) -> Option<String> {
+    let detection = async {
+        let audio = copy_to_temp_file(&state.filestore, &first_chunk.file_id).await?;
+        let language = state.language_detector.detect_language(&audio).await?;
+        if let Some(language) = &language {
+            state.db.update_video_language(&video.id, Some(language)).await?;
+        }
+        Ok::<_, AppError>(language)
+    };
+
+    match detection.await {
+        Ok(language) => {
+            info!(video_id = %video.id, language = ?language, "Detected video language");
+            language
+        }
+        Err(e) => {
+            warn!(video_id = %video.id, error = %e, "Language detection failed, continuing without a hint");
+            None
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateLanguageRequest {
+    /// Language code like "en", or null to detect it on the next transcription
+    pub language: Option<String>,
+}
+
+/// Set the spoken language of a video
+#[utoipa::path(
+    put,
+    path = "/api/videos/{id}/language",
+    params(("id" = String, Path, description = "Video ID")),
+    request_body = UpdateLanguageRequest,
+    responses(
+        (status = 200, description = "Video with its new language", body = Video),
+        (status = 400, description = "Invalid language code"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "videos"
+)]
+pub async fn update_language(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+    Json(req): Json<UpdateLanguageRequest>,
+) -> std::result::Result<impl IntoResponse, AppError> {
+    let mut video = get_owned_video(&state, &video_id, &auth_user).await?;
+
+    video.language = req.language.as_deref().map(normalize_language).transpose()?;
+    state
+        .db
+        .update_video_language(&video_id, video.language.as_deref())
+        .await?;
+
+    info!(video_id = %video_id, language = ?video.language, "Updated video language");
+
+    Ok((StatusCode::OK, Json(video)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_language() {
+        assert_eq!(normalize_language(" EN ").unwrap(), "en");
+        assert_eq!(normalize_language("haw").unwrap(), "haw");
+        assert!(normalize_language("english").is_err());
+        assert!(normalize_language("e1").is_err());
+        assert!(normalize_language("").is_err());
+    }
+}
@@ -15,11 +15,31 @@ use tracing::info;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
 pub mod messages;
+pub mod burn_in;
+pub mod chunking;
 pub mod filestore;
+pub mod jobs;
+pub mod language;
 pub mod db;
+pub mod diarizer;
+pub mod events;
+pub mod export;
+pub mod glossary;
+pub mod import;
 pub mod upload;
 pub mod auth;
 pub mod session_store;
+pub mod replace;
+pub mod retranscribe;
+pub mod revisions;
+pub mod review;
+pub mod search;
+pub mod silence;
+pub mod speakers;
+pub mod transcript;
+pub mod transcriber;
+pub mod transliterate;
+pub mod whisper;
 pub mod websocket;
 pub mod error;
 pub mod test_data;
@@ -58,6 +78,46 @@ pub fn create_router(
         .routes(routes!(upload::upload_video))
         .routes(routes!(upload::get_user_videos))
         .routes(routes!(upload::stream_video))
+        .routes(routes!(language::update_language))
+        .routes(routes!(silence::get_silences, silence::analyze_silences))
+        .routes(routes!(jobs::start_transcription, jobs::get_transcription, jobs::cancel_transcription))
+        .routes(routes!(transcript::list_blocks))
+        .routes(routes!(transcript::create_block))
+        .routes(routes!(transcript::update_block, transcript::delete_block))
+        .routes(routes!(transcript::split_block))
+        .routes(routes!(transcript::merge_blocks))
+        .routes(routes!(transcript::list_low_confidence_words))
+        .routes(routes!(export::export_transcript))
+        .routes(routes!(export::caption_track))
+        .routes(routes!(burn_in::start_burn_in, burn_in::get_burn_in))
+        .routes(routes!(burn_in::download_burn_in))
+        // Caption files are small, so imports don't get the upload-sized body limit
+        .merge(
+            OpenApiRouter::new()
+                .routes(routes!(import::import_transcript))
+                .layer(DefaultBodyLimit::max(import::MAX_IMPORT_BYTES)),
+        )
+        .routes(routes!(review::get_review_queue))
+        .routes(routes!(review::mark_reviewed, review::unmark_reviewed))
+        .routes(routes!(speakers::list_speakers, speakers::create_speaker))
+        .routes(routes!(speakers::update_speaker, speakers::delete_speaker))
+        .routes(routes!(speakers::merge_speakers))
+        .routes(routes!(speakers::assign_speaker))
+        .routes(routes!(speakers::diarize))
+        .routes(routes!(transliterate::transliterate_transcript))
+        .routes(routes!(replace::find_replace))
+        .routes(routes!(revisions::list_history))
+        .routes(routes!(revisions::list_block_history))
+        .routes(routes!(revisions::restore_revision))
+        .routes(routes!(revisions::undo))
+        .routes(routes!(retranscribe::retranscribe_block))
+        .routes(routes!(retranscribe::retranscribe_range))
+        .routes(routes!(glossary::list_terms, glossary::create_term))
+        .routes(routes!(glossary::update_term, glossary::delete_term))
+        .routes(routes!(glossary::get_prompt))
+        .routes(routes!(transliterate::transliterate_text))
+        .routes(routes!(search::search))
+        .routes(routes!(events::progress_events))
         .routes(routes!(auth::register))
         .routes(routes!(auth::login))
         .routes(routes!(auth::logout))
@@ -115,6 +175,7 @@ pub fn create_router(
                     axum::http::Method::GET,
                     axum::http::Method::POST,
                     axum::http::Method::PUT,
+                    axum::http::Method::PATCH,
                     axum::http::Method::DELETE,
                     axum::http::Method::OPTIONS,
                 ])
@@ -250,8 +311,12 @@ pub async fn start_server(
     filestore_path: Option<PathBuf>,
 ) -> Result<(tokio::task::JoinHandle<Result<(), std::io::Error>>, Arc<AppState>), Box<dyn std::error::Error>> {
     use db::Database;
+    use diarizer::{Diarizer, MockDiarizer};
     use filestore::LocalFileStore;
     use session_store::InMemorySessionStore;
+    use language::{LanguageDetector, MockLanguageDetector};
+    use transcriber::{MockTranscriber, Transcriber};
+    use whisper::{WhisperCppConfig, WhisperCppTranscriber};
 
     // Load environment variables if not provided
     dotenvy::dotenv().ok();
@@ -305,17 +370,60 @@ pub async fn start_server(
     let session_store = InMemorySessionStore::new();
     info!("Session store initialized");
 
+    // Initialize transcriber (TRANSCRIBER=mock skips whisper.cpp for local development)
+    // whisper.cpp also detects the language of videos that don't have one set
+    let (transcriber, language_detector): (Arc<dyn Transcriber>, Arc<dyn LanguageDetector>) =
+        match std::env::var("TRANSCRIBER").as_deref() {
+            Ok("mock") => {
+                info!("Using mock transcriber");
+                (Arc::new(MockTranscriber::new()), Arc::new(MockLanguageDetector::default()))
+            }
+            _ => {
+                let config = WhisperCppConfig::from_env();
+                info!(
+                    binary_path = ?config.binary_path,
+                    model_path = ?config.model_path,
+                    "Using whisper.cpp transcriber"
+                );
+                let whisper = Arc::new(WhisperCppTranscriber::new(config));
+                (whisper.clone(), whisper)
+            }
+        };
+
+    // Speaker diarization is opt-in (DIARIZER=mock uses canned turns for local development)
+    let diarizer: Option<Arc<dyn Diarizer>> = match std::env::var("DIARIZER").as_deref() {
+        Ok("mock") => {
+            info!("Using mock diarizer");
+            Some(Arc::new(MockDiarizer::new()))
+        }
+        _ => {
+            info!("Speaker diarization disabled");
+            None
+        }
+    };
+
     // Create app state
     let state = Arc::new(AppState {
         db,
         filestore: Arc::new(filestore),
         session_store: Arc::new(session_store),
+        transcriber,
+        language_detector,
+        diarizer,
+        jobs: jobs::JobRegistry::new(),
+        events: events::EventBus::new(),
     });
 
     // Spawn background persistence task
     info!("Spawning session persistence task");
     spawn_persistence_task(state.clone());
 
+    // Pick up transcription jobs interrupted by a restart
+    let resumed = jobs::resume_unfinished_jobs(state.clone()).await?;
+    info!(count = resumed, "Resumed unfinished transcription jobs");
+    let restarted = burn_in::resume_unfinished_burn_jobs(state.clone()).await?;
+    info!(count = restarted, "Restarted unfinished caption burn-in jobs");
+
     let (router, _api) = create_router(state.clone(), Some(frontend_path));
 
     let addr = format!("0.0.0.0:{}", port);
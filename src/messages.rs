@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
+use crate::db::JobStatus;
+
 // ============================================================================
 // Shared Session State (Single Source of Truth)
 // ============================================================================
@@ -67,6 +69,31 @@ pub enum ServerMessage {
         height: Option<i64>,
         duration_seconds: Option<f64>,
     },
+    /// Transcription of an audio chunk began; times are in seconds of the video
+    ChunkStarted {
+        #[ts(type = "number")]
+        chunk_index: i64,
+        start: f64,
+        end: f64,
+    },
+    /// Text of a new transcript block as it arrives from the engine
+    BlockTextDelta {
+        block_id: String,
+        #[ts(type = "number")]
+        chunk_index: i64,
+        start: f64,
+        end: f64,
+        text: String,
+    },
+    /// A chunk's blocks were saved and can be fetched and edited
+    ChunkCompleted {
+        #[ts(type = "number")]
+        chunk_index: i64,
+        #[ts(type = "number")]
+        chunks_completed: i64,
+        #[ts(type = "number")]
+        chunks_total: i64,
+    },
 }
 
 /// Messages sent from client to server
@@ -83,3 +110,41 @@ pub enum ClientMessage {
     /// Authoritative state sync from client (used when client wins conflict resolution)
     SyncState(SessionState),
 }
+
+// ============================================================================
+// Server → Client Progress Feed (SSE)
+// ============================================================================
+
+/// Progress of background work on any of the user's videos
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(tag = "type")]
+#[ts(export, export_to = "../frontend/src/types/")]
+pub enum ProgressEvent {
+    /// Upload received, video is being optimized and analyzed
+    UploadProcessing { video_id: String, filename: String },
+    /// Processing finished and the video is playable
+    UploadReady { video_id: String },
+    UploadFailed {
+        video_id: String,
+        filename: String,
+        error: String,
+    },
+    /// Status or chunk progress of a transcription job changed
+    TranscriptionProgress {
+        video_id: String,
+        job_id: String,
+        #[ts(type = "\"queued\" | \"running\" | \"cancelled\" | \"failed\" | \"done\"")]
+        status: JobStatus,
+        #[ts(type = "number")]
+        chunks_completed: i64,
+        #[ts(type = "number")]
+        chunks_total: i64,
+        /// 0-100
+        percent: f64,
+    },
+    TranscriptionFailed {
+        video_id: String,
+        job_id: String,
+        error: String,
+    },
+}
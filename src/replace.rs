@@ -0,0 +1,405 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json as SqlJson;
+use std::{collections::HashSet, ops::Range, sync::Arc};
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::{
+    auth::AuthUser,
+    db::{TranscriptBlock, TranscriptWord},
+    error::AppError,
+    upload::{get_owned_video, AppState},
+};
+
+/// How the search text of a find-and-replace is matched
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum MatchMode {
+    /// The text anywhere, as written
+    #[default]
+    Literal,
+    /// The text only where it isn't part of a longer word
+    WholeWord,
+    /// A regular expression; the replacement may refer to groups as `$1` or `${name}`
+    Regex,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct FindReplaceRequest {
+    /// Videos whose transcripts are searched
+    pub video_ids: Vec<String>,
+    pub find: String,
+    pub replace: String,
+    #[serde(default)]
+    pub mode: MatchMode,
+    #[serde(default)]
+    pub case_insensitive: bool,
+    /// Report what would change without saving anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffOp {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// A run of text that is kept, removed or added by a replacement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct DiffSegment {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReplacedBlock {
+    pub video_id: String,
+    pub block_id: String,
+    pub start_time: f64,
+    pub before: String,
+    pub after: String,
+    pub matches: usize,
+    /// Changes from `before` to `after`, in text order
+    pub diff: Vec<DiffSegment>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FindReplaceResponse {
+    /// True if nothing was saved
+    pub dry_run: bool,
+    /// Total number of replacements
+    pub matches: usize,
+    pub blocks: Vec<ReplacedBlock>,
+}
+
+/// A compiled find-and-replace
+pub struct Replacer {
+    regex: Regex,
+    replacement: String,
+    /// Whether `$` group references in the replacement are expanded
+    expand: bool,
+}
+
+/// The result of replacing in one text
+#[derive(Debug, PartialEq)]
+pub struct Replaced {
+    pub text: String,
+    pub matches: usize,
+    pub diff: Vec<DiffSegment>,
+    /// Byte range of each match in the original text, with its replacement
+    pub edits: Vec<(Range<usize>, String)>,
+}
+
+impl Replacer {
+    /// Compile a find-and-replace, rejecting empty or invalid patterns
+    pub fn new(find: &str, replace: &str, mode: MatchMode, case_insensitive: bool) -> Result<Self, AppError> {
+        if find.is_empty() {
+            return Err(AppError::BadRequest("Search text must not be empty".to_string()));
+        }
+
+        let pattern = match mode {
+            MatchMode::Literal => regex::escape(find),
+            MatchMode::WholeWord => {
+                // Boundaries only make sense next to word characters ("Dr." ends in punctuation)
+                let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+                let start = if is_word(find.chars().next()) { r"\b" } else { "" };
+                let end = if is_word(find.chars().last()) { r"\b" } else { "" };
+                format!("{}{}{}", start, regex::escape(find), end)
+            }
+            MatchMode::Regex => find.to_string(),
+        };
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(case_insensitive)
+            .build()
+            .map_err(|e| AppError::BadRequest(format!("Invalid regular expression: {}", e)))?;
+
+        // A pattern matching nothing would insert the replacement between every character
+        if regex.is_match("") {
+            return Err(AppError::BadRequest("Search pattern must not match empty text".to_string()));
+        }
+
+        Ok(Self {
+            regex,
+            replacement: replace.to_string(),
+            expand: mode == MatchMode::Regex,
+        })
+    }
+
+    /// Replace every match in `text`, or None if nothing matches
+    pub fn apply(&self, text: &str) -> Option<Replaced> {
+        let mut output = String::with_capacity(text.len());
+        let mut diff = Vec::new();
+        let mut edits = Vec::new();
+        let mut matches = 0;
+        let mut copied = 0;
+
+        for captures in self.regex.captures_iter(text) {
+            let found = captures.get(0).unwrap();
+            let mut replacement = String::new();
+            if self.expand {
+                captures.expand(&self.replacement, &mut replacement);
+            } else {
+                replacement.push_str(&self.replacement);
+            }
+
+            push_segment(&mut diff, DiffOp::Equal, &text[copied..found.start()]);
+            push_segment(&mut diff, DiffOp::Delete, found.as_str());
+            push_segment(&mut diff, DiffOp::Insert, &replacement);
+            output.push_str(&text[copied..found.start()]);
+            output.push_str(&replacement);
+            edits.push((found.range(), replacement));
+            copied = found.end();
+            matches += 1;
+        }
+
+        if matches == 0 {
+            return None;
+        }
+        push_segment(&mut diff, DiffOp::Equal, &text[copied..]);
+        output.push_str(&text[copied..]);
+
+        Some(Replaced {
+            text: output,
+            matches,
+            diff,
+            edits,
+        })
+    }
+}
+
+/// Apply the matches found in a block's text to the block's words
+///
+/// Words are located in `text` in order. A word takes the matches that lie within
+/// it; a word that a match only partly covers (one match spanning several words),
+/// or that can't be located, keeps its text.
+pub fn replace_words(text: &str, words: &[TranscriptWord], edits: &[(Range<usize>, String)]) -> Vec<TranscriptWord> {
+    let mut cursor = 0;
+    words
+        .iter()
+        .map(|word| {
+            let core = word.text.trim();
+            let Some(offset) = text[cursor..].find(core).filter(|_| !core.is_empty()) else {
+                return word.clone();
+            };
+            let span = cursor + offset..cursor + offset + core.len();
+            cursor = span.end;
+
+            let overlapping: Vec<&(Range<usize>, String)> = edits
+                .iter()
+                .filter(|(range, _)| range.start < span.end && range.end > span.start)
+                .collect();
+            if overlapping.is_empty()
+                || overlapping
+                    .iter()
+                    .any(|(range, _)| range.start < span.start || range.end > span.end)
+            {
+                return word.clone();
+            }
+
+            let mut replaced = word.text[..word.text.len() - word.text.trim_start().len()].to_string();
+            let mut copied = span.start;
+            for (range, replacement) in overlapping {
+                replaced.push_str(&text[copied..range.start]);
+                replaced.push_str(replacement);
+                copied = range.end;
+            }
+            replaced.push_str(&text[copied..span.end]);
+            replaced.push_str(&word.text[word.text.trim_end().len()..]);
+            TranscriptWord {
+                text: replaced,
+                ..word.clone()
+            }
+        })
+        .collect()
+}
+
+/// Helper: Append a diff segment, merging it into the previous one of the same kind
+fn push_segment(diff: &mut Vec<DiffSegment>, op: DiffOp, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    match diff.last_mut() {
+        Some(last) if last.op == op => last.text.push_str(text),
+        _ => diff.push(DiffSegment {
+            op,
+            text: text.to_string(),
+        }),
+    }
+}
+
+/// Find and replace text across the transcripts of one or more videos
+///
+/// Words are rewritten where a match lies within a single word; words covered by a
+/// match spanning several of them keep their text and timings. With
+/// `dry_run`, the affected blocks and their diffs are returned without saving;
+/// otherwise all blocks are updated in one transaction, which is refused if any
+/// of them was edited after it was read. `previous_text` is left as is.
+#[utoipa::path(
+    post,
+    path = "/api/transcripts/replace",
+    request_body = FindReplaceRequest,
+    responses(
+        (status = 200, description = "Affected blocks with their diffs", body = FindReplaceResponse),
+        (status = 400, description = "Empty or invalid search pattern"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 409, description = "A block was edited while replacing"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn find_replace(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Json(req): Json<FindReplaceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if req.video_ids.is_empty() {
+        return Err(AppError::BadRequest("At least one video is required".to_string()));
+    }
+    let replacer = Replacer::new(&req.find, &req.replace, req.mode, req.case_insensitive)?;
+
+    let mut blocks = Vec::new();
+    let mut rewrites: Vec<TranscriptBlock> = Vec::new();
+    let mut seen = HashSet::new();
+    for video_id in req.video_ids.iter().filter(|id| seen.insert(id.as_str())) {
+        get_owned_video(&state, video_id, &auth_user).await?;
+
+        for block in state.db.list_transcript_blocks(video_id).await? {
+            let Some(replaced) = replacer.apply(&block.text).filter(|r| r.text != block.text) else {
+                continue;
+            };
+            let words = replace_words(&block.text, &block.words, &replaced.edits);
+
+            rewrites.push(TranscriptBlock {
+                text: replaced.text.clone(),
+                words: SqlJson(words),
+                ..block.clone()
+            });
+            blocks.push(ReplacedBlock {
+                video_id: block.video_id,
+                block_id: block.id,
+                start_time: block.start_time,
+                before: block.text,
+                after: replaced.text,
+                matches: replaced.matches,
+                diff: replaced.diff,
+            });
+        }
+    }
+
+    if !req.dry_run && !state.db.rewrite_transcript_block_texts(&rewrites, &auth_user.user_id).await? {
+        return Err(AppError::Conflict(
+            "Transcript was edited during find and replace; try again".to_string(),
+        ));
+    }
+
+    let matches = blocks.iter().map(|b| b.matches).sum();
+    info!(
+        user_id = %auth_user.user_id,
+        videos = req.video_ids.len(),
+        mode = ?req.mode,
+        blocks = blocks.len(),
+        matches = matches,
+        dry_run = req.dry_run,
+        "Find and replace"
+    );
+
+    Ok((
+        StatusCode::OK,
+        Json(FindReplaceResponse {
+            dry_run: req.dry_run,
+            matches,
+            blocks,
+        }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replace(find: &str, replace: &str, mode: MatchMode, case_insensitive: bool, text: &str) -> Option<String> {
+        Replacer::new(find, replace, mode, case_insensitive)
+            .unwrap()
+            .apply(text)
+            .map(|r| r.text)
+    }
+
+    #[test]
+    fn test_match_modes() {
+        let text = "Metta and metta-bhavana; mettam.";
+
+        assert_eq!(
+            replace("metta", "mettā", MatchMode::Literal, false, text).as_deref(),
+            Some("Metta and mettā-bhavana; mettām.")
+        );
+        assert_eq!(
+            replace("metta", "mettā", MatchMode::WholeWord, true, text).as_deref(),
+            Some("mettā and mettā-bhavana; mettam.")
+        );
+        assert_eq!(
+            replace(r"(\w+)-bhavana", "$1 bhāvanā", MatchMode::Regex, false, text).as_deref(),
+            Some("Metta and metta bhāvanā; mettam.")
+        );
+        // Literal replacements aren't expanded
+        assert_eq!(replace("and", "$1", MatchMode::Literal, false, text).as_deref(), Some("Metta $1 metta-bhavana; mettam."));
+        assert_eq!(replace("karuna", "karuṇā", MatchMode::Literal, false, text), None);
+        assert_eq!(
+            replace("Dr.", "Doctor", MatchMode::WholeWord, false, "Dr. Smith, not Mr.Dr.").as_deref(),
+            Some("Doctor Smith, not Mr.Doctor")
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let replacer = Replacer::new("sutta", "sutta", MatchMode::Literal, true).unwrap();
+        let replaced = replacer.apply("Sutta after sutta").unwrap();
+
+        assert_eq!(replaced.matches, 2);
+        let ops: Vec<(DiffOp, &str)> = replaced.diff.iter().map(|s| (s.op, s.text.as_str())).collect();
+        assert_eq!(
+            ops,
+            vec![
+                (DiffOp::Delete, "Sutta"),
+                (DiffOp::Insert, "sutta"),
+                (DiffOp::Equal, " after "),
+                (DiffOp::Delete, "sutta"),
+                (DiffOp::Insert, "sutta"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replace_words() {
+        let word = |text: &str| TranscriptWord {
+            start_time: 0.0,
+            end_time: 1.0,
+            text: text.to_string(),
+            confidence: None,
+        };
+        let words = [word("metta"), word(" and"), word(" mettam,"), word(" Ajahn"), word(" Cha.")];
+        let text = "metta and mettam, Ajahn Cha.";
+        let texts = |find: &str, replace: &str| -> Vec<String> {
+            let replaced = Replacer::new(find, replace, MatchMode::Regex, false).unwrap().apply(text).unwrap();
+            replace_words(text, &words, &replaced.edits).into_iter().map(|w| w.text).collect()
+        };
+
+        // Only the word the block-level match touched, not every word the pattern would match alone
+        assert_eq!(texts("^metta", "mettā"), vec!["mettā", " and", " mettam,", " Ajahn", " Cha."]);
+        assert_eq!(texts("metta", "mettā"), vec!["mettā", " and", " mettām,", " Ajahn", " Cha."]);
+        // A match across words leaves the words as they were
+        assert_eq!(texts("Ajahn Cha", "Ajahn Chah"), vec!["metta", " and", " mettam,", " Ajahn", " Cha."]);
+    }
+
+    #[test]
+    fn test_rejects_bad_patterns() {
+        assert!(Replacer::new("", "x", MatchMode::Literal, false).is_err());
+        assert!(Replacer::new("(", "x", MatchMode::Regex, false).is_err());
+        assert!(Replacer::new("a*", "x", MatchMode::Regex, false).is_err());
+    }
+}
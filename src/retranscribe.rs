@@ -0,0 +1,285 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use futures_util::TryStreamExt;
+use serde::Deserialize;
+use sqlx::types::Json as SqlJson;
+use std::sync::Arc;
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::{
+    auth::AuthUser,
+    chunking::extract_audio_span,
+    db::{TranscriptBlock, TranscriptWord, Video},
+    error::AppError,
+    glossary::load_glossary,
+    transcriber::{TranscriptSegment, TranscriptionRequest},
+    transcript::{get_video_block, validate_timing},
+    upload::{copy_to_temp_file, get_owned_video, AppState},
+};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RetranscribeRangeRequest {
+    pub start_time: f64,
+    pub end_time: f64,
+}
+
+/// Distribute segments over blocks by the midpoint of each segment
+///
+/// A segment goes to the block containing its midpoint, or to the nearest block
+/// if the midpoint falls between blocks. Returns the joined text and the
+/// concatenated words per block, or None for a block that got no text.
+fn assign_segments(
+    blocks: &[TranscriptBlock],
+    segments: &[TranscriptSegment],
+) -> Vec<Option<(String, Vec<TranscriptWord>)>> {
+    let mut texts: Vec<Vec<&str>> = vec![Vec::new(); blocks.len()];
+    let mut words: Vec<Vec<TranscriptWord>> = vec![Vec::new(); blocks.len()];
+
+    for segment in segments {
+        let mid = (segment.start + segment.end) / 2.0;
+        let distance = |block: &TranscriptBlock| {
+            if mid < block.start_time {
+                block.start_time - mid
+            } else if mid > block.end_time {
+                mid - block.end_time
+            } else {
+                0.0
+            }
+        };
+
+        let nearest = blocks
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| distance(a).total_cmp(&distance(b)))
+            .map(|(index, _)| index);
+
+        if let Some(index) = nearest {
+            let text = segment.text.trim();
+            if !text.is_empty() {
+                texts[index].push(text);
+            }
+            words[index].extend(segment.words.iter().cloned());
+        }
+    }
+
+    texts
+        .into_iter()
+        .zip(words)
+        .map(|(parts, words)| (!parts.is_empty()).then(|| (parts.join(" "), words)))
+        .collect()
+}
+
+/// Re-extract the audio span covered by `blocks`, transcribe it and replace the blocks' text and words
+///
+/// The previous text of each block is kept in `previous_text`. Blocks that get no
+/// segments are left unchanged. Fails with a conflict if a block was edited while
+/// the audio was being transcribed.
+pub async fn retranscribe_blocks(
+    state: &AppState,
+    video: &Video,
+    blocks: Vec<TranscriptBlock>,
+) -> Result<Vec<TranscriptBlock>, AppError> {
+    let start = blocks.iter().map(|b| b.start_time).fold(f64::INFINITY, f64::min);
+    let end = blocks.iter().map(|b| b.end_time).fold(f64::NEG_INFINITY, f64::max);
+    if blocks.is_empty() || end <= start {
+        return Err(AppError::BadRequest("Nothing to retranscribe in this span".to_string()));
+    }
+
+    let temp_video = copy_to_temp_file(&state.filestore, &video.file_path).await?;
+    let audio = extract_audio_span(temp_video.as_ref(), start, end).await?;
+
+    let audio_path = tempfile::Builder::new()
+        .prefix("gatha_retranscribe_")
+        .suffix(".wav")
+        .tempfile()
+        .map_err(|e| AppError::Internal(format!("Failed to create temp file: {}", e)))?
+        .into_temp_path();
+    tokio::fs::write(&audio_path, audio)
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to write audio span: {}", e)))?;
+
+    let (glossary, prompt) = load_glossary(state, &video.user_id).await?;
+    let mut segments: Vec<TranscriptSegment> = state
+        .transcriber
+        .transcribe(TranscriptionRequest {
+            audio_path: audio_path.to_path_buf(),
+            time_offset: start,
+            language: video.language.clone(),
+            prompt,
+        })
+        .await?
+        .try_collect()
+        .await?;
+    for segment in &mut segments {
+        glossary.normalize_segment(segment);
+    }
+
+    let replaced: Vec<TranscriptBlock> = blocks
+        .iter()
+        .zip(assign_segments(&blocks, &segments))
+        .filter_map(|(block, assigned)| {
+            let (text, words) = assigned?;
+            Some(TranscriptBlock {
+                text,
+                words: SqlJson(words),
+                ..block.clone()
+            })
+        })
+        .collect();
+    if !state.db.replace_transcript_block_texts(&replaced, &video.user_id).await? {
+        return Err(AppError::Conflict(
+            "Transcript was edited during retranscription; try again".to_string(),
+        ));
+    }
+
+    info!(
+        video_id = %video.id,
+        start = start,
+        end = end,
+        blocks = blocks.len(),
+        segments = segments.len(),
+        "Retranscribed audio span"
+    );
+
+    let mut updated = Vec::with_capacity(blocks.len());
+    for block in blocks {
+        if let Some(block) = state.db.get_transcript_block(&block.id).await? {
+            updated.push(block);
+        }
+    }
+    Ok(updated)
+}
+
+/// Retranscribe the audio of a single block
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/transcript/blocks/{block_id}/retranscribe",
+    params(
+        ("id" = String, Path, description = "Video ID"),
+        ("block_id" = String, Path, description = "Transcript block ID")
+    ),
+    responses(
+        (status = 200, description = "Block with new text; the old text is in previous_text", body = TranscriptBlock),
+        (status = 400, description = "Block has no duration"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video or block not found"),
+        (status = 409, description = "The block was edited while it was being retranscribed"),
+        (status = 500, description = "Internal server error - extraction or transcription failed"),
+        (status = 503, description = "Transcription engine is not installed")
+    ),
+    tag = "transcript"
+)]
+pub async fn retranscribe_block(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path((video_id, block_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, AppError> {
+    let video = get_owned_video(&state, &video_id, &auth_user).await?;
+    let block = get_video_block(&state, &video_id, &block_id).await?;
+
+    // The block may have been deleted while its audio was being transcribed
+    let block = retranscribe_blocks(&state, &video, vec![block])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::NotFound("Transcript block not found".to_string()))?;
+
+    Ok((StatusCode::OK, Json(block)))
+}
+
+/// Retranscribe the audio of every block overlapping a time range
+///
+/// The span is widened to whole blocks so no block is transcribed from partial audio.
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/transcript/retranscribe",
+    params(("id" = String, Path, description = "Video ID")),
+    request_body = RetranscribeRangeRequest,
+    responses(
+        (status = 200, description = "Affected blocks with new text", body = Vec<TranscriptBlock>),
+        (status = 400, description = "Invalid range or no blocks in range"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 409, description = "A block was edited while the range was being retranscribed"),
+        (status = 500, description = "Internal server error - extraction or transcription failed"),
+        (status = 503, description = "Transcription engine is not installed")
+    ),
+    tag = "transcript"
+)]
+pub async fn retranscribe_range(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+    Json(req): Json<RetranscribeRangeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let video = get_owned_video(&state, &video_id, &auth_user).await?;
+    validate_timing(req.start_time, req.end_time)?;
+
+    let blocks: Vec<TranscriptBlock> = state
+        .db
+        .list_transcript_blocks(&video_id)
+        .await?
+        .into_iter()
+        .filter(|b| b.start_time < req.end_time && b.end_time > req.start_time)
+        .collect();
+
+    if blocks.is_empty() {
+        return Err(AppError::BadRequest("No transcript blocks in this range".to_string()));
+    }
+
+    let blocks = retranscribe_blocks(&state, &video, blocks).await?;
+
+    Ok((StatusCode::OK, Json(blocks)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start: f64, end: f64, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            start,
+            end,
+            text: text.to_string(),
+            words: vec![TranscriptWord {
+                start_time: start,
+                end_time: end,
+                text: text.trim().to_string(),
+                confidence: Some(0.9),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_assign_segments_by_midpoint() {
+        let blocks = [
+            TranscriptBlock::new("video".to_string(), 0.0, 4.0, "old".to_string(), 0),
+            TranscriptBlock::new("video".to_string(), 5.0, 8.0, "old".to_string(), 1),
+        ];
+        let segments = [
+            segment(0.0, 2.0, " First."),
+            segment(2.0, 4.5, "Second."),
+            // Midpoint 4.6 is in the gap, closer to the second block
+            segment(4.2, 5.0, "Third."),
+            segment(5.0, 9.0, "Fourth."),
+        ];
+
+        let assigned = assign_segments(&blocks, &segments);
+        let texts: Vec<&str> = assigned.iter().map(|a| a.as_ref().unwrap().0.as_str()).collect();
+        assert_eq!(texts, vec!["First. Second.", "Third. Fourth."]);
+
+        // Words follow their segment
+        let words: Vec<&str> = assigned[1].as_ref().unwrap().1.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(words, vec!["Third.", "Fourth."]);
+
+        // A block without segments gets nothing, so it keeps its text
+        let assigned = assign_segments(&blocks, &segments[..1]);
+        assert_eq!(assigned[0].as_ref().unwrap().0, "First.");
+        assert_eq!(assigned[1], None);
+    }
+}
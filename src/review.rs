@@ -0,0 +1,151 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::info;
+use utoipa::IntoParams;
+
+use crate::{
+    auth::AuthUser,
+    db::TranscriptBlock,
+    error::AppError,
+    transcript::get_video_block,
+    upload::{get_owned_video, AppState},
+};
+
+/// Blocks below this confidence are queued for review by default
+const DEFAULT_REVIEW_THRESHOLD: f64 = 0.8;
+const DEFAULT_REVIEW_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ReviewQueueQuery {
+    /// Only queue blocks with a confidence strictly below this value (default 0.8)
+    pub max_confidence: Option<f64>,
+    /// Also return blocks that were already marked as reviewed (default false)
+    #[serde(default)]
+    pub include_reviewed: bool,
+    /// Maximum number of blocks to return (default 100)
+    pub limit: Option<i64>,
+}
+
+/// List a video's transcript blocks the engine was least sure about
+///
+/// Blocks without a confidence score are never queued.
+#[utoipa::path(
+    get,
+    path = "/api/videos/{id}/review-queue",
+    params(
+        ("id" = String, Path, description = "Video ID"),
+        ReviewQueueQuery
+    ),
+    responses(
+        (status = 200, description = "Blocks ordered by lowest confidence", body = Vec<TranscriptBlock>),
+        (status = 400, description = "Invalid threshold or limit"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn get_review_queue(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+    Query(query): Query<ReviewQueueQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let max_confidence = query.max_confidence.unwrap_or(DEFAULT_REVIEW_THRESHOLD);
+    if !max_confidence.is_finite() {
+        return Err(AppError::BadRequest("max_confidence must be a number".to_string()));
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_REVIEW_LIMIT);
+    if limit <= 0 {
+        return Err(AppError::BadRequest("limit must be positive".to_string()));
+    }
+
+    let blocks = state
+        .db
+        .list_review_queue(&video_id, max_confidence, query.include_reviewed, limit)
+        .await?;
+
+    Ok((StatusCode::OK, Json(blocks)))
+}
+
+/// Mark a block as reviewed, removing it from the review queue
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/review-queue/{block_id}",
+    params(
+        ("id" = String, Path, description = "Video ID"),
+        ("block_id" = String, Path, description = "Transcript block ID")
+    ),
+    responses(
+        (status = 200, description = "Block marked as reviewed", body = TranscriptBlock),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video or block not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn mark_reviewed(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path((video_id, block_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+    let mut block = get_video_block(&state, &video_id, &block_id).await?;
+
+    let now = Utc::now();
+    block.reviewed_at = Some(now);
+    block.updated_at = now;
+    state
+        .db
+        .set_transcript_block_reviewed(&block_id, block.reviewed_at, block.updated_at, &auth_user.user_id)
+        .await?;
+
+    info!(video_id = %video_id, block_id = %block_id, "Marked transcript block as reviewed");
+
+    Ok((StatusCode::OK, Json(block)))
+}
+
+/// Clear a block's reviewed mark, returning it to the review queue
+#[utoipa::path(
+    delete,
+    path = "/api/videos/{id}/review-queue/{block_id}",
+    params(
+        ("id" = String, Path, description = "Video ID"),
+        ("block_id" = String, Path, description = "Transcript block ID")
+    ),
+    responses(
+        (status = 200, description = "Reviewed mark cleared", body = TranscriptBlock),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video or block not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn unmark_reviewed(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path((video_id, block_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+    let mut block = get_video_block(&state, &video_id, &block_id).await?;
+
+    block.reviewed_at = None;
+    block.updated_at = Utc::now();
+    state
+        .db
+        .set_transcript_block_reviewed(&block_id, None, block.updated_at, &auth_user.user_id)
+        .await?;
+
+    info!(video_id = %video_id, block_id = %block_id, "Cleared transcript block review");
+
+    Ok((StatusCode::OK, Json(block)))
+}
@@ -0,0 +1,184 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{info, warn};
+use utoipa::IntoParams;
+
+use crate::{
+    auth::AuthUser,
+    db::{TranscriptBlock, TranscriptRevision},
+    error::AppError,
+    upload::{get_owned_video, AppState},
+};
+
+const DEFAULT_LIMIT: i64 = 100;
+const MAX_LIMIT: i64 = 500;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct HistoryQuery {
+    /// Only return revisions older than this revision ID, for paging
+    pub before: Option<i64>,
+    /// Maximum number of revisions to return (default 100, at most 500)
+    pub limit: Option<i64>,
+}
+
+/// List the change history of a video's transcript, newest first
+#[utoipa::path(
+    get,
+    path = "/api/videos/{id}/transcript/revisions",
+    params(("id" = String, Path, description = "Video ID"), HistoryQuery),
+    responses(
+        (status = 200, description = "Revisions, newest first", body = Vec<TranscriptRevision>),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn list_history(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let revisions = state
+        .db
+        .list_transcript_revisions(&video_id, query.before, limit)
+        .await?;
+
+    Ok((StatusCode::OK, Json(revisions)))
+}
+
+/// List the past versions of a block, newest first
+///
+/// Deleted blocks keep their history.
+#[utoipa::path(
+    get,
+    path = "/api/videos/{id}/transcript/blocks/{block_id}/revisions",
+    params(
+        ("id" = String, Path, description = "Video ID"),
+        ("block_id" = String, Path, description = "Transcript block ID")
+    ),
+    responses(
+        (status = 200, description = "Versions of the block, newest first", body = Vec<TranscriptRevision>),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video or block not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn list_block_history(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path((video_id, block_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let revisions = state.db.list_block_revisions(&block_id).await?;
+    if revisions.is_empty() || revisions.iter().any(|r| r.video_id != video_id) {
+        warn!(video_id = %video_id, block_id = %block_id, "Transcript block history not found");
+        return Err(AppError::NotFound("Transcript block not found".to_string()));
+    }
+
+    Ok((StatusCode::OK, Json(revisions)))
+}
+
+/// Restore a block to the state recorded in a revision
+///
+/// A deleted block is re-created. The restore is recorded as a new revision, so it
+/// can be undone too.
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/transcript/revisions/{revision_id}/restore",
+    params(
+        ("id" = String, Path, description = "Video ID"),
+        ("revision_id" = i64, Path, description = "Revision ID")
+    ),
+    responses(
+        (status = 200, description = "Restored block", body = TranscriptBlock),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video or revision not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn restore_revision(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path((video_id, revision_id)): Path<(String, i64)>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let revision = match state.db.get_transcript_revision(revision_id).await? {
+        Some(revision) if revision.video_id == video_id => revision,
+        _ => {
+            warn!(video_id = %video_id, revision_id = revision_id, "Transcript revision not found");
+            return Err(AppError::NotFound("Revision not found".to_string()));
+        }
+    };
+
+    state
+        .db
+        .restore_transcript_revision(&revision, &auth_user.user_id)
+        .await?;
+
+    let block = state
+        .db
+        .get_transcript_block(&revision.block_id)
+        .await?
+        .ok_or_else(|| AppError::Internal("Restored block is missing".to_string()))?;
+
+    info!(
+        video_id = %video_id,
+        block_id = %block.id,
+        revision_id = revision_id,
+        "Restored transcript block"
+    );
+
+    Ok((StatusCode::OK, Json(block)))
+}
+
+/// Undo the latest change to a video's transcript
+///
+/// All blocks touched by the change (e.g. a find-and-replace) go back to their
+/// previous state at once. Calling it again undoes the change before that.
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/transcript/undo",
+    params(("id" = String, Path, description = "Video ID")),
+    responses(
+        (status = 200, description = "Revisions recording the undo", body = Vec<TranscriptRevision>),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 409, description = "Nothing to undo"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn undo(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let Some(revisions) = state
+        .db
+        .undo_transcript_change(&video_id, &auth_user.user_id)
+        .await?
+    else {
+        return Err(AppError::Conflict("Nothing to undo".to_string()));
+    };
+
+    info!(video_id = %video_id, blocks = revisions.len(), "Undid transcript change");
+
+    Ok((StatusCode::OK, Json(revisions)))
+}
@@ -0,0 +1,135 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::info;
+use utoipa::IntoParams;
+
+use crate::{auth::AuthUser, db::TranscriptSearchHit, error::AppError, upload::AppState};
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+/// Markers SQLite puts around matches; control characters can't clash with transcript text
+const MATCH_START: &str = "\u{2}";
+const MATCH_END: &str = "\u{3}";
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SearchQuery {
+    /// Words that must all appear in a block; "quoted phrases" must appear as written
+    pub q: String,
+    /// Maximum number of matches to return (default 50, at most 200)
+    pub limit: Option<i64>,
+    /// Number of matches to skip, for paging
+    pub offset: Option<i64>,
+}
+
+/// Turn a user's search into an FTS5 query, or None if it has nothing to search for
+///
+/// Every word and phrase is quoted so FTS5 operators and punctuation in the
+/// input are matched literally.
+pub fn fts_query(input: &str) -> Option<String> {
+    let mut terms = Vec::new();
+    // Text between double quotes (odd parts) is a phrase; an unclosed quote runs to the end
+    for (index, part) in input.split('"').enumerate() {
+        if index % 2 == 1 {
+            terms.push(part.to_string());
+        } else {
+            terms.extend(part.split_whitespace().map(str::to_string));
+        }
+    }
+
+    let terms: Vec<String> = terms
+        .into_iter()
+        .filter(|term| term.chars().any(char::is_alphanumeric))
+        .map(|term| format!("\"{}\"", term.trim()))
+        .collect();
+
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Helper: HTML-escape a snippet and wrap its matches in `<mark>` tags
+fn highlight_snippet(snippet: &str) -> String {
+    let mut html = String::with_capacity(snippet.len());
+    for c in snippet.chars() {
+        match c {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+    html.replace(MATCH_START, "<mark>").replace(MATCH_END, "</mark>")
+}
+
+/// Search the transcripts of all of the user's videos
+///
+/// Matching ignores case and diacritics. Snippets are HTML with matches wrapped in
+/// `<mark>` tags; the rest of the text is escaped.
+#[utoipa::path(
+    get,
+    path = "/api/search",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "Matching blocks, best matches first", body = Vec<TranscriptSearchHit>),
+        (status = 400, description = "Empty search"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "search"
+)]
+pub async fn search(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Query(query): Query<SearchQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let Some(fts) = fts_query(&query.q) else {
+        return Err(AppError::BadRequest("Search must contain at least one word".to_string()));
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let mut hits = state
+        .db
+        .search_transcripts(&auth_user.user_id, &fts, (MATCH_START, MATCH_END), limit, offset)
+        .await?;
+    for hit in &mut hits {
+        hit.snippet = highlight_snippet(&hit.snippet);
+    }
+
+    info!(user_id = %auth_user.user_id, query = %fts, hits = hits.len(), "Searched transcripts");
+
+    Ok((StatusCode::OK, Json(hits)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fts_query() {
+        assert_eq!(fts_query("four foundations").as_deref(), Some("\"four\" \"foundations\""));
+        assert_eq!(
+            fts_query("\"four foundations\" mindfulness").as_deref(),
+            Some("\"four foundations\" \"mindfulness\"")
+        );
+        // Operators and stray punctuation are not syntax
+        assert_eq!(fts_query("NOT anattā -").as_deref(), Some("\"NOT\" \"anattā\""));
+        assert_eq!(fts_query("\"unclosed phrase").as_deref(), Some("\"unclosed phrase\""));
+        assert_eq!(fts_query("  \"\" * "), None);
+    }
+
+    #[test]
+    fn test_highlight_snippet() {
+        assert_eq!(
+            highlight_snippet("the \u{2}four\u{3} <foundations> & more"),
+            "the <mark>four</mark> &lt;foundations&gt; &amp; more"
+        );
+    }
+}
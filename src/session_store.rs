@@ -70,6 +70,12 @@ impl InMemorySessionStore {
     }
 }
 
+impl Default for InMemorySessionStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait::async_trait]
 impl SessionStore for InMemorySessionStore {
     async fn get(&self, key: &SessionKey) -> Result<Option<TranscriptionSession>> {
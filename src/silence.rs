@@ -0,0 +1,276 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::process::Command;
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+
+use crate::{
+    auth::AuthUser,
+    db::{SilenceAnalysis, SilenceInterval},
+    error::AppError,
+    upload::{copy_to_temp_file, get_owned_video, AppState},
+};
+
+pub const DEFAULT_NOISE_DB: f64 = -30.0;
+pub const DEFAULT_MIN_DURATION: f64 = 0.5;
+
+/// Parameters for ffmpeg's silencedetect filter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SilenceParams {
+    /// Volume below which audio counts as silence, in dB
+    pub noise_db: f64,
+    /// Minimum length of a silent interval, in seconds
+    pub min_duration: f64,
+}
+
+impl Default for SilenceParams {
+    fn default() -> Self {
+        Self {
+            noise_db: DEFAULT_NOISE_DB,
+            min_duration: DEFAULT_MIN_DURATION,
+        }
+    }
+}
+
+/// Run ffmpeg silencedetect over a local media file
+///
+/// `duration` closes a silence that runs to the end of the file.
+pub async fn detect_silences(
+    file_path: &str,
+    params: SilenceParams,
+    duration: Option<f64>,
+) -> Result<Vec<SilenceInterval>, AppError> {
+    info!(
+        file_path = file_path,
+        noise_db = params.noise_db,
+        min_duration = params.min_duration,
+        "Detecting silences with ffmpeg"
+    );
+
+    // silencedetect reports intervals on stderr; decoded output is discarded
+    let filter = format!("silencedetect=noise={}dB:d={}", params.noise_db, params.min_duration);
+    let output = Command::new("ffmpeg")
+        .args([
+            "-hide_banner",
+            "-nostats",
+            "-i", file_path,
+            "-vn",
+            "-af", &filter,
+            "-f", "null",
+            "-",
+        ])
+        .output()
+        .await
+        .map_err(|e| {
+            error!(error = %e, file_path = file_path, "Failed to execute ffmpeg silencedetect");
+            AppError::Internal(format!("Silence detection failed: {}", e))
+        })?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if !output.status.success() {
+        warn!(
+            file_path = file_path,
+            exit_code = ?output.status.code(),
+            stderr = %stderr,
+            "ffmpeg silencedetect failed"
+        );
+        return Err(AppError::Internal("Silence detection failed".to_string()));
+    }
+
+    let silences = parse_silencedetect_output(&stderr, duration);
+
+    info!(file_path = file_path, count = silences.len(), "Silences detected");
+
+    Ok(silences)
+}
+
+/// Parse `silence_start: X` / `silence_end: Y | silence_duration: Z` lines from ffmpeg stderr
+fn parse_silencedetect_output(stderr: &str, duration: Option<f64>) -> Vec<SilenceInterval> {
+    let mut silences = Vec::new();
+    let mut pending_start: Option<f64> = None;
+
+    for line in stderr.lines() {
+        if let Some((_, rest)) = line.split_once("silence_start:") {
+            pending_start = rest.trim().parse::<f64>().ok();
+        } else if let Some((_, rest)) = line.split_once("silence_end:") {
+            let end = rest
+                .split('|')
+                .next()
+                .and_then(|v| v.trim().parse::<f64>().ok());
+
+            if let (Some(start), Some(end)) = (pending_start.take(), end) {
+                // ffmpeg can report slightly negative starts for leading silence
+                silences.push(SilenceInterval {
+                    start_time: start.max(0.0),
+                    end_time: end,
+                });
+            }
+        }
+    }
+
+    // Silence running to the end of the file has no silence_end line
+    if let (Some(start), Some(duration)) = (pending_start, duration)
+        && duration > start
+    {
+        silences.push(SilenceInterval {
+            start_time: start.max(0.0),
+            end_time: duration,
+        });
+    }
+
+    silences
+}
+
+/// Parameters for re-analyzing a video's silences; omitted ones take their defaults
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct AnalyzeSilencesRequest {
+    /// Noise threshold in dB (default -30)
+    pub noise_db: Option<f64>,
+    /// Minimum silence duration in seconds (default 0.5)
+    pub min_duration: Option<f64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SilenceMapResponse {
+    pub video_id: String,
+    pub noise_db: f64,
+    pub min_duration: f64,
+    /// When the analysis ran, or null if the video has never been analyzed
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub analyzed_at: Option<DateTime<Utc>>,
+    pub silences: Vec<SilenceInterval>,
+}
+
+/// Helper: Build the response from the stored silence analysis of a video
+async fn silence_map(state: &AppState, video_id: String) -> Result<SilenceMapResponse, AppError> {
+    let analysis = state.db.get_silence_analysis(&video_id).await?;
+    let silences = state.db.list_silences(&video_id).await?;
+
+    Ok(SilenceMapResponse {
+        video_id,
+        noise_db: analysis.as_ref().map_or(DEFAULT_NOISE_DB, |a| a.noise_db),
+        min_duration: analysis.as_ref().map_or(DEFAULT_MIN_DURATION, |a| a.min_duration),
+        analyzed_at: analysis.map(|a| a.analyzed_at),
+        silences,
+    })
+}
+
+/// Get the stored silence map of a video
+#[utoipa::path(
+    get,
+    path = "/api/videos/{id}/silences",
+    params(("id" = String, Path, description = "Video ID")),
+    responses(
+        (status = 200, description = "Silent intervals of the video", body = SilenceMapResponse),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "videos"
+)]
+pub async fn get_silences(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    Ok((StatusCode::OK, Json(silence_map(&state, video_id).await?)))
+}
+
+/// Re-analyze the silences of a video with new parameters, replacing the stored map
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/silences",
+    params(("id" = String, Path, description = "Video ID")),
+    request_body = AnalyzeSilencesRequest,
+    responses(
+        (status = 200, description = "New silent intervals of the video", body = SilenceMapResponse),
+        (status = 400, description = "Invalid analysis parameters"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 500, description = "Internal server error - analysis failed")
+    ),
+    tag = "videos"
+)]
+pub async fn analyze_silences(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+    Json(req): Json<AnalyzeSilencesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let video = get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let params = SilenceParams {
+        noise_db: req.noise_db.unwrap_or(DEFAULT_NOISE_DB),
+        min_duration: req.min_duration.unwrap_or(DEFAULT_MIN_DURATION),
+    };
+    if !params.noise_db.is_finite() || params.noise_db > 0.0 {
+        return Err(AppError::BadRequest("noise_db must be at most 0 dB".to_string()));
+    }
+    if !params.min_duration.is_finite() || params.min_duration <= 0.0 {
+        return Err(AppError::BadRequest("min_duration must be positive".to_string()));
+    }
+
+    let temp_file = copy_to_temp_file(&state.filestore, &video.file_path).await?;
+    let silences = detect_silences(
+        &temp_file.to_string_lossy(),
+        params,
+        video.duration_seconds,
+    )
+    .await?;
+
+    let analysis = SilenceAnalysis {
+        video_id: video_id.clone(),
+        noise_db: params.noise_db,
+        min_duration: params.min_duration,
+        analyzed_at: Utc::now(),
+    };
+    state.db.replace_silences(&analysis, &silences).await?;
+
+    info!(
+        video_id = %video_id,
+        count = silences.len(),
+        "Re-analyzed video silences"
+    );
+
+    Ok((StatusCode::OK, Json(silence_map(&state, video_id).await?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_silencedetect_output() {
+        let stderr = "\
+Input #0, mov,mp4,m4a,3gp,3g2,mj2, from 'talk.mp4':
+[silencedetect @ 0x5581] silence_start: -0.00133333
+[silencedetect @ 0x5581] silence_end: 1.504 | silence_duration: 1.50533
+[silencedetect @ 0x5581] silence_start: 12.25
+[silencedetect @ 0x5581] silence_end: 14 | silence_duration: 1.75
+[silencedetect @ 0x5581] silence_start: 58.1
+";
+
+        let silences = parse_silencedetect_output(stderr, Some(60.0));
+        assert_eq!(
+            silences,
+            vec![
+                SilenceInterval { start_time: 0.0, end_time: 1.504 },
+                SilenceInterval { start_time: 12.25, end_time: 14.0 },
+                SilenceInterval { start_time: 58.1, end_time: 60.0 },
+            ]
+        );
+
+        // Without a known duration the trailing silence can't be closed
+        assert_eq!(parse_silencedetect_output(stderr, None).len(), 2);
+    }
+}
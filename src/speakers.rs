@@ -0,0 +1,455 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+
+use crate::{
+    auth::AuthUser,
+    db::{speaker_for_span, Speaker, SpeakerSpan, TranscriptBlock, Video},
+    diarizer::DiarizationRequest,
+    error::AppError,
+    upload::{copy_to_temp_file, get_owned_video, AppState},
+};
+
+/// Colors handed out to new speakers in order
+const SPEAKER_COLORS: &[&str] = &[
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1", "#ff9da7",
+];
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateSpeakerRequest {
+    pub name: String,
+    /// CSS color (picked from a palette if omitted)
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateSpeakerRequest {
+    pub name: Option<String>,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MergeSpeakersRequest {
+    /// Speakers whose blocks move to the target speaker; they are deleted afterwards
+    pub speaker_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AssignSpeakerRequest {
+    pub block_ids: Vec<String>,
+    /// Speaker to assign, or null to clear the blocks' speaker
+    pub speaker_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AssignSpeakerResponse {
+    pub updated: u64,
+}
+
+/// Helper: Pick the palette color for the n-th speaker of a video
+fn palette_color(index: usize) -> String {
+    SPEAKER_COLORS[index % SPEAKER_COLORS.len()].to_string()
+}
+
+/// Helper: Reject blank speaker names
+fn validate_name(name: &str) -> Result<(), AppError> {
+    if name.trim().is_empty() {
+        return Err(AppError::BadRequest("Speaker name must not be empty".to_string()));
+    }
+    Ok(())
+}
+
+/// Helper: Load a speaker and verify it belongs to the given video
+pub(crate) async fn get_video_speaker(
+    state: &AppState,
+    video_id: &str,
+    speaker_id: &str,
+) -> Result<Speaker, AppError> {
+    match state.db.get_speaker(speaker_id).await? {
+        Some(speaker) if speaker.video_id == video_id => Ok(speaker),
+        _ => {
+            warn!(video_id = %video_id, speaker_id = %speaker_id, "Speaker not found");
+            Err(AppError::NotFound("Speaker not found".to_string()))
+        }
+    }
+}
+
+/// Helper: Assign diarized speakers to new blocks
+pub(crate) fn assign_from_spans(spans: &[SpeakerSpan], blocks: &mut [TranscriptBlock]) {
+    for block in blocks {
+        block.speaker_id = speaker_for_span(spans, block.start_time, block.end_time);
+    }
+}
+
+/// Run the diarizer over a video and replace its speakers with the detected voices
+///
+/// Existing blocks are re-assigned from the new turns; blocks transcribed later are
+/// assigned as they are stored. Returns the new speakers.
+///
+/// Fails with a conflict if the video's speakers or their blocks were changed by hand, as
+/// replacing the speakers would lose that labelling.
+pub async fn diarize_video(state: &AppState, video: &Video) -> Result<Vec<Speaker>, AppError> {
+    let Some(diarizer) = &state.diarizer else {
+        return Err(AppError::BadRequest("Speaker diarization is not enabled".to_string()));
+    };
+
+    let media = copy_to_temp_file(&state.filestore, &video.file_path).await?;
+    let turns = diarizer
+        .diarize(DiarizationRequest {
+            audio_path: media.to_path_buf(),
+            duration: video.duration_seconds,
+        })
+        .await?;
+
+    // One speaker per distinct label, numbered in order of first appearance
+    let mut speakers: Vec<Speaker> = Vec::new();
+    let mut by_label: HashMap<String, String> = HashMap::new();
+    let mut spans = Vec::with_capacity(turns.len());
+    for turn in turns {
+        let speaker_id = match by_label.get(&turn.speaker) {
+            Some(id) => id.clone(),
+            None => {
+                let speaker = Speaker::new(
+                    video.id.clone(),
+                    format!("Speaker {}", speakers.len() + 1),
+                    palette_color(speakers.len()),
+                );
+                by_label.insert(turn.speaker.clone(), speaker.id.clone());
+                let id = speaker.id.clone();
+                speakers.push(speaker);
+                id
+            }
+        };
+        spans.push(SpeakerSpan {
+            speaker_id,
+            start_time: turn.start,
+            end_time: turn.end,
+        });
+    }
+
+    let Some(assigned) = state.db.replace_diarization(&video.id, &speakers, &spans, &video.user_id).await? else {
+        return Err(AppError::Conflict(
+            "Speakers were edited by hand; delete them all before re-running diarization".to_string(),
+        ));
+    };
+
+    info!(
+        video_id = %video.id,
+        speakers = speakers.len(),
+        turns = spans.len(),
+        blocks = assigned,
+        "Diarized video"
+    );
+
+    Ok(speakers)
+}
+
+/// Diarize a freshly uploaded video in the background, if a diarizer is configured
+///
+/// Failures are logged; speakers can still be added by hand.
+pub fn spawn_diarization(state: Arc<AppState>, video: Video) {
+    if state.diarizer.is_none() {
+        return;
+    }
+
+    tokio::spawn(async move {
+        match diarize_video(&state, &video).await {
+            Ok(_) => {}
+            // Speakers added while the diarizer ran are kept
+            Err(AppError::Conflict(_)) => {
+                info!(video_id = %video.id, "Skipped diarization of a video with hand-edited speakers");
+            }
+            Err(e) => {
+                error!(video_id = %video.id, error = %e, "Speaker diarization failed");
+            }
+        }
+    });
+}
+
+/// List the speakers of a video
+#[utoipa::path(
+    get,
+    path = "/api/videos/{id}/speakers",
+    params(("id" = String, Path, description = "Video ID")),
+    responses(
+        (status = 200, description = "Speakers of the video", body = Vec<Speaker>),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "speakers"
+)]
+pub async fn list_speakers(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let speakers = state.db.list_speakers(&video_id).await?;
+
+    Ok((StatusCode::OK, Json(speakers)))
+}
+
+/// Add a speaker to a video
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/speakers",
+    params(("id" = String, Path, description = "Video ID")),
+    request_body = CreateSpeakerRequest,
+    responses(
+        (status = 201, description = "Speaker created", body = Speaker),
+        (status = 400, description = "Empty speaker name"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "speakers"
+)]
+pub async fn create_speaker(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+    Json(req): Json<CreateSpeakerRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+    validate_name(&req.name)?;
+
+    let color = match req.color {
+        Some(color) => color,
+        None => palette_color(state.db.list_speakers(&video_id).await?.len()),
+    };
+    let speaker = Speaker::new(video_id, req.name.trim().to_string(), color);
+    state.db.insert_speaker(&speaker).await?;
+
+    info!(video_id = %speaker.video_id, speaker_id = %speaker.id, "Created speaker");
+
+    Ok((StatusCode::CREATED, Json(speaker)))
+}
+
+/// Rename or recolor a speaker
+#[utoipa::path(
+    patch,
+    path = "/api/videos/{id}/speakers/{speaker_id}",
+    params(
+        ("id" = String, Path, description = "Video ID"),
+        ("speaker_id" = String, Path, description = "Speaker ID")
+    ),
+    request_body = UpdateSpeakerRequest,
+    responses(
+        (status = 200, description = "Speaker updated", body = Speaker),
+        (status = 400, description = "Empty speaker name"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video or speaker not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "speakers"
+)]
+pub async fn update_speaker(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path((video_id, speaker_id)): Path<(String, String)>,
+    Json(req): Json<UpdateSpeakerRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+    let mut speaker = get_video_speaker(&state, &video_id, &speaker_id).await?;
+
+    if let Some(name) = req.name {
+        validate_name(&name)?;
+        speaker.name = name.trim().to_string();
+    }
+    if let Some(color) = req.color {
+        speaker.color = color;
+    }
+    state.db.update_speaker(&speaker).await?;
+
+    info!(video_id = %video_id, speaker_id = %speaker_id, "Updated speaker");
+
+    Ok((StatusCode::OK, Json(speaker)))
+}
+
+/// Delete a speaker; its blocks become unassigned
+#[utoipa::path(
+    delete,
+    path = "/api/videos/{id}/speakers/{speaker_id}",
+    params(
+        ("id" = String, Path, description = "Video ID"),
+        ("speaker_id" = String, Path, description = "Speaker ID")
+    ),
+    responses(
+        (status = 204, description = "Speaker deleted"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video or speaker not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "speakers"
+)]
+pub async fn delete_speaker(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path((video_id, speaker_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+    get_video_speaker(&state, &video_id, &speaker_id).await?;
+
+    state.db.delete_speaker(&video_id, &speaker_id, &auth_user.user_id).await?;
+
+    info!(video_id = %video_id, speaker_id = %speaker_id, "Deleted speaker");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Merge other speakers into this one
+///
+/// Blocks of the merged speakers are reassigned to the target and the merged speakers are deleted.
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/speakers/{speaker_id}/merge",
+    params(
+        ("id" = String, Path, description = "Video ID"),
+        ("speaker_id" = String, Path, description = "Speaker ID to merge into")
+    ),
+    request_body = MergeSpeakersRequest,
+    responses(
+        (status = 200, description = "Speakers merged", body = Speaker),
+        (status = 400, description = "Speaker merged into itself"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video or speaker not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "speakers"
+)]
+pub async fn merge_speakers(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path((video_id, speaker_id)): Path<(String, String)>,
+    Json(req): Json<MergeSpeakersRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+    let target = get_video_speaker(&state, &video_id, &speaker_id).await?;
+
+    if req.speaker_ids.contains(&speaker_id) {
+        return Err(AppError::BadRequest("A speaker can't be merged into itself".to_string()));
+    }
+    for source_id in &req.speaker_ids {
+        get_video_speaker(&state, &video_id, source_id).await?;
+    }
+
+    state
+        .db
+        .merge_speakers(&video_id, &speaker_id, &req.speaker_ids, &auth_user.user_id)
+        .await?;
+
+    info!(
+        video_id = %video_id,
+        speaker_id = %speaker_id,
+        merged = req.speaker_ids.len(),
+        "Merged speakers"
+    );
+
+    Ok((StatusCode::OK, Json(target)))
+}
+
+/// Assign a speaker to many blocks at once
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/speakers/assign",
+    params(("id" = String, Path, description = "Video ID")),
+    request_body = AssignSpeakerRequest,
+    responses(
+        (status = 200, description = "Number of blocks updated", body = AssignSpeakerResponse),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video or speaker not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "speakers"
+)]
+pub async fn assign_speaker(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+    Json(req): Json<AssignSpeakerRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+    if let Some(speaker_id) = &req.speaker_id {
+        get_video_speaker(&state, &video_id, speaker_id).await?;
+    }
+
+    let assignments: Vec<(String, Option<String>)> = req
+        .block_ids
+        .into_iter()
+        .map(|block_id| (block_id, req.speaker_id.clone()))
+        .collect();
+    let updated = state.db.assign_block_speakers(&video_id, &assignments, &auth_user.user_id).await?;
+
+    info!(
+        video_id = %video_id,
+        speaker_id = ?req.speaker_id,
+        updated = updated,
+        "Assigned speaker to blocks"
+    );
+
+    Ok((StatusCode::OK, Json(AssignSpeakerResponse { updated })))
+}
+
+/// Re-run speaker diarization, replacing the video's speakers
+///
+/// Refused once speakers were added, renamed, recolored, merged or deleted, or blocks were
+/// given or cleared of a speaker by hand, until all speakers are deleted.
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/speakers/diarize",
+    params(("id" = String, Path, description = "Video ID")),
+    responses(
+        (status = 200, description = "Detected speakers; blocks were re-assigned", body = Vec<Speaker>),
+        (status = 400, description = "Diarization is not enabled"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 409, description = "Speakers were edited by hand"),
+        (status = 500, description = "Internal server error - diarization failed")
+    ),
+    tag = "speakers"
+)]
+pub async fn diarize(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let video = get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let speakers = diarize_video(&state, &video).await?;
+
+    Ok((StatusCode::OK, Json(speakers)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(speaker_id: &str, start_time: f64, end_time: f64) -> SpeakerSpan {
+        SpeakerSpan {
+            speaker_id: speaker_id.to_string(),
+            start_time,
+            end_time,
+        }
+    }
+
+    #[test]
+    fn test_speaker_for_span_picks_largest_overlap() {
+        let spans = [span("a", 0.0, 10.0), span("b", 10.0, 14.0), span("a", 14.0, 20.0)];
+
+        assert_eq!(speaker_for_span(&spans, 2.0, 5.0).as_deref(), Some("a"));
+        // 3s of b against 1s + 1s of a
+        assert_eq!(speaker_for_span(&spans, 9.0, 15.0).as_deref(), Some("b"));
+        // a's turns add up: 4s + 3s against 4s of b
+        assert_eq!(speaker_for_span(&spans, 6.0, 17.0).as_deref(), Some("a"));
+        assert_eq!(speaker_for_span(&spans, 25.0, 30.0), None);
+    }
+}
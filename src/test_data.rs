@@ -59,6 +59,7 @@ pub async fn seed_test_videos(
             width: None,
             height: None,
             duration_seconds: None,
+            language: None,
         };
 
         db.insert_video(&video).await?;
@@ -0,0 +1,169 @@
+use futures_util::stream::{self, BoxStream};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use thiserror::Error;
+
+use crate::db::TranscriptWord;
+
+#[derive(Error, Debug)]
+pub enum TranscriberError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Transcription engine binary not found: {0}")]
+    BinaryNotFound(PathBuf),
+    #[error("Transcription model not found: {0}")]
+    ModelNotFound(PathBuf),
+    #[error("Transcription failed: {0}")]
+    Failed(String),
+}
+
+pub type Result<T> = std::result::Result<T, TranscriberError>;
+
+/// A chunk of audio to be transcribed
+#[derive(Debug, Clone)]
+pub struct TranscriptionRequest {
+    /// Path to the audio file on the local filesystem
+    pub audio_path: PathBuf,
+    /// Position of the chunk on the video timeline, added to every segment timestamp
+    pub time_offset: f64,
+    /// Language hint (e.g. "en"), or None to let the backend decide
+    pub language: Option<String>,
+    /// Vocabulary prompt biasing the engine towards preferred spellings, if any
+    pub prompt: Option<String>,
+}
+
+/// A piece of transcribed text with timestamps on the video timeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    /// Word timings and confidence, empty if the backend doesn't report them
+    pub words: Vec<TranscriptWord>,
+}
+
+/// Stream of segments, emitted as the backend produces them
+pub type SegmentStream = BoxStream<'static, Result<TranscriptSegment>>;
+
+/// Trait for speech-to-text backends
+#[async_trait::async_trait]
+pub trait Transcriber: Send + Sync {
+    /// Transcribe an audio chunk, streaming segments in timeline order
+    async fn transcribe(&self, request: TranscriptionRequest) -> Result<SegmentStream>;
+}
+
+const MOCK_SEGMENTS: &[&str] = &[
+    "May all beings be happy.",
+    "May all beings be free from suffering.",
+    "May all beings be at peace.",
+];
+const MOCK_SEGMENT_SECONDS: f64 = 2.0;
+/// Words the mock reports as uncertain, to exercise low-confidence review
+const MOCK_UNSURE_WORDS: &[&str] = &["suffering."];
+
+/// Helper: Spread the words of `text` evenly over `start..end`
+fn mock_words(text: &str, start: f64, end: f64) -> Vec<TranscriptWord> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let step = (end - start) / words.len().max(1) as f64;
+
+    words
+        .into_iter()
+        .enumerate()
+        .map(|(i, word)| TranscriptWord {
+            start_time: start + i as f64 * step,
+            end_time: start + (i + 1) as f64 * step,
+            text: word.to_string(),
+            confidence: Some(if MOCK_UNSURE_WORDS.contains(&word) { 0.45 } else { 0.95 }),
+        })
+        .collect()
+}
+
+/// Deterministic transcriber that emits canned text without reading the audio
+///
+/// Every request yields the same segments, each `MOCK_SEGMENT_SECONDS` long,
+/// starting at the request's time offset. Words are spread evenly over their
+/// segment and are all confident except `MOCK_UNSURE_WORDS`.
+pub struct MockTranscriber;
+
+impl MockTranscriber {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for MockTranscriber {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Transcriber for MockTranscriber {
+    async fn transcribe(&self, request: TranscriptionRequest) -> Result<SegmentStream> {
+        let segments = MOCK_SEGMENTS
+            .iter()
+            .enumerate()
+            .map(move |(i, text)| {
+                let start = request.time_offset + i as f64 * MOCK_SEGMENT_SECONDS;
+                let end = start + MOCK_SEGMENT_SECONDS;
+                Ok(TranscriptSegment {
+                    start,
+                    end,
+                    text: text.to_string(),
+                    words: mock_words(text, start, end),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Box::pin(stream::iter(segments)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::TryStreamExt;
+
+    #[tokio::test]
+    async fn test_mock_transcriber_is_deterministic() {
+        let transcriber = MockTranscriber::new();
+        let request = TranscriptionRequest {
+            audio_path: PathBuf::from("chunk_0.wav"),
+            time_offset: 30.0,
+            language: Some("en".to_string()),
+            prompt: None,
+        };
+
+        let first: Vec<TranscriptSegment> = transcriber
+            .transcribe(request.clone())
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        let second: Vec<TranscriptSegment> = transcriber
+            .transcribe(request)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(first.len(), MOCK_SEGMENTS.len());
+
+        // Timestamps are offset onto the video timeline
+        assert_eq!(first[0].start, 30.0);
+        assert_eq!(first[0].end, 32.0);
+        assert_eq!(first[2].end, 36.0);
+
+        // Words cover their segment and carry a confidence
+        let words = &first[1].words;
+        assert_eq!(words.len(), 7);
+        assert_eq!(words[0].start_time, 32.0);
+        assert_eq!(words[6].end_time, 34.0);
+        assert_eq!(words[6].text, "suffering.");
+        assert!(words[6].confidence.unwrap() < 0.5);
+        assert!(words[0].confidence.unwrap() > 0.9);
+    }
+}
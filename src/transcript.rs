@@ -0,0 +1,592 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::{info, warn};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    auth::AuthUser,
+    db::{LowConfidenceWord, TranscriptBlock, TranscriptWord},
+    error::AppError,
+    speakers::get_video_speaker,
+    upload::{get_owned_video, AppState},
+};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateBlockRequest {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub text: String,
+    /// Position of the block in the transcript (appended after existing blocks if omitted)
+    pub ordering: Option<i64>,
+    /// Word-level timings and confidence, if known
+    #[serde(default)]
+    pub words: Vec<TranscriptWord>,
+    /// Block confidence from 0 to 1 (derived from the words if omitted)
+    pub confidence: Option<f64>,
+    pub speaker_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateBlockRequest {
+    pub start_time: Option<f64>,
+    pub end_time: Option<f64>,
+    pub text: Option<String>,
+    pub ordering: Option<i64>,
+    /// Replaces the block's words and recomputes its confidence; send an empty list to clear them
+    pub words: Option<Vec<TranscriptWord>>,
+    /// Overrides the block confidence from 0 to 1
+    pub confidence: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SplitBlockRequest {
+    /// Character offset in the block's text where the second block begins
+    pub offset: usize,
+    /// Time where the second block begins (the start of its first word if omitted)
+    pub time: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MergeBlocksRequest {
+    /// Two or more adjacent blocks; the merged block keeps the ID of the first one
+    pub block_ids: Vec<String>,
+}
+
+/// Default threshold of the low-confidence word query
+const DEFAULT_MAX_CONFIDENCE: f64 = 0.6;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct LowConfidenceQuery {
+    /// Return words with a confidence strictly below this value (default 0.6)
+    pub max_confidence: Option<f64>,
+}
+
+/// Helper: Reject block timings that can't be placed on the video timeline
+pub(crate) fn validate_timing(start_time: f64, end_time: f64) -> Result<(), AppError> {
+    if !start_time.is_finite() || !end_time.is_finite() || start_time < 0.0 {
+        return Err(AppError::BadRequest("Block times must be non-negative numbers".to_string()));
+    }
+    if end_time < start_time {
+        return Err(AppError::BadRequest("Block end_time must not be before start_time".to_string()));
+    }
+    Ok(())
+}
+
+/// Helper: Reject confidence scores outside 0..=1
+pub(crate) fn validate_confidence(confidence: Option<f64>) -> Result<(), AppError> {
+    match confidence {
+        Some(confidence) if !(0.0..=1.0).contains(&confidence) => Err(AppError::BadRequest(
+            "Confidence must be between 0 and 1".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Helper: Reject word timings that can't be placed on the timeline or confidences outside 0..=1
+pub(crate) fn validate_words(words: &[TranscriptWord]) -> Result<(), AppError> {
+    for word in words {
+        validate_timing(word.start_time, word.end_time)?;
+        validate_confidence(word.confidence)?;
+    }
+    Ok(())
+}
+
+/// Helper: Load a block and verify it belongs to the given video
+pub(crate) async fn get_video_block(
+    state: &AppState,
+    video_id: &str,
+    block_id: &str,
+) -> Result<TranscriptBlock, AppError> {
+    match state.db.get_transcript_block(block_id).await? {
+        Some(block) if block.video_id == video_id => Ok(block),
+        _ => {
+            warn!(video_id = %video_id, block_id = %block_id, "Transcript block not found");
+            Err(AppError::NotFound("Transcript block not found".to_string()))
+        }
+    }
+}
+
+/// Helper: Find the byte position of each word in a block's text
+///
+/// Returns None if the words don't appear in the text in order, e.g. after the text
+/// was edited by hand.
+fn word_positions(text: &str, words: &[TranscriptWord]) -> Option<Vec<usize>> {
+    let mut positions = Vec::with_capacity(words.len());
+    let mut cursor = 0;
+    for word in words {
+        let needle = word.text.trim();
+        let found = cursor + text[cursor..].find(needle)?;
+        positions.push(found);
+        cursor = found + needle.len();
+    }
+    Some(positions)
+}
+
+/// Split a block at a character offset of its text, returning the shortened block and the new one after it
+///
+/// Words are divided where they appear in the text, or by their start time if they
+/// no longer match it. Blocks without words keep their confidence on both halves.
+pub fn divide_block(
+    block: &TranscriptBlock,
+    offset: usize,
+    time: Option<f64>,
+) -> Result<(TranscriptBlock, TranscriptBlock), AppError> {
+    let split_at = match block.text.char_indices().nth(offset) {
+        Some((index, _)) if offset > 0 => index,
+        _ => return Err(AppError::BadRequest("Split offset must fall inside the block text".to_string())),
+    };
+    let first_text = block.text[..split_at].trim_end();
+    let second_text = block.text[split_at..].trim_start();
+    if first_text.is_empty() || second_text.is_empty() {
+        return Err(AppError::BadRequest("Both parts of a split block need text".to_string()));
+    }
+
+    let words = &block.words.0;
+    let first_count = match (word_positions(&block.text, words), time) {
+        (Some(positions), _) => positions.iter().take_while(|&&p| p < split_at).count(),
+        (None, Some(time)) => words.iter().take_while(|w| w.start_time < time).count(),
+        (None, None) => {
+            return Err(AppError::BadRequest(
+                "A split time is required when the words don't match the block text".to_string(),
+            ))
+        }
+    };
+    let Some(time) = time.or_else(|| words.get(first_count).map(|w| w.start_time)) else {
+        return Err(AppError::BadRequest(
+            "A split time is required when the second part has no word timings".to_string(),
+        ));
+    };
+    if !time.is_finite() || time < block.start_time || time > block.end_time {
+        return Err(AppError::BadRequest("Split time must be within the block".to_string()));
+    }
+
+    let mut first = block.clone();
+    first.end_time = time;
+    first.text = first_text.to_string();
+    first.updated_at = Utc::now();
+
+    let mut second = TranscriptBlock::new(
+        block.video_id.clone(),
+        time,
+        block.end_time,
+        second_text.to_string(),
+        block.ordering + 1,
+    );
+    second.reviewed_at = block.reviewed_at;
+    second.speaker_id = block.speaker_id.clone();
+
+    if words.is_empty() {
+        second.confidence = block.confidence;
+    } else {
+        first.set_words(words[..first_count].to_vec());
+        second.set_words(words[first_count..].to_vec());
+    }
+
+    Ok((first, second))
+}
+
+/// Merge adjacent blocks, given in transcript order, into the first of them
+///
+/// The merged block keeps the first block's speaker, and stays reviewed only if all
+/// of the blocks were.
+pub fn join_blocks(blocks: &[TranscriptBlock]) -> TranscriptBlock {
+    let mut merged = blocks[0].clone();
+    merged.start_time = blocks.iter().map(|b| b.start_time).fold(f64::INFINITY, f64::min);
+    merged.end_time = blocks.iter().map(|b| b.end_time).fold(f64::NEG_INFINITY, f64::max);
+    merged.text = blocks
+        .iter()
+        .map(|b| b.text.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    merged.previous_text = None;
+
+    let words: Vec<TranscriptWord> = blocks.iter().flat_map(|b| b.words.iter().cloned()).collect();
+    if words.is_empty() {
+        let scores: Vec<f64> = blocks.iter().filter_map(|b| b.confidence).collect();
+        merged.confidence = (!scores.is_empty()).then(|| scores.iter().sum::<f64>() / scores.len() as f64);
+    } else {
+        merged.set_words(words);
+    }
+
+    merged.reviewed_at = blocks
+        .iter()
+        .map(|b| b.reviewed_at)
+        .collect::<Option<Vec<_>>>()
+        .and_then(|times| times.into_iter().max());
+    merged.updated_at = Utc::now();
+    merged
+}
+
+/// List the transcript blocks of a video
+#[utoipa::path(
+    get,
+    path = "/api/videos/{id}/transcript",
+    params(("id" = String, Path, description = "Video ID")),
+    responses(
+        (status = 200, description = "Transcript blocks in display order", body = Vec<TranscriptBlock>),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn list_blocks(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let blocks = state.db.list_transcript_blocks(&video_id).await?;
+
+    Ok((StatusCode::OK, Json(blocks)))
+}
+
+/// Create a transcript block
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/transcript/blocks",
+    params(("id" = String, Path, description = "Video ID")),
+    request_body = CreateBlockRequest,
+    responses(
+        (status = 201, description = "Block created", body = TranscriptBlock),
+        (status = 400, description = "Invalid timing or confidence"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video or speaker not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn create_block(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+    Json(req): Json<CreateBlockRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+    validate_timing(req.start_time, req.end_time)?;
+    validate_words(&req.words)?;
+    validate_confidence(req.confidence)?;
+    if let Some(speaker_id) = &req.speaker_id {
+        get_video_speaker(&state, &video_id, speaker_id).await?;
+    }
+
+    let ordering = match req.ordering {
+        Some(ordering) => ordering,
+        None => state.db.next_transcript_block_ordering(&video_id).await?,
+    };
+
+    let mut block = TranscriptBlock::new(video_id, req.start_time, req.end_time, req.text, ordering);
+    block.set_words(req.words);
+    if req.confidence.is_some() {
+        block.confidence = req.confidence;
+    }
+    block.speaker_id = req.speaker_id;
+    state.db.insert_transcript_block(&block, &auth_user.user_id).await?;
+
+    info!(
+        video_id = %block.video_id,
+        block_id = %block.id,
+        "Created transcript block"
+    );
+
+    Ok((StatusCode::CREATED, Json(block)))
+}
+
+/// Update a transcript block
+#[utoipa::path(
+    patch,
+    path = "/api/videos/{id}/transcript/blocks/{block_id}",
+    params(
+        ("id" = String, Path, description = "Video ID"),
+        ("block_id" = String, Path, description = "Transcript block ID")
+    ),
+    request_body = UpdateBlockRequest,
+    responses(
+        (status = 200, description = "Block updated", body = TranscriptBlock),
+        (status = 400, description = "Invalid timing or confidence"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video or block not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn update_block(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path((video_id, block_id)): Path<(String, String)>,
+    Json(req): Json<UpdateBlockRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+    let mut block = get_video_block(&state, &video_id, &block_id).await?;
+
+    if let Some(start_time) = req.start_time {
+        block.start_time = start_time;
+    }
+    if let Some(end_time) = req.end_time {
+        block.end_time = end_time;
+    }
+    if let Some(text) = req.text {
+        block.text = text;
+    }
+    if let Some(ordering) = req.ordering {
+        block.ordering = ordering;
+    }
+    if let Some(words) = req.words {
+        validate_words(&words)?;
+        block.set_words(words);
+    }
+    if req.confidence.is_some() {
+        validate_confidence(req.confidence)?;
+        block.confidence = req.confidence;
+    }
+    validate_timing(block.start_time, block.end_time)?;
+    block.updated_at = Utc::now();
+
+    state.db.update_transcript_block(&block, &auth_user.user_id).await?;
+
+    info!(
+        video_id = %video_id,
+        block_id = %block_id,
+        "Updated transcript block"
+    );
+
+    Ok((StatusCode::OK, Json(block)))
+}
+
+/// Delete a transcript block
+#[utoipa::path(
+    delete,
+    path = "/api/videos/{id}/transcript/blocks/{block_id}",
+    params(
+        ("id" = String, Path, description = "Video ID"),
+        ("block_id" = String, Path, description = "Transcript block ID")
+    ),
+    responses(
+        (status = 204, description = "Block deleted"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video or block not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn delete_block(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path((video_id, block_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+    get_video_block(&state, &video_id, &block_id).await?;
+
+    state.db.delete_transcript_block(&block_id, &auth_user.user_id).await?;
+
+    info!(
+        video_id = %video_id,
+        block_id = %block_id,
+        "Deleted transcript block"
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Split a transcript block in two
+///
+/// The first part keeps the block's ID; the second is a new block placed right after
+/// it. Word timings go with the text they belong to.
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/transcript/blocks/{block_id}/split",
+    params(
+        ("id" = String, Path, description = "Video ID"),
+        ("block_id" = String, Path, description = "Transcript block ID")
+    ),
+    request_body = SplitBlockRequest,
+    responses(
+        (status = 200, description = "The two blocks, in order", body = Vec<TranscriptBlock>),
+        (status = 400, description = "Invalid split offset or time"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video or block not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn split_block(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path((video_id, block_id)): Path<(String, String)>,
+    Json(req): Json<SplitBlockRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+    let block = get_video_block(&state, &video_id, &block_id).await?;
+
+    let (first, second) = divide_block(&block, req.offset, req.time)?;
+    state
+        .db
+        .split_transcript_block(&first, &second, &auth_user.user_id)
+        .await?;
+
+    info!(
+        video_id = %video_id,
+        block_id = %block_id,
+        new_block_id = %second.id,
+        "Split transcript block"
+    );
+
+    Ok((StatusCode::OK, Json(vec![first, second])))
+}
+
+/// Merge adjacent transcript blocks into one
+///
+/// Texts are joined with a space and words are kept with their timings. The other
+/// blocks are deleted in the same transaction.
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/transcript/blocks/merge",
+    params(("id" = String, Path, description = "Video ID")),
+    request_body = MergeBlocksRequest,
+    responses(
+        (status = 200, description = "Merged block", body = TranscriptBlock),
+        (status = 400, description = "Fewer than two blocks, or blocks that aren't adjacent"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video or block not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn merge_blocks(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+    Json(req): Json<MergeBlocksRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let transcript = state.db.list_transcript_blocks(&video_id).await?;
+    let mut positions = Vec::with_capacity(req.block_ids.len());
+    for block_id in &req.block_ids {
+        let Some(position) = transcript.iter().position(|b| &b.id == block_id) else {
+            warn!(video_id = %video_id, block_id = %block_id, "Transcript block not found");
+            return Err(AppError::NotFound("Transcript block not found".to_string()));
+        };
+        positions.push(position);
+    }
+    positions.sort_unstable();
+    positions.dedup();
+    if positions.len() < 2 {
+        return Err(AppError::BadRequest("At least two blocks are required".to_string()));
+    }
+    if positions.windows(2).any(|pair| pair[1] != pair[0] + 1) {
+        return Err(AppError::BadRequest("Only adjacent blocks can be merged".to_string()));
+    }
+
+    let blocks = &transcript[positions[0]..=positions[positions.len() - 1]];
+    let merged = join_blocks(blocks);
+    let removed: Vec<String> = blocks[1..].iter().map(|b| b.id.clone()).collect();
+    state
+        .db
+        .merge_transcript_blocks(&merged, &removed, &auth_user.user_id)
+        .await?;
+
+    info!(
+        video_id = %video_id,
+        block_id = %merged.id,
+        merged = blocks.len(),
+        "Merged transcript blocks"
+    );
+
+    Ok((StatusCode::OK, Json(merged)))
+}
+
+/// List the words of a video's transcript below a confidence threshold, least confident first
+///
+/// Words without a confidence score are left out.
+#[utoipa::path(
+    get,
+    path = "/api/videos/{id}/transcript/words",
+    params(
+        ("id" = String, Path, description = "Video ID"),
+        LowConfidenceQuery
+    ),
+    responses(
+        (status = 200, description = "Low-confidence words with their block", body = Vec<LowConfidenceWord>),
+        (status = 400, description = "Invalid confidence threshold"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn list_low_confidence_words(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+    Query(query): Query<LowConfidenceQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    let max_confidence = query.max_confidence.unwrap_or(DEFAULT_MAX_CONFIDENCE);
+    if !max_confidence.is_finite() {
+        return Err(AppError::BadRequest("max_confidence must be a number".to_string()));
+    }
+
+    let words = state
+        .db
+        .list_low_confidence_words(&video_id, max_confidence)
+        .await?;
+
+    Ok((StatusCode::OK, Json(words)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(start_time: f64, end_time: f64, text: &str) -> TranscriptWord {
+        TranscriptWord {
+            start_time,
+            end_time,
+            text: text.to_string(),
+            confidence: None,
+        }
+    }
+
+    fn block(text: &str, words: Vec<TranscriptWord>) -> TranscriptBlock {
+        let mut block = TranscriptBlock::new("video".to_string(), 0.0, 3.0, text.to_string(), 4);
+        block.set_words(words);
+        block
+    }
+
+    #[test]
+    fn test_divide_block_by_text_position() {
+        let block = block("so so, so", vec![word(0.0, 1.0, "so"), word(1.0, 2.0, "so,"), word(2.0, 3.0, "so")]);
+
+        let (first, second) = divide_block(&block, 6, None).unwrap();
+        assert_eq!((first.text.as_str(), second.text.as_str()), ("so so,", "so"));
+        assert_eq!(first.words.len(), 2);
+        assert_eq!(second.words[0].start_time, 2.0);
+        assert_eq!((first.end_time, second.start_time), (2.0, 2.0));
+        assert_eq!(second.ordering, 5);
+
+        assert!(divide_block(&block, 9, None).is_err());
+        assert!(divide_block(&block, 0, Some(1.0)).is_err());
+    }
+
+    #[test]
+    fn test_divide_block_falls_back_to_time() {
+        // Words that no longer match the edited text are divided by time
+        let block = block("One two", vec![word(0.0, 1.0, "Won"), word(1.5, 3.0, "too")]);
+
+        assert!(divide_block(&block, 4, None).is_err());
+        let (first, second) = divide_block(&block, 4, Some(1.2)).unwrap();
+        assert_eq!(first.words[0].text, "Won");
+        assert_eq!(second.words[0].text, "too");
+    }
+}
@@ -0,0 +1,383 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, sync::Arc};
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::{
+    auth::AuthUser,
+    db::{TranscriptBlock, TranscriptWord},
+    error::AppError,
+    transcript::get_video_block,
+    upload::{get_owned_video, AppState},
+};
+
+/// Romanization schemes for Sanskrit and Pali
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Scheme {
+    /// Diacritics, e.g. "Śāriputra"
+    Iast,
+    /// Case-sensitive ASCII, e.g. "zAriputra"; has no capitals, so converted words are lowercased
+    HarvardKyoto,
+    /// ASCII with punctuation marks, e.g. "\"saariputra"
+    Velthuis,
+    /// Diacritics dropped for readers, e.g. "Shariputra"; can't be converted back
+    Ascii,
+}
+
+/// Spellings of each letter that differs between schemes: (IAST, Harvard-Kyoto, Velthuis, ASCII)
+///
+/// Where two IAST letters share a spelling, the first one is used when reading that scheme.
+const LETTERS: &[(char, &str, &str, &str)] = &[
+    ('ā', "A", "aa", "a"),
+    ('ī', "I", "ii", "i"),
+    ('ū', "U", "uu", "u"),
+    ('ṛ', "R", ".r", "ri"),
+    ('ṝ', "RR", ".rr", "ri"),
+    ('ḷ', "lR", ".l", "li"),
+    ('ḹ', "lRR", ".ll", "li"),
+    ('ṃ', "M", ".m", "m"),
+    ('ṁ', "M", ".m", "m"),
+    ('ḥ', "H", ".h", "h"),
+    ('ṅ', "G", "\"n", "n"),
+    ('ñ', "J", "~n", "n"),
+    ('ṭ', "T", ".t", "t"),
+    ('ḍ', "D", ".d", "d"),
+    ('ṇ', "N", ".n", "n"),
+    ('ś', "z", "\"s", "sh"),
+    ('ṣ', "S", ".s", "sh"),
+];
+
+/// Combining marks folded into precomposed IAST letters: (base, mark, letter)
+const COMPOSITIONS: &[(char, char, char)] = &[
+    ('a', '\u{304}', 'ā'), ('A', '\u{304}', 'Ā'),
+    ('i', '\u{304}', 'ī'), ('I', '\u{304}', 'Ī'),
+    ('u', '\u{304}', 'ū'), ('U', '\u{304}', 'Ū'),
+    ('r', '\u{323}', 'ṛ'), ('R', '\u{323}', 'Ṛ'),
+    ('ṛ', '\u{304}', 'ṝ'), ('Ṛ', '\u{304}', 'Ṝ'),
+    ('l', '\u{323}', 'ḷ'), ('L', '\u{323}', 'Ḷ'),
+    ('ḷ', '\u{304}', 'ḹ'), ('Ḷ', '\u{304}', 'Ḹ'),
+    ('m', '\u{323}', 'ṃ'), ('M', '\u{323}', 'Ṃ'),
+    ('m', '\u{307}', 'ṁ'), ('M', '\u{307}', 'Ṁ'),
+    ('h', '\u{323}', 'ḥ'), ('H', '\u{323}', 'Ḥ'),
+    ('n', '\u{307}', 'ṅ'), ('N', '\u{307}', 'Ṅ'),
+    ('n', '\u{303}', 'ñ'), ('N', '\u{303}', 'Ñ'),
+    ('t', '\u{323}', 'ṭ'), ('T', '\u{323}', 'Ṭ'),
+    ('d', '\u{323}', 'ḍ'), ('D', '\u{323}', 'Ḍ'),
+    ('n', '\u{323}', 'ṇ'), ('N', '\u{323}', 'Ṇ'),
+    ('s', '\u{301}', 'ś'), ('S', '\u{301}', 'Ś'),
+    ('s', '\u{323}', 'ṣ'), ('S', '\u{323}', 'Ṣ'),
+];
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TransliterateRequest {
+    pub text: String,
+    pub from: Scheme,
+    pub to: Scheme,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransliterateResponse {
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TransliterateTranscriptRequest {
+    pub from: Scheme,
+    pub to: Scheme,
+    /// Blocks to convert; all blocks of the video if omitted. Required when reading
+    /// Harvard-Kyoto or Velthuis, whose spellings also occur in English text.
+    pub block_ids: Option<Vec<String>>,
+}
+
+/// Helper: Replace letters written with combining marks by their precomposed form
+fn compose(text: &str) -> String {
+    let mut output: Vec<char> = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        let composed = output.last().and_then(|&base| {
+            COMPOSITIONS
+                .iter()
+                .find(|&&(b, mark, _)| b == base && mark == c)
+                .map(|&(_, _, letter)| letter)
+        });
+        match composed {
+            Some(letter) => *output.last_mut().unwrap() = letter,
+            None => output.push(c),
+        }
+    }
+    output.into_iter().collect()
+}
+
+/// Helper: Read Harvard-Kyoto or Velthuis text into IAST, longest spelling first
+fn read_scheme(text: &str, from: Scheme) -> String {
+    let mut rules: Vec<(String, String)> = Vec::new();
+    for &(iast, hk, velthuis, _) in LETTERS {
+        match from {
+            Scheme::HarvardKyoto => rules.push((hk.to_string(), iast.to_string())),
+            Scheme::Velthuis => {
+                let upper = iast.to_uppercase().to_string();
+                rules.push((velthuis.to_string(), iast.to_string()));
+                rules.push((velthuis.to_uppercase(), upper.clone()));
+                // Capital long vowels are also written "Aa"
+                let mut chars = velthuis.chars();
+                if let Some(first) = chars.next().filter(|c| c.is_alphabetic()) {
+                    rules.push((first.to_uppercase().chain(chars).collect(), upper));
+                }
+            }
+            Scheme::Iast | Scheme::Ascii => {}
+        }
+    }
+    // Stable sort keeps table order among spellings of the same length
+    rules.sort_by_key(|(spelling, _)| std::cmp::Reverse(spelling.len()));
+
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        match rules.iter().find(|(spelling, _)| rest.starts_with(spelling.as_str())) {
+            Some((spelling, letter)) => {
+                output.push_str(letter);
+                rest = &rest[spelling.len()..];
+            }
+            None => {
+                output.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    output
+}
+
+/// Helper: Whether a character is an IAST letter with a diacritic
+fn is_diacritic_letter(c: char) -> bool {
+    let lower = c.to_lowercase().next().unwrap_or(c);
+    LETTERS.iter().any(|(iast, ..)| *iast == lower)
+}
+
+/// Helper: Write IAST text in another scheme
+///
+/// Only words with a diacritic are converted, so English around the Pali keeps its spelling and case.
+fn write_scheme(text: &str, to: Scheme) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while !rest.is_empty() {
+        let split = rest
+            .char_indices()
+            .find(|&(_, c)| c.is_alphabetic() != rest.starts_with(char::is_alphabetic))
+            .map_or(rest.len(), |(i, _)| i);
+        let (run, tail) = rest.split_at(split);
+        if run.chars().any(is_diacritic_letter) {
+            write_word(run, to, &mut output);
+        } else {
+            output.push_str(run);
+        }
+        rest = tail;
+    }
+    output
+}
+
+/// Helper: Write one IAST word in another scheme
+fn write_word(word: &str, to: Scheme, output: &mut String) {
+    for c in word.chars() {
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        let is_upper = lower != c;
+        let Some(&(_, hk, velthuis, ascii)) = LETTERS.iter().find(|(iast, ..)| *iast == lower) else {
+            match to {
+                Scheme::HarvardKyoto => output.push(lower),
+                _ => output.push(c),
+            }
+            continue;
+        };
+
+        match (to, is_upper) {
+            (Scheme::Iast, _) => output.push(c),
+            (Scheme::HarvardKyoto, _) => output.push_str(hk),
+            (Scheme::Velthuis, false) => output.push_str(velthuis),
+            (Scheme::Velthuis, true) => output.push_str(&velthuis.to_uppercase()),
+            (Scheme::Ascii, false) => output.push_str(ascii),
+            (Scheme::Ascii, true) => {
+                let mut chars = ascii.chars();
+                output.extend(chars.next().map(|first| first.to_ascii_uppercase()));
+                output.push_str(chars.as_str());
+            }
+        }
+    }
+}
+
+/// Convert text between romanization schemes
+///
+/// Text is read into IAST (composing letters written with combining marks) and
+/// written in the target scheme. Plain ASCII has no diacritics to recover, so
+/// reading it leaves the text unchanged. Words without diacritics and characters
+/// that aren't part of a scheme pass through.
+pub fn transliterate(text: &str, from: Scheme, to: Scheme) -> String {
+    let text = compose(text);
+    let iast = match from {
+        Scheme::Iast | Scheme::Ascii => text,
+        Scheme::HarvardKyoto | Scheme::Velthuis => read_scheme(&text, from),
+    };
+    write_scheme(&iast, to)
+}
+
+/// Convert text between IAST, Harvard-Kyoto, Velthuis and plain ASCII
+#[utoipa::path(
+    post,
+    path = "/api/transliterate",
+    request_body = TransliterateRequest,
+    responses(
+        (status = 200, description = "Converted text", body = TransliterateResponse),
+        (status = 401, description = "Unauthorized - authentication required")
+    ),
+    tag = "transcript"
+)]
+pub async fn transliterate_text(
+    _auth_user: AuthUser,
+    Json(req): Json<TransliterateRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok((
+        StatusCode::OK,
+        Json(TransliterateResponse {
+            text: transliterate(&req.text, req.from, req.to),
+        }),
+    ))
+}
+
+/// Convert the stored text of a video's transcript between romanization schemes
+///
+/// Word timings are converted along with the text. Confidence, review marks and
+/// `previous_text` are unchanged.
+#[utoipa::path(
+    post,
+    path = "/api/videos/{id}/transcript/transliterate",
+    params(("id" = String, Path, description = "Video ID")),
+    request_body = TransliterateTranscriptRequest,
+    responses(
+        (status = 200, description = "Blocks whose text changed", body = Vec<TranscriptBlock>),
+        (status = 400, description = "Reading Harvard-Kyoto or Velthuis without choosing blocks"),
+        (status = 401, description = "Unauthorized - authentication required"),
+        (status = 404, description = "Video or block not found"),
+        (status = 409, description = "A block was edited while transliterating"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "transcript"
+)]
+pub async fn transliterate_transcript(
+    State(state): State<Arc<AppState>>,
+    auth_user: AuthUser,
+    Path(video_id): Path<String>,
+    Json(req): Json<TransliterateTranscriptRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
+    // Capitals and marks like "aa" or ".m" are letters in these schemes, so English would be mangled
+    if matches!(req.from, Scheme::HarvardKyoto | Scheme::Velthuis) && req.block_ids.is_none() {
+        return Err(AppError::BadRequest(
+            "Choose the blocks to convert when reading Harvard-Kyoto or Velthuis".to_string(),
+        ));
+    }
+
+    let blocks = match &req.block_ids {
+        Some(block_ids) => {
+            // A repeated ID would be rewritten twice and fail the concurrency check
+            let mut seen = HashSet::new();
+            let mut blocks = Vec::with_capacity(block_ids.len());
+            for block_id in block_ids.iter().filter(|id| seen.insert(id.as_str())) {
+                blocks.push(get_video_block(&state, &video_id, block_id).await?);
+            }
+            blocks
+        }
+        None => state.db.list_transcript_blocks(&video_id).await?,
+    };
+
+    let mut changed: Vec<TranscriptBlock> = Vec::new();
+    for mut block in blocks {
+        let text = transliterate(&block.text, req.from, req.to);
+        let words: Vec<TranscriptWord> = block
+            .words
+            .iter()
+            .map(|word| TranscriptWord {
+                text: transliterate(&word.text, req.from, req.to),
+                ..word.clone()
+            })
+            .collect();
+        if text != block.text || words != *block.words {
+            block.text = text;
+            block.words.0 = words;
+            changed.push(block);
+        }
+    }
+
+    if !state.db.rewrite_transcript_block_texts(&changed, &auth_user.user_id).await? {
+        return Err(AppError::Conflict(
+            "Transcript was edited during transliteration; try again".to_string(),
+        ));
+    }
+
+    info!(
+        video_id = %video_id,
+        from = ?req.from,
+        to = ?req.to,
+        changed = changed.len(),
+        "Transliterated transcript"
+    );
+
+    let mut updated = Vec::with_capacity(changed.len());
+    for block in changed {
+        if let Some(block) = state.db.get_transcript_block(&block.id).await? {
+            updated.push(block);
+        }
+    }
+
+    Ok((StatusCode::OK, Json(updated)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trips_through_iast() {
+        let iast = "namo tassa bhagavato arahato sammāsambuddhassa. śāriputra, kṛṣṇa, saṃgha, jñāna, ṭhāna, ḷ";
+        for scheme in [Scheme::HarvardKyoto, Scheme::Velthuis] {
+            let converted = transliterate(iast, Scheme::Iast, scheme);
+            assert!(converted.is_ascii(), "{converted}");
+            let back = transliterate(&converted, scheme, Scheme::Iast);
+            assert_eq!(back, iast);
+        }
+    }
+
+    #[test]
+    fn test_english_words_are_left_alone() {
+        let text = "The Buddha taught Mettā to Sāriputta, not to Devadatta.";
+        assert_eq!(
+            transliterate(text, Scheme::Iast, Scheme::HarvardKyoto),
+            "The Buddha taught mettA to sAriputta, not to Devadatta."
+        );
+        assert_eq!(
+            transliterate(text, Scheme::Iast, Scheme::Velthuis),
+            "The Buddha taught Mettaa to Saariputta, not to Devadatta."
+        );
+    }
+
+    #[test]
+    fn test_scheme_spellings() {
+        assert_eq!(transliterate("Śāriputra", Scheme::Iast, Scheme::HarvardKyoto), "zAriputra");
+        assert_eq!(transliterate("Śāriputra", Scheme::Iast, Scheme::Velthuis), "\"Saariputra");
+        assert_eq!(transliterate("Śāriputra kṛṣṇa", Scheme::Iast, Scheme::Ascii), "Shariputra krishna");
+        assert_eq!(transliterate("zAriputra", Scheme::HarvardKyoto, Scheme::Velthuis), "\"saariputra");
+        assert_eq!(transliterate("AAnanda", Scheme::Velthuis, Scheme::Iast), "Ānanda");
+        assert_eq!(transliterate("Aananda", Scheme::Velthuis, Scheme::Iast), "Ānanda");
+    }
+
+    #[test]
+    fn test_combining_marks_are_composed() {
+        let decomposed = "sa\u{304}dhu s\u{301}ri\u{304} r\u{323}\u{304}";
+        assert_eq!(transliterate(decomposed, Scheme::Iast, Scheme::Iast), "sādhu śrī ṝ");
+        assert_eq!(transliterate(decomposed, Scheme::Iast, Scheme::HarvardKyoto), "sAdhu zrI RR");
+    }
+}
@@ -15,10 +15,18 @@ use uuid::Uuid;
 
 use crate::{
     auth::AuthUser,
-    db::{Database, Video},
+    db::{Database, SilenceAnalysis, SilenceInterval, Video},
+    diarizer::Diarizer,
     error::AppError,
+    events::EventBus,
     filestore::FileStore,
+    jobs::JobRegistry,
+    language::{normalize_language, LanguageDetector},
+    messages::ProgressEvent,
     session_store::SessionStore,
+    silence::{detect_silences, SilenceParams},
+    speakers::spawn_diarization,
+    transcriber::Transcriber,
 };
 
 #[derive(Serialize, Deserialize, ToSchema)]
@@ -31,20 +39,84 @@ pub struct AppState {
     pub db: Database,
     pub filestore: Arc<dyn FileStore>,
     pub session_store: Arc<dyn SessionStore>,
+    pub transcriber: Arc<dyn Transcriber>,
+    /// Detects the spoken language of videos that don't have one set
+    pub language_detector: Arc<dyn LanguageDetector>,
+    /// Speaker diarization backend, or None to leave speaker assignment to users
+    pub diarizer: Option<Arc<dyn Diarizer>>,
+    pub jobs: JobRegistry,
+    pub events: EventBus,
+}
+
+/// Look up a video and verify it belongs to the authenticated user
+///
+/// Videos owned by other users are reported as not found so their existence isn't leaked.
+pub(crate) async fn get_owned_video(
+    state: &AppState,
+    video_id: &str,
+    auth_user: &AuthUser,
+) -> Result<Video, AppError> {
+    match state.db.get_video(video_id).await? {
+        Some(video) if video.user_id == auth_user.user_id => Ok(video),
+        _ => {
+            warn!(video_id = %video_id, user_id = %auth_user.user_id, "Video not found for user");
+            Err(AppError::NotFound("Video not found".to_string()))
+        }
+    }
+}
+
+/// Copy a file from the FileStore to a local temp file for tools like ffmpeg
+/// The temp file is deleted when the returned path is dropped
+pub(crate) async fn copy_to_temp_file(
+    filestore: &Arc<dyn FileStore>,
+    file_id: &str,
+) -> Result<tempfile::TempPath, AppError> {
+    let file_data = filestore.get_file(file_id).await?;
+
+    let extension = std::path::Path::new(file_id)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| format!(".{}", e))
+        .unwrap_or_default();
+
+    let temp_path = tempfile::Builder::new()
+        .prefix("gatha_")
+        .suffix(&extension)
+        .tempfile()
+        .map_err(|e| AppError::Internal(format!("Failed to create temp file: {}", e)))?
+        .into_temp_path();
+
+    tokio::fs::write(&temp_path, &file_data).await
+        .map_err(|e| {
+            error!(error = %e, file_id = file_id, "Failed to write temp file");
+            AppError::Internal(format!("Failed to write temp file: {}", e))
+        })?;
+
+    Ok(temp_path)
+}
+
+/// Results of post-upload video processing
+#[derive(Debug, Default)]
+struct ProcessedVideo {
+    width: Option<i64>,
+    height: Option<i64>,
+    duration_seconds: Option<f64>,
+    /// Silent intervals, or None if silence detection didn't run
+    silences: Option<Vec<SilenceInterval>>,
 }
 
 /// Process MP4 video to optimize for streaming and extract metadata
 /// Uses ffmpeg with -movflags +faststart to reorganize the file
 /// Works with any FileStore implementation by using temp files
-/// Returns (width, height, duration_seconds)
+/// Returns dimensions, duration and detected silences
 async fn process_video_for_streaming(
     filestore: &Arc<dyn FileStore>,
     file_id: &str,
-) -> Result<(Option<i64>, Option<i64>, Option<f64>), AppError> {
+) -> Result<ProcessedVideo, AppError> {
     // Only process MP4 files
     if !file_id.ends_with(".mp4") && !file_id.ends_with(".MP4") {
         info!(file_id = file_id, "Skipping video processing for non-MP4 file");
-        return Ok((None, None, None));
+        return Ok(ProcessedVideo::default());
     }
 
     let process_start = Instant::now();
@@ -79,7 +151,7 @@ async fn process_video_for_streaming(
     // -c copy: copy streams without re-encoding (fast)
     // -f mp4: explicitly specify output format
     let output = Command::new("ffmpeg")
-        .args(&[
+        .args([
             "-i", &temp_input,
             "-movflags", "+faststart",
             "-c", "copy",
@@ -91,7 +163,7 @@ async fn process_video_for_streaming(
         .await
         .map_err(|e| {
             error!(error = %e, file_id = file_id, "Failed to execute ffmpeg");
-            let _ = tokio::fs::remove_file(&temp_input);
+            let _ = std::fs::remove_file(&temp_input);
             AppError::Internal(format!("Video processing failed: {}", e))
         })?;
 
@@ -108,7 +180,7 @@ async fn process_video_for_streaming(
         );
         // Clean up output temp file if it exists
         let _ = tokio::fs::remove_file(&temp_output).await;
-        return Ok((None, None, None)); // Don't fail upload, just skip processing
+        return Ok(ProcessedVideo::default()); // Don't fail upload, just skip processing
     }
 
     info!(file_id = file_id, "ffmpeg processing succeeded, extracting metadata");
@@ -116,11 +188,20 @@ async fn process_video_for_streaming(
     // Step 3: Extract metadata from the processed file
     let (width, height, duration_seconds) = extract_metadata_from_file(&temp_output).await?;
 
+    // Detect silent intervals with default parameters (re-analysis is available per request)
+    let silences = match detect_silences(&temp_output, SilenceParams::default(), duration_seconds).await {
+        Ok(silences) => Some(silences),
+        Err(e) => {
+            warn!(error = %e, file_id = file_id, "Silence detection failed, continuing without silences");
+            None
+        }
+    };
+
     // Step 4: Read processed file and save back to FileStore
     let processed_data = tokio::fs::read(&temp_output).await
         .map_err(|e| {
             error!(error = %e, file_id = file_id, "Failed to read processed file");
-            let _ = tokio::fs::remove_file(&temp_output);
+            let _ = std::fs::remove_file(&temp_output);
             AppError::Internal(format!("Failed to read processed file: {}", e))
         })?;
 
@@ -152,7 +233,12 @@ async fn process_video_for_streaming(
         "Video processing completed successfully"
     );
 
-    Ok((width, height, duration_seconds))
+    Ok(ProcessedVideo {
+        width,
+        height,
+        duration_seconds,
+        silences,
+    })
 }
 
 #[derive(Debug, Deserialize)]
@@ -174,12 +260,12 @@ struct FfprobeOutput {
 }
 
 /// Extract video metadata from a file path using ffprobe
-async fn extract_metadata_from_file(file_path: &str) -> Result<(Option<i64>, Option<i64>, Option<f64>), AppError> {
+pub(crate) async fn extract_metadata_from_file(file_path: &str) -> Result<(Option<i64>, Option<i64>, Option<f64>), AppError> {
     info!(file_path = file_path, "Extracting video metadata with ffprobe");
 
     // Run ffprobe to get metadata
     let output = Command::new("ffprobe")
-        .args(&[
+        .args([
             "-v", "quiet",
             "-print_format", "json",
             "-show_format",
@@ -236,14 +322,28 @@ async fn extract_metadata_from_file(file_path: &str) -> Result<(Option<i64>, Opt
     Ok((width, height, duration))
 }
 
+/// Helper: Read a multipart `language` field, or None if it is blank
+async fn read_language(field: axum::extract::multipart::Field<'_>) -> Result<Option<String>, AppError> {
+    let value = field.text().await.map_err(|e| {
+        AppError::BadRequest(format!("Failed to read language: {}", e))
+    })?;
+    if value.trim().is_empty() {
+        return Ok(None);
+    }
+    normalize_language(&value).map(Some)
+}
+
 /// Handle video upload
+///
+/// An optional `language` field (e.g. "hi"), before or after the `video` field, sets
+/// the spoken language; without it the language is detected when transcription starts.
 #[utoipa::path(
     post,
     path = "/api/videos/upload",
     request_body(content_type = "multipart/form-data"),
     responses(
         (status = 200, description = "Video uploaded successfully", body = UploadResponse),
-        (status = 400, description = "Bad request - missing video file or invalid data"),
+        (status = 400, description = "Bad request - missing video file, invalid language or invalid data"),
         (status = 401, description = "Unauthorized - authentication required"),
         (status = 500, description = "Internal server error - failed to save file or database error")
     ),
@@ -256,6 +356,9 @@ pub async fn upload_video(
 ) -> Result<impl IntoResponse, AppError> {
     let upload_start = Instant::now();
 
+    // Optional spoken language of the video
+    let mut language: Option<String> = None;
+
     // Process multipart fields
     while let Some(mut field) = multipart.next_field().await.map_err(|e| {
         warn!(error = %e, "Failed to read multipart field");
@@ -263,6 +366,13 @@ pub async fn upload_video(
     })? {
         let name = field.name().unwrap_or("").to_string();
 
+        if name == "language" {
+            if let Some(value) = read_language(field).await? {
+                language = Some(value);
+            }
+            continue;
+        }
+
         if name == "video" {
             // Get original filename
             let original_filename = field.file_name().ok_or_else(|| {
@@ -349,9 +459,52 @@ pub async fn upload_video(
                 AppError::BadRequest(format!("Upload failed: {}", e))
             })?;
 
+            // Fields after the video are read too, so a trailing language isn't lost
+            drop(field);
+            let trailing_language = async {
+                let mut trailing = None;
+                while let Some(field) = multipart.next_field().await.map_err(|e| {
+                    AppError::BadRequest(format!("Failed to read multipart: {}", e))
+                })? {
+                    if field.name() == Some("language") {
+                        trailing = read_language(field).await?;
+                    }
+                }
+                Ok::<_, AppError>(trailing)
+            };
+            match trailing_language.await {
+                Ok(Some(value)) => language = Some(value),
+                Ok(None) => {}
+                Err(e) => {
+                    let _ = state.filestore.delete_file(&file_path).await;
+                    return Err(e);
+                }
+            }
+
+            state.events.publish_progress(
+                &auth_user.user_id,
+                ProgressEvent::UploadProcessing {
+                    video_id: video_id.clone(),
+                    filename: original_filename.clone(),
+                },
+            );
+
             // Process video to optimize for streaming and extract metadata in one pass
             // This works with any FileStore implementation (local, S3, etc.)
-            let (width, height, duration_seconds) = process_video_for_streaming(&state.filestore, &file_path).await?;
+            let processed = match process_video_for_streaming(&state.filestore, &file_path).await {
+                Ok(processed) => processed,
+                Err(e) => {
+                    state.events.publish_progress(
+                        &auth_user.user_id,
+                        ProgressEvent::UploadFailed {
+                            video_id: video_id.clone(),
+                            filename: original_filename.clone(),
+                            error: e.user_message(),
+                        },
+                    );
+                    return Err(e);
+                }
+            };
 
             // Create video record with the same UUID used for file path
             let video = Video {
@@ -360,14 +513,36 @@ pub async fn upload_video(
                 original_filename: original_filename.clone(),
                 user_id: auth_user.user_id.clone(),
                 uploaded_at: chrono::Utc::now(),
-                width,
-                height,
-                duration_seconds,
+                width: processed.width,
+                height: processed.height,
+                duration_seconds: processed.duration_seconds,
+                language: language.take(),
             };
 
             // Save video metadata to database
             state.db.insert_video(&video).await?;
 
+            if let Some(silences) = processed.silences {
+                let params = SilenceParams::default();
+                let analysis = SilenceAnalysis {
+                    video_id: video_id.clone(),
+                    noise_db: params.noise_db,
+                    min_duration: params.min_duration,
+                    analyzed_at: chrono::Utc::now(),
+                };
+                state.db.replace_silences(&analysis, &silences).await?;
+            }
+
+            // Pre-assign speakers so blocks get them as they are transcribed
+            spawn_diarization(state.clone(), video);
+
+            state.events.publish_progress(
+                &auth_user.user_id,
+                ProgressEvent::UploadReady {
+                    video_id: video_id.clone(),
+                },
+            );
+
             let upload_duration = upload_start.elapsed();
             let throughput_mbps = if upload_duration.as_secs_f64() > 0.0 {
                 (total_bytes as f64 / 1_024_000.0) / upload_duration.as_secs_f64()
@@ -446,7 +621,7 @@ fn get_content_type(file_path: &str) -> &'static str {
 
 /// Helper: Parse Range header (e.g., "bytes=0-1023")
 /// Returns (start, end) where end is inclusive
-fn parse_range_header(range_header: &str, file_size: u64) -> Option<(u64, u64)> {
+pub(crate) fn parse_range_header(range_header: &str, file_size: u64) -> Option<(u64, u64)> {
     // Expected format: "bytes=start-end" or "bytes=start-" or "bytes=-end"
     let range_header = range_header.trim();
 
@@ -7,13 +7,15 @@ use axum::{
 };
 use futures_util::sink::SinkExt;
 use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
 use tracing::{error, info, warn};
 
 use crate::{
     auth::AuthUser,
+    error::AppError,
     messages::{ClientMessage, ServerMessage, SessionState},
     session_store::{SessionKey, TranscriptionSession},
-    upload::AppState,
+    upload::{get_owned_video, AppState},
 };
 
 /// WebSocket handler with auth and video_id
@@ -22,14 +24,17 @@ pub async fn ws_handler(
     Path(video_id): Path<String>,
     State(state): State<Arc<AppState>>,
     auth_user: AuthUser,
-) -> Response {
+) -> Result<Response, AppError> {
+    // Live events carry transcript text, so only the owner may subscribe
+    get_owned_video(&state, &video_id, &auth_user).await?;
+
     info!(
         user_id = %auth_user.user_id,
         video_id = %video_id,
         "WebSocket connection upgrading"
     );
 
-    ws.on_upgrade(move |socket| handle_socket(socket, state, auth_user.user_id, video_id))
+    Ok(ws.on_upgrade(move |socket| handle_socket(socket, state, auth_user.user_id, video_id)))
 }
 
 async fn handle_socket(
@@ -46,6 +51,9 @@ async fn handle_socket(
         "WebSocket connection established"
     );
 
+    // Subscribe before the initial sync so no event in between is missed
+    let mut events = state.events.subscribe();
+
     // Load or create session
     let session = match load_or_create_session(&state, &session_key).await {
         Ok(s) => s,
@@ -107,45 +115,70 @@ async fn handle_socket(
         return;
     }
 
-    // Handle incoming messages
+    // Handle incoming messages and forward events of this video
     loop {
-        match socket.recv().await {
-            Some(Ok(Message::Text(text))) => {
-                if let Err(e) = handle_text_message(&text, &state, &session_key).await {
+        tokio::select! {
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    if let Err(e) = handle_text_message(&text, &state, &session_key).await {
+                        warn!(
+                            user_id = %user_id,
+                            video_id = %video_id,
+                            error = %e,
+                            "Error handling message"
+                        );
+                    }
+                }
+                Some(Ok(Message::Close(_))) => {
+                    info!(
+                        user_id = %user_id,
+                        video_id = %video_id,
+                        "WebSocket closed by client"
+                    );
+                    break;
+                }
+                Some(Err(e)) => {
                     warn!(
                         user_id = %user_id,
                         video_id = %video_id,
                         error = %e,
-                        "Error handling message"
+                        "WebSocket error"
                     );
+                    break;
                 }
-            }
-            Some(Ok(Message::Close(_))) => {
-                info!(
-                    user_id = %user_id,
-                    video_id = %video_id,
-                    "WebSocket closed by client"
-                );
-                break;
-            }
-            Some(Err(e)) => {
-                warn!(
-                    user_id = %user_id,
-                    video_id = %video_id,
-                    error = %e,
-                    "WebSocket error"
-                );
-                break;
-            }
-            None => {
-                info!(
-                    user_id = %user_id,
-                    video_id = %video_id,
-                    "WebSocket connection closed"
-                );
-                break;
-            }
-            _ => {} // Ignore other message types
+                None => {
+                    info!(
+                        user_id = %user_id,
+                        video_id = %video_id,
+                        "WebSocket connection closed"
+                    );
+                    break;
+                }
+                _ => {} // Ignore other message types
+            },
+            event = events.recv() => match event {
+                Ok(event) if event.video_id == video_id => {
+                    if let Err(e) = send_message(&mut socket, &event.message).await {
+                        warn!(
+                            user_id = %user_id,
+                            video_id = %video_id,
+                            error = %e,
+                            "Failed to forward event"
+                        );
+                        break;
+                    }
+                }
+                Ok(_) => {} // Event of another video
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(
+                        user_id = %user_id,
+                        video_id = %video_id,
+                        skipped = skipped,
+                        "WebSocket fell behind, events dropped"
+                    );
+                }
+                Err(RecvError::Closed) => break,
+            },
         }
     }
 
@@ -334,6 +367,16 @@ async fn send_state_sync(
         .map_err(|e| format!("Send error: {}", e))
 }
 
+/// Send a server message to client
+async fn send_message(socket: &mut WebSocket, msg: &ServerMessage) -> Result<(), String> {
+    let json = serde_json::to_string(msg).map_err(|e| format!("JSON error: {}", e))?;
+
+    socket
+        .send(Message::Text(json.into()))
+        .await
+        .map_err(|e| format!("Send error: {}", e))
+}
+
 /// Handle text message from client
 async fn handle_text_message(
     text: &str,
@@ -0,0 +1,522 @@
+use futures_util::stream;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+use tracing::{error, info, warn};
+
+use crate::{
+    db::TranscriptWord,
+    language::LanguageDetector,
+    transcriber::{
+        Result, SegmentStream, TranscriberError, TranscriptSegment, Transcriber, TranscriptionRequest,
+    },
+};
+
+/// Output file format requested from whisper.cpp
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WhisperOutputFormat {
+    Json,
+    Srt,
+}
+
+/// Configuration for the whisper.cpp CLI backend
+#[derive(Debug, Clone)]
+pub struct WhisperCppConfig {
+    /// whisper.cpp executable (a bare name is looked up on PATH)
+    pub binary_path: PathBuf,
+    /// GGML model file
+    pub model_path: PathBuf,
+    /// Number of CPU threads, or None to use all available cores
+    pub threads: Option<usize>,
+    pub output_format: WhisperOutputFormat,
+}
+
+impl WhisperCppConfig {
+    /// Build config from WHISPER_CPP_BIN, WHISPER_MODEL_PATH, WHISPER_THREADS and WHISPER_OUTPUT_FORMAT
+    pub fn from_env() -> Self {
+        let binary_path = std::env::var("WHISPER_CPP_BIN").unwrap_or_else(|_| "whisper-cli".to_string());
+        let model_path = std::env::var("WHISPER_MODEL_PATH")
+            .unwrap_or_else(|_| "models/ggml-base.bin".to_string());
+        let threads = std::env::var("WHISPER_THREADS").ok().and_then(|t| t.parse().ok());
+        let output_format = match std::env::var("WHISPER_OUTPUT_FORMAT").as_deref() {
+            Ok("srt") => WhisperOutputFormat::Srt,
+            _ => WhisperOutputFormat::Json,
+        };
+
+        Self {
+            binary_path: PathBuf::from(binary_path),
+            model_path: PathBuf::from(model_path),
+            threads,
+            output_format,
+        }
+    }
+}
+
+/// Transcriber that shells out to a locally installed whisper.cpp binary
+///
+/// Runs with GPU offload disabled so it behaves the same on CPU-only machines.
+pub struct WhisperCppTranscriber {
+    config: WhisperCppConfig,
+}
+
+impl WhisperCppTranscriber {
+    pub fn new(config: WhisperCppConfig) -> Self {
+        Self { config }
+    }
+
+    /// Helper: Fail early with a typed error if the model file is missing
+    async fn check_model(&self) -> Result<()> {
+        if tokio::fs::metadata(&self.config.model_path).await.is_err() {
+            error!(model_path = ?self.config.model_path, "Whisper model file not found");
+            return Err(TranscriberError::ModelNotFound(self.config.model_path.clone()));
+        }
+        Ok(())
+    }
+
+    /// Helper: Run a whisper.cpp command, mapping a missing binary and non-zero exits to typed errors
+    async fn run(&self, mut command: Command) -> Result<std::process::Output> {
+        let output = command.output().await.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                error!(binary_path = ?self.config.binary_path, "whisper.cpp binary not found");
+                TranscriberError::BinaryNotFound(self.config.binary_path.clone())
+            } else {
+                error!(error = %e, "Failed to execute whisper.cpp");
+                TranscriberError::Io(e)
+            }
+        })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            warn!(
+                exit_code = ?output.status.code(),
+                stderr = %stderr,
+                "whisper.cpp failed"
+            );
+            return Err(TranscriberError::Failed(format!(
+                "whisper.cpp exited with {:?}: {}",
+                output.status.code(),
+                stderr.trim()
+            )));
+        }
+
+        Ok(output)
+    }
+
+    fn thread_count(&self) -> usize {
+        self.config.threads.unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(4)
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Transcriber for WhisperCppTranscriber {
+    async fn transcribe(&self, request: TranscriptionRequest) -> Result<SegmentStream> {
+        self.check_model().await?;
+
+        // whisper.cpp writes its result to {output_prefix}.{json,srt}
+        let output_dir = tempfile::tempdir()?;
+        let output_prefix = output_dir.path().join("transcript");
+
+        // The full JSON output includes per-token timings and probabilities
+        let format_flag = match self.config.output_format {
+            WhisperOutputFormat::Json => "--output-json-full",
+            WhisperOutputFormat::Srt => "--output-srt",
+        };
+
+        let mut command = Command::new(&self.config.binary_path);
+        // A cancelled job drops the transcription; the process must not keep running
+        command
+            .kill_on_drop(true)
+            .arg("--model").arg(&self.config.model_path)
+            .arg("--file").arg(&request.audio_path)
+            .arg("--language").arg(request.language.as_deref().unwrap_or("auto"))
+            .arg("--threads").arg(self.thread_count().to_string())
+            .arg("--no-gpu")
+            .arg("--no-prints")
+            .arg(format_flag)
+            .arg("--output-file").arg(&output_prefix);
+        if let Some(prompt) = &request.prompt {
+            command.arg("--prompt").arg(prompt);
+        }
+
+        info!(
+            audio_path = ?request.audio_path,
+            time_offset = request.time_offset,
+            language = ?request.language,
+            "Running whisper.cpp"
+        );
+
+        self.run(command).await?;
+
+        let segments = read_output(&output_prefix, self.config.output_format).await?;
+
+        // Shift chunk-relative timestamps onto the video timeline
+        let segments = segments
+            .into_iter()
+            .map(|segment| {
+                Ok(TranscriptSegment {
+                    start: segment.start + request.time_offset,
+                    end: segment.end + request.time_offset,
+                    text: segment.text,
+                    words: segment
+                        .words
+                        .into_iter()
+                        .map(|word| TranscriptWord {
+                            start_time: word.start_time + request.time_offset,
+                            end_time: word.end_time + request.time_offset,
+                            ..word
+                        })
+                        .collect(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        info!(segments = segments.len(), "whisper.cpp transcription complete");
+
+        Ok(Box::pin(stream::iter(segments)))
+    }
+}
+
+#[async_trait::async_trait]
+impl LanguageDetector for WhisperCppTranscriber {
+    async fn detect_language(&self, audio_path: &Path) -> Result<Option<String>> {
+        self.check_model().await?;
+
+        let mut command = Command::new(&self.config.binary_path);
+        command
+            .kill_on_drop(true)
+            .arg("--model").arg(&self.config.model_path)
+            .arg("--file").arg(audio_path)
+            .arg("--language").arg("auto")
+            .arg("--detect-language")
+            .arg("--threads").arg(self.thread_count().to_string())
+            .arg("--no-gpu");
+
+        info!(audio_path = ?audio_path, "Detecting language with whisper.cpp");
+
+        // whisper.cpp logs the detected language rather than writing an output file
+        let output = self.run(command).await?;
+        let log = format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stderr),
+            String::from_utf8_lossy(&output.stdout)
+        );
+
+        Ok(parse_detected_language(&log))
+    }
+}
+
+/// Parse the language code from whisper.cpp's "auto-detected language: en (p = 0.97)" log line
+fn parse_detected_language(log: &str) -> Option<String> {
+    log.lines()
+        .find_map(|line| line.split_once("auto-detected language:"))
+        .and_then(|(_, rest)| rest.split_whitespace().next())
+        .map(str::to_string)
+}
+
+/// Helper: Read and parse the output file whisper.cpp wrote next to `output_prefix`
+async fn read_output(output_prefix: &Path, format: WhisperOutputFormat) -> Result<Vec<TranscriptSegment>> {
+    let extension = match format {
+        WhisperOutputFormat::Json => "json",
+        WhisperOutputFormat::Srt => "srt",
+    };
+    let path = output_prefix.with_extension(extension);
+    let contents = tokio::fs::read_to_string(&path).await?;
+
+    match format {
+        WhisperOutputFormat::Json => parse_json_output(&contents),
+        WhisperOutputFormat::Srt => parse_srt_output(&contents),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperJsonOffsets {
+    from: i64,
+    to: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperJsonToken {
+    offsets: WhisperJsonOffsets,
+    text: String,
+    /// Token probability
+    p: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperJsonSegment {
+    offsets: WhisperJsonOffsets,
+    text: String,
+    /// Only present in `--output-json-full` output
+    #[serde(default)]
+    tokens: Vec<WhisperJsonToken>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperJsonOutput {
+    transcription: Vec<WhisperJsonSegment>,
+}
+
+/// Group whisper.cpp tokens into words
+///
+/// A token starting with whitespace begins a new word; special tokens such as
+/// `[_BEG_]` are skipped. A word's confidence is the mean of its token probabilities.
+fn group_tokens_into_words(tokens: &[WhisperJsonToken]) -> Vec<TranscriptWord> {
+    let mut words = Vec::new();
+    let mut current: Option<(TranscriptWord, Vec<f64>)> = None;
+
+    let finish = |(mut word, probabilities): (TranscriptWord, Vec<f64>)| {
+        word.confidence = Some(probabilities.iter().sum::<f64>() / probabilities.len() as f64);
+        word
+    };
+
+    for token in tokens {
+        if token.text.starts_with("[_") || token.text.trim().is_empty() {
+            continue;
+        }
+
+        let start = token.offsets.from as f64 / 1000.0;
+        let end = token.offsets.to as f64 / 1000.0;
+
+        match current.as_mut() {
+            Some((word, probabilities)) if !token.text.starts_with(char::is_whitespace) => {
+                word.text.push_str(&token.text);
+                word.end_time = end;
+                probabilities.push(token.p);
+            }
+            _ => {
+                words.extend(current.take().map(finish));
+                current = Some((
+                    TranscriptWord {
+                        start_time: start,
+                        end_time: end,
+                        text: token.text.trim_start().to_string(),
+                        confidence: None,
+                    },
+                    vec![token.p],
+                ));
+            }
+        }
+    }
+
+    words.extend(current.map(finish));
+    words
+}
+
+/// Parse whisper.cpp `--output-json` output (offsets are in milliseconds)
+///
+/// Word timings are filled in when the output has tokens (`--output-json-full`).
+fn parse_json_output(contents: &str) -> Result<Vec<TranscriptSegment>> {
+    let output: WhisperJsonOutput = serde_json::from_str(contents)
+        .map_err(|e| TranscriberError::Failed(format!("Failed to parse whisper.cpp JSON: {}", e)))?;
+
+    Ok(output
+        .transcription
+        .into_iter()
+        .map(|segment| TranscriptSegment {
+            start: segment.offsets.from as f64 / 1000.0,
+            end: segment.offsets.to as f64 / 1000.0,
+            text: segment.text.trim().to_string(),
+            words: group_tokens_into_words(&segment.tokens),
+        })
+        .filter(|segment| !segment.text.is_empty())
+        .collect())
+}
+
+/// Parse an SRT timestamp (e.g. "00:01:02,345") into seconds
+fn parse_srt_timestamp(timestamp: &str) -> Option<f64> {
+    let (hms, millis) = timestamp.trim().split_once(',')?;
+    let parts: Vec<&str> = hms.split(':').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    let hours = parts[0].parse::<f64>().ok()?;
+    let minutes = parts[1].parse::<f64>().ok()?;
+    let seconds = parts[2].parse::<f64>().ok()?;
+    let millis = millis.parse::<f64>().ok()?;
+    Some(hours * 3600.0 + minutes * 60.0 + seconds + millis / 1000.0)
+}
+
+/// Parse whisper.cpp `--output-srt` output
+fn parse_srt_output(contents: &str) -> Result<Vec<TranscriptSegment>> {
+    let mut segments = Vec::new();
+
+    for cue in contents.replace("\r\n", "\n").split("\n\n") {
+        let mut lines = cue.lines().filter(|l| !l.trim().is_empty());
+
+        // First line is the cue index, second is the timing
+        let (Some(_index), Some(timing)) = (lines.next(), lines.next()) else {
+            continue;
+        };
+
+        let (start, end) = timing
+            .split_once("-->")
+            .and_then(|(start, end)| Some((parse_srt_timestamp(start)?, parse_srt_timestamp(end)?)))
+            .ok_or_else(|| TranscriberError::Failed(format!("Invalid SRT timing line: {}", timing)))?;
+
+        let text = lines.map(str::trim).collect::<Vec<_>>().join(" ");
+        if !text.is_empty() {
+            segments.push(TranscriptSegment {
+                start,
+                end,
+                text,
+                words: Vec::new(),
+            });
+        }
+    }
+
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json_output() {
+        let json = r#"{
+            "transcription": [
+                {"timestamps": {"from": "00:00:00,000", "to": "00:00:02,500"}, "offsets": {"from": 0, "to": 2500}, "text": " Namo tassa"},
+                {"timestamps": {"from": "00:00:02,500", "to": "00:00:05,000"}, "offsets": {"from": 2500, "to": 5000}, "text": " bhagavato arahato"},
+                {"timestamps": {"from": "00:00:05,000", "to": "00:00:05,100"}, "offsets": {"from": 5000, "to": 5100}, "text": " "}
+            ]
+        }"#;
+
+        let segments = parse_json_output(json).unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].text, "Namo tassa");
+        assert_eq!(segments[1].start, 2.5);
+        assert_eq!(segments[1].end, 5.0);
+        assert!(segments[0].words.is_empty());
+    }
+
+    #[test]
+    fn test_parse_full_json_output_words() {
+        let json = r#"{
+            "transcription": [
+                {
+                    "offsets": {"from": 0, "to": 2500},
+                    "text": " Namo tassa",
+                    "tokens": [
+                        {"text": "[_BEG_]", "offsets": {"from": 0, "to": 0}, "id": 50364, "p": 0.99},
+                        {"text": " Na", "offsets": {"from": 0, "to": 400}, "id": 1, "p": 0.9},
+                        {"text": "mo", "offsets": {"from": 400, "to": 900}, "id": 2, "p": 0.5},
+                        {"text": " tassa", "offsets": {"from": 1000, "to": 2500}, "id": 3, "p": 0.8},
+                        {"text": "[_TT_125]", "offsets": {"from": 2500, "to": 2500}, "id": 50489, "p": 0.4}
+                    ]
+                }
+            ]
+        }"#;
+
+        let words = &parse_json_output(json).unwrap()[0].words;
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "Namo");
+        assert_eq!(words[0].start_time, 0.0);
+        assert_eq!(words[0].end_time, 0.9);
+        assert!((words[0].confidence.unwrap() - 0.7).abs() < 1e-9);
+        assert_eq!(words[1].text, "tassa");
+        assert_eq!(words[1].start_time, 1.0);
+        assert_eq!(words[1].confidence, Some(0.8));
+    }
+
+    #[test]
+    fn test_parse_srt_output() {
+        let srt = "1\r\n00:00:00,000 --> 00:00:02,500\r\n Namo tassa\r\n\r\n2\r\n00:01:02,500 --> 00:01:05,000\r\n bhagavato\r\narahato\r\n";
+
+        let segments = parse_srt_output(srt).unwrap();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].end, 2.5);
+        assert_eq!(segments[1].start, 62.5);
+        assert_eq!(segments[1].text, "bhagavato arahato");
+    }
+
+    #[test]
+    fn test_parse_detected_language() {
+        let log = "whisper_init_from_file_with_params_no_state: loading model\n\
+                   whisper_full_with_state: auto-detected language: hi (p = 0.873412)\n";
+        assert_eq!(parse_detected_language(log).as_deref(), Some("hi"));
+        assert_eq!(parse_detected_language("no detection here"), None);
+    }
+
+    #[tokio::test]
+    async fn test_missing_binary_is_typed_error() {
+        let model = tempfile::NamedTempFile::new().unwrap();
+        let transcriber = WhisperCppTranscriber::new(WhisperCppConfig {
+            binary_path: PathBuf::from("/nonexistent/whisper-cli"),
+            model_path: model.path().to_path_buf(),
+            threads: Some(1),
+            output_format: WhisperOutputFormat::Json,
+        });
+
+        let request = TranscriptionRequest {
+            audio_path: PathBuf::from("chunk_0.wav"),
+            time_offset: 0.0,
+            language: None,
+            prompt: None,
+        };
+
+        match transcriber.transcribe(request.clone()).await {
+            Err(TranscriberError::BinaryNotFound(path)) => {
+                assert_eq!(path, PathBuf::from("/nonexistent/whisper-cli"))
+            }
+            other => panic!("Expected BinaryNotFound, got {:?}", other.map(|_| ())),
+        }
+
+        let transcriber = WhisperCppTranscriber::new(WhisperCppConfig {
+            binary_path: PathBuf::from("whisper-cli"),
+            model_path: PathBuf::from("/nonexistent/ggml-base.bin"),
+            threads: Some(1),
+            output_format: WhisperOutputFormat::Json,
+        });
+
+        assert!(matches!(
+            transcriber.transcribe(request).await,
+            Err(TranscriberError::ModelNotFound(_))
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_dropped_transcription_kills_whisper() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // A stand-in for whisper.cpp that records its PID and never finishes
+        let dir = tempfile::tempdir().unwrap();
+        let binary = dir.path().join("whisper-cli");
+        let pid_file = dir.path().join("pid");
+        std::fs::write(&binary, format!("#!/bin/sh\necho $$ > {}\nexec sleep 30\n", pid_file.display())).unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let model = tempfile::NamedTempFile::new().unwrap();
+        let transcriber = WhisperCppTranscriber::new(WhisperCppConfig {
+            binary_path: binary,
+            model_path: model.path().to_path_buf(),
+            threads: Some(1),
+            output_format: WhisperOutputFormat::Json,
+        });
+
+        let request = TranscriptionRequest {
+            audio_path: PathBuf::from("chunk_0.wav"),
+            time_offset: 0.0,
+            language: None,
+            prompt: None,
+        };
+        let timed_out = tokio::time::timeout(std::time::Duration::from_millis(500), transcriber.transcribe(request)).await;
+        assert!(timed_out.is_err());
+
+        let pid = std::fs::read_to_string(&pid_file).unwrap().trim().to_string();
+        let running = || {
+            std::fs::read_to_string(format!("/proc/{}/stat", pid))
+                .is_ok_and(|stat| !stat.split_once(") ").is_some_and(|(_, rest)| rest.starts_with('Z')))
+        };
+        for _ in 0..50 {
+            if !running() {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        panic!("whisper.cpp process {} kept running after the transcription was dropped", pid);
+    }
+}
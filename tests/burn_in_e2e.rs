@@ -0,0 +1,195 @@
+mod common;
+
+use common::{create_authenticated_client, create_test_state, seed_video_for_user, start_test_server};
+use reqwest::Client;
+use serde_json::json;
+
+/// Poll the burn-in endpoint until the latest job has finished
+async fn wait_until_finished(client: &Client, base_url: &str, video_id: &str) -> serde_json::Value {
+    for _ in 0..200 {
+        let job: serde_json::Value = client
+            .get(format!("{}/api/videos/{}/burn-in", base_url, video_id))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        if !matches!(job["status"].as_str(), Some("queued" | "running")) {
+            return job;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+    panic!("Caption burn-in job did not finish");
+}
+
+#[tokio::test]
+async fn test_burn_in_job_lifecycle() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    let burn_in_url = format!("{}/api/videos/{}/burn-in", base_url, video_id);
+
+    let response = client.get(&burn_in_url).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+
+    // Nothing to burn in without a transcript
+    let response = client.post(&burn_in_url).json(&json!({})).send().await.unwrap();
+    assert_eq!(response.status(), 409);
+
+    let response = client
+        .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+        .json(&json!({ "start_time": 0.0, "end_time": 3.0, "text": "Welcome to the evening talk." }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    // Styles are validated before a job is queued
+    for style in [
+        json!({ "font": "Sans':original_size=1x1" }),
+        json!({ "font_size": 4 }),
+        json!({ "outline": 20.0 }),
+    ] {
+        let response = client.post(&burn_in_url).json(&style).send().await.unwrap();
+        assert_eq!(response.status(), 400, "style {} should be rejected", style);
+    }
+
+    // Omitted style fields take their defaults
+    let response = client
+        .post(&burn_in_url)
+        .json(&json!({ "font": "DejaVu Sans", "position": "top" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 202);
+    let job: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(job["video_id"], video_id);
+    assert_eq!(job["style"], json!({ "font": "DejaVu Sans", "font_size": 18, "position": "top", "outline": 1.5 }));
+    assert!(job.get("file_id").is_none());
+
+    // The seeded video isn't real media, so the render fails without exposing server details
+    let finished = wait_until_finished(&client, &base_url, &video_id).await;
+    assert_eq!(finished["id"], job["id"]);
+    assert_eq!(finished["status"], "failed");
+    assert_eq!(finished["error"], "Internal server error");
+
+    let response = client.get(format!("{}/download", burn_in_url)).send().await.unwrap();
+    assert_eq!(response.status(), 409);
+
+    // Jobs belong to the video's owner
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+    let response = other.get(&burn_in_url).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+    let response = other.post(&burn_in_url).json(&json!({})).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+
+    println!("✓ Caption burn-in jobs validate styles, record failures and guard downloads");
+}
+
+#[tokio::test]
+async fn test_burn_in_download() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+
+    // Stand in for a finished render, which needs ffmpeg and real media
+    let user = state.db.get_user_by_email("test@example.com").await.unwrap().unwrap();
+    let file_id = format!("captioned/{}.mp4", video_id);
+    state.filestore.save_file(&file_id, Box::new(&b"rendered"[..])).await.unwrap();
+    let job = gatha_transcribe::db::CaptionBurnJob::new(video_id.clone(), user.id, Default::default());
+    assert!(state.db.insert_caption_burn_job(&job).await.unwrap());
+    state
+        .db
+        .update_caption_burn_job_status(&job.id, gatha_transcribe::db::JobStatus::Done, Some(&file_id), None)
+        .await
+        .unwrap();
+
+    let response = client
+        .get(format!("{}/api/videos/{}/burn-in/download", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "video/mp4");
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"test_video.captioned.mp4\"; filename*=UTF-8''test_video.captioned.mp4"
+    );
+    assert_eq!(response.headers()["content-length"], "8");
+    assert_eq!(response.headers()["accept-ranges"], "bytes");
+    assert_eq!(response.bytes().await.unwrap().as_ref(), b"rendered");
+
+    // Players and download managers fetch the render in ranges
+    let response = client
+        .get(format!("{}/api/videos/{}/burn-in/download", base_url, video_id))
+        .header("Range", "bytes=2-5")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 206);
+    assert_eq!(response.headers()["content-range"], "bytes 2-5/8");
+    assert_eq!(response.headers()["content-length"], "4");
+    assert_eq!(response.bytes().await.unwrap().as_ref(), b"nder");
+
+    let response = client
+        .get(format!("{}/api/videos/{}/burn-in/download", base_url, video_id))
+        .header("Range", "bytes=8-")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    println!("✓ Finished caption burn-in renders download with a captioned filename and ranges");
+}
+
+#[tokio::test]
+async fn test_burn_in_runs_once_per_video() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    let user = state.db.get_user_by_email("test@example.com").await.unwrap().unwrap();
+
+    let response = client
+        .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+        .json(&json!({ "start_time": 0.0, "end_time": 3.0, "text": "Welcome to the evening talk." }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    // The database holds at most one unfinished job per video, however the inserts race
+    let first = gatha_transcribe::db::CaptionBurnJob::new(video_id.clone(), user.id.clone(), Default::default());
+    let second = gatha_transcribe::db::CaptionBurnJob::new(video_id.clone(), user.id.clone(), Default::default());
+    let (first_inserted, second_inserted) = tokio::join!(
+        state.db.insert_caption_burn_job(&first),
+        state.db.insert_caption_burn_job(&second)
+    );
+    assert_eq!(
+        [first_inserted.unwrap(), second_inserted.unwrap()].iter().filter(|inserted| **inserted).count(),
+        1
+    );
+
+    let response = client
+        .post(format!("{}/api/videos/{}/burn-in", base_url, video_id))
+        .json(&json!({}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    // A finished job no longer blocks a new one
+    let unfinished = state.db.get_latest_caption_burn_job(&video_id).await.unwrap().unwrap();
+    state
+        .db
+        .update_caption_burn_job_status(&unfinished.id, gatha_transcribe::db::JobStatus::Failed, None, Some("failed"))
+        .await
+        .unwrap();
+    let third = gatha_transcribe::db::CaptionBurnJob::new(video_id.clone(), user.id, Default::default());
+    assert!(state.db.insert_caption_burn_job(&third).await.unwrap());
+
+    println!("✓ A video has at most one unfinished caption burn-in job");
+}
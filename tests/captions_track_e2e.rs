@@ -0,0 +1,77 @@
+mod common;
+
+use common::{create_authenticated_client, create_test_state, seed_video_for_user, start_test_server};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_caption_track_follows_transcript() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    let track_url = format!("{}/api/videos/{}/captions.vtt", base_url, video_id);
+    // The player's <track> request, like the stream's, carries no credentials
+    let player = reqwest::Client::new();
+
+    let response = player.get(&track_url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "WEBVTT\n\n");
+
+    let response = client
+        .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+        .json(&json!({ "start_time": 1.0, "end_time": 3.0, "text": "Welcome back." }))
+        .send()
+        .await
+        .unwrap();
+    let block: serde_json::Value = response.json().await.unwrap();
+
+    let response = player.get(&track_url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "text/vtt; charset=utf-8");
+    assert_eq!(response.headers()["cache-control"], "no-cache");
+    let etag = response.headers()["etag"].to_str().unwrap().to_string();
+    assert_eq!(
+        response.text().await.unwrap(),
+        "WEBVTT\n\n00:00:01.000 --> 00:00:03.000\nWelcome back.\n\n"
+    );
+
+    // Unchanged captions revalidate without a body
+    let response = player
+        .get(&track_url)
+        .header("If-None-Match", &etag)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 304);
+
+    // An edit changes the ETag
+    client
+        .patch(format!(
+            "{}/api/videos/{}/transcript/blocks/{}",
+            base_url,
+            video_id,
+            block["id"].as_str().unwrap()
+        ))
+        .json(&json!({ "text": "Welcome back, everyone." }))
+        .send()
+        .await
+        .unwrap();
+    let response = player
+        .get(&track_url)
+        .header("If-None-Match", &etag)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_ne!(response.headers()["etag"].to_str().unwrap(), etag);
+    assert!(response.text().await.unwrap().contains("\nWelcome back, everyone.\n"));
+
+    let response = player
+        .get(format!("{}/api/videos/nonexistent/captions.vtt", base_url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    println!("✓ The caption track serves the live transcript with revision ETags");
+}
@@ -1,17 +1,35 @@
+// Each integration test binary compiles this module and only uses some of the helpers
+#![allow(dead_code)]
+
+use chrono::Utc;
 use gatha_transcribe::{
     create_router,
-    db::Database,
+    db::{AudioChunk, Database},
+    diarizer::MockDiarizer,
+    events::EventBus,
     filestore::LocalFileStore,
+    jobs::JobRegistry,
+    language::MockLanguageDetector,
     session_store::InMemorySessionStore,
+    test_data,
+    transcriber::{MockTranscriber, Transcriber},
     upload::AppState,
 };
 use reqwest::Client;
+use serde_json::Value;
 use std::sync::Arc;
 use tempfile::TempDir;
 use tokio::net::TcpListener;
 
 /// Helper to create test app state with temporary database and filestore
 pub async fn create_test_state() -> (Arc<AppState>, TempDir, TempDir) {
+    create_test_state_with_transcriber(Arc::new(MockTranscriber::new())).await
+}
+
+/// Helper to create test app state with a custom transcription backend
+pub async fn create_test_state_with_transcriber(
+    transcriber: Arc<dyn Transcriber>,
+) -> (Arc<AppState>, TempDir, TempDir) {
     let db_dir = TempDir::new().unwrap();
     let filestore_dir = TempDir::new().unwrap();
 
@@ -32,6 +50,11 @@ pub async fn create_test_state() -> (Arc<AppState>, TempDir, TempDir) {
         db,
         filestore: Arc::new(filestore),
         session_store: Arc::new(session_store),
+        transcriber,
+        language_detector: Arc::new(MockLanguageDetector::default()),
+        diarizer: Some(Arc::new(MockDiarizer::new())),
+        jobs: JobRegistry::new(),
+        events: EventBus::new(),
     });
 
     (state, db_dir, filestore_dir)
@@ -77,3 +100,55 @@ pub async fn create_authenticated_client(base_url: &str, email: &str, name: &str
 
     client
 }
+
+/// Seed a video owned by an already registered user and return its ID
+pub async fn seed_video_for_user(state: &AppState, email: &str) -> String {
+    let user = state.db.get_user_by_email(email).await.unwrap().unwrap();
+    let videos = test_data::seed_test_videos(&state.db, &user.id, &["test_video.mp4"])
+        .await
+        .unwrap();
+    videos[0].id.clone()
+}
+
+/// Add a transcript block through the API and return its ID
+///
+/// `block` is the request body, so tests can pass words, speakers and the like.
+pub async fn add_block(client: &Client, base_url: &str, video_id: &str, block: Value) -> String {
+    let response = client
+        .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+        .json(&block)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let block: Value = response.json().await.unwrap();
+    block["id"].as_str().unwrap().to_string()
+}
+
+/// Seed extracted audio chunks of 45s each (1s of silence trimmed on both ends)
+///
+/// The audio is a dummy file; the mock transcriber never reads it.
+pub async fn seed_audio_chunks(state: &AppState, video_id: &str, count: i64) {
+    let mut chunks = Vec::new();
+    for index in 0..count {
+        let file_id = format!("chunks/{}/{:04}.wav", video_id, index);
+        state
+            .filestore
+            .save_file(&file_id, Box::new(&b"RIFF"[..]))
+            .await
+            .unwrap();
+
+        let source_start = index as f64 * 45.0;
+        chunks.push(AudioChunk {
+            video_id: video_id.to_string(),
+            chunk_index: index,
+            source_start,
+            source_end: source_start + 45.0,
+            trimmed_start: source_start + 1.0,
+            trimmed_end: source_start + 44.0,
+            file_id,
+            created_at: Utc::now(),
+        });
+    }
+    state.db.replace_audio_chunks(video_id, &chunks).await.unwrap();
+}
@@ -0,0 +1,205 @@
+mod common;
+
+use common::{
+    create_authenticated_client, create_test_state, create_test_state_with_transcriber,
+    seed_audio_chunks, seed_video_for_user, start_test_server,
+};
+use gatha_transcribe::transcriber::{
+    SegmentStream, Transcriber, TranscriberError, TranscriptionRequest,
+};
+use reqwest::{multipart, Client, Response};
+use std::sync::Arc;
+
+/// Minimal reader for a `text/event-stream` response
+struct EventReader {
+    response: Response,
+    buffer: String,
+}
+
+impl EventReader {
+    async fn connect(client: &Client, base_url: &str, last_event_id: Option<&str>) -> Self {
+        let mut request = client.get(format!("{}/api/events", base_url));
+        if let Some(id) = last_event_id {
+            request = request.header("Last-Event-ID", id);
+        }
+
+        let response = request.send().await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+        Self {
+            response,
+            buffer: String::new(),
+        }
+    }
+
+    /// Next event as (id, data), or None if nothing arrives within a second
+    async fn next(&mut self) -> Option<(String, serde_json::Value)> {
+        loop {
+            if let Some(end) = self.buffer.find("\n\n") {
+                let frame: String = self.buffer.drain(..end + 2).collect();
+                let mut id = String::new();
+                let mut data = String::new();
+                for line in frame.lines() {
+                    if let Some(value) = line.strip_prefix("id:") {
+                        id = value.trim().to_string();
+                    } else if let Some(value) = line.strip_prefix("data:") {
+                        data.push_str(value.trim());
+                    }
+                }
+                if data.is_empty() {
+                    continue; // Keep-alive comment
+                }
+                return Some((id, serde_json::from_str(&data).unwrap()));
+            }
+
+            let chunk = tokio::time::timeout(tokio::time::Duration::from_secs(1), self.response.chunk())
+                .await
+                .ok()?
+                .unwrap()
+                .expect("Event stream closed");
+            self.buffer.push_str(&String::from_utf8_lossy(&chunk));
+        }
+    }
+}
+
+async fn upload(client: &Client, base_url: &str, filename: &str) -> String {
+    let part = multipart::Part::bytes(vec![0u8; 1024])
+        .file_name(filename.to_string())
+        .mime_str("video/mp4")
+        .unwrap();
+    let response = client
+        .post(format!("{}/api/videos/upload", base_url))
+        .multipart(multipart::Form::new().part("video", part))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let json: serde_json::Value = response.json().await.unwrap();
+    json["id"].as_str().unwrap().to_string()
+}
+
+/// Transcriber whose engine always errors
+struct FailingTranscriber;
+
+#[async_trait::async_trait]
+impl Transcriber for FailingTranscriber {
+    async fn transcribe(
+        &self,
+        _request: TranscriptionRequest,
+    ) -> gatha_transcribe::transcriber::Result<SegmentStream> {
+        Err(TranscriberError::Failed("model crashed reading /srv/models/ggml-base.bin".to_string()))
+    }
+}
+
+#[tokio::test]
+async fn test_feed_reports_transcription_percentage() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let other_client = create_authenticated_client(&base_url, "other@example.com", "Other User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    seed_audio_chunks(&state, &video_id, 2).await;
+
+    let mut feed = EventReader::connect(&client, &base_url, None).await;
+    let mut other_feed = EventReader::connect(&other_client, &base_url, None).await;
+
+    let response = client
+        .post(format!("{}/api/videos/{}/transcription", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 202);
+
+    let mut percents = Vec::new();
+    loop {
+        let (_, event) = feed.next().await.expect("Transcription did not finish");
+        assert_eq!(event["type"], "TranscriptionProgress");
+        assert_eq!(event["video_id"], video_id.as_str());
+        assert_eq!(event["chunks_total"], 2);
+        percents.push(event["percent"].as_f64().unwrap());
+        if event["status"] == "done" {
+            break;
+        }
+    }
+    assert_eq!(percents.first(), Some(&0.0));
+    assert_eq!(percents.last(), Some(&100.0));
+    assert!(percents.contains(&50.0));
+
+    // Progress is private to the video owner
+    assert!(other_feed.next().await.is_none());
+
+    println!("✓ Progress feed reports transcription percentage to the owner only");
+}
+
+#[tokio::test]
+async fn test_feed_reports_transcription_failure() {
+    let (state, _db_dir, _filestore_dir) =
+        create_test_state_with_transcriber(Arc::new(FailingTranscriber)).await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    seed_audio_chunks(&state, &video_id, 1).await;
+
+    let mut feed = EventReader::connect(&client, &base_url, None).await;
+
+    client
+        .post(format!("{}/api/videos/{}/transcription", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+
+    let failure = loop {
+        let (_, event) = feed.next().await.expect("No failure reported");
+        if event["type"] == "TranscriptionFailed" {
+            break event;
+        }
+    };
+    assert_eq!(failure["video_id"], video_id.as_str());
+    // Engine internals stay in the server log
+    assert_eq!(failure["error"], "Internal server error");
+    let job = state.db.get_latest_transcription_job(&video_id).await.unwrap().unwrap();
+    assert_eq!(job.error.as_deref(), Some("Internal server error"));
+
+    println!("✓ Progress feed reports failed transcriptions without internal details");
+}
+
+#[tokio::test]
+async fn test_reconnect_replays_missed_events() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+
+    let mut feed = EventReader::connect(&client, &base_url, None).await;
+    let first_video = upload(&client, &base_url, "first.mp4").await;
+
+    let (processing_id, processing) = feed.next().await.unwrap();
+    assert_eq!(processing["type"], "UploadProcessing");
+    assert_eq!(processing["video_id"], first_video.as_str());
+    assert_eq!(processing["filename"], "first.mp4");
+    let (ready_id, ready) = feed.next().await.unwrap();
+    assert_eq!(ready["type"], "UploadReady");
+    drop(feed);
+
+    // Upload while disconnected
+    let second_video = upload(&client, &base_url, "second.mp4").await;
+
+    // Reconnect as if the UploadReady event was lost
+    let mut feed = EventReader::connect(&client, &base_url, Some(&processing_id)).await;
+    let (replayed_id, replayed) = feed.next().await.unwrap();
+    assert_eq!(replayed_id, ready_id);
+    assert_eq!(replayed["video_id"], first_video.as_str());
+
+    let (_, event) = feed.next().await.unwrap();
+    assert_eq!(event["type"], "UploadProcessing");
+    assert_eq!(event["video_id"], second_video.as_str());
+    let (_, event) = feed.next().await.unwrap();
+    assert_eq!(event["type"], "UploadReady");
+    assert_eq!(event["video_id"], second_video.as_str());
+
+    // Nothing is replayed twice
+    assert!(feed.next().await.is_none());
+
+    println!("✓ Progress feed replays missed events after Last-Event-ID");
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use common::{create_authenticated_client, create_test_state, seed_video_for_user, start_test_server};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_export_captions() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+
+    for (start, end, text) in [
+        (1.0, 4.5, "Welcome to the evening talk."),
+        (4.5, 4.8, "Yes."),
+        (62.25, 65.0, "Tonight: sīla & samādhi <part one>."),
+    ] {
+        let response = client
+            .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+            .json(&json!({ "start_time": start, "end_time": end, "text": text }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 201);
+    }
+    let export_url = format!("{}/api/videos/{}/transcript/export", base_url, video_id);
+
+    let response = client.get(&export_url).query(&[("format", "srt")]).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "application/x-subrip; charset=utf-8");
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"test_video.srt\"; filename*=UTF-8''test_video.srt"
+    );
+    assert_eq!(
+        response.text().await.unwrap(),
+        "1\r\n00:00:01,000 --> 00:00:04,500\r\nWelcome to the evening talk.\r\n\r\n\
+         2\r\n00:00:04,500 --> 00:00:05,500\r\nYes.\r\n\r\n\
+         3\r\n00:01:02,250 --> 00:01:05,000\r\nTonight: sīla & samādhi ‹part one>.\r\n\r\n"
+    );
+
+    // Narrow lines, one line per cue, no minimum duration
+    let response = client
+        .get(&export_url)
+        .query(&[("format", "vtt"), ("max_line_length", "16"), ("max_lines", "1"), ("min_duration", "0")])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "text/vtt; charset=utf-8");
+    let vtt = response.text().await.unwrap();
+    assert!(vtt.starts_with("WEBVTT\n\n00:00:01.000 --> "));
+    assert!(vtt.contains("\nWelcome to the\n"));
+    assert!(vtt.contains("00:00:04.500 --> 00:00:04.800\nYes.\n"));
+    assert!(vtt.contains("\nTonight: sīla &amp;\n"));
+    assert!(vtt.contains("\nsamādhi &lt;part\n"));
+
+    for query in [vec![("format", "ass")], vec![("format", "srt"), ("max_lines", "0")]] {
+        let response = client.get(&export_url).query(&query).send().await.unwrap();
+        assert_eq!(response.status(), 400);
+    }
+
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+    let response = other.get(&export_url).query(&[("format", "srt")]).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+
+    println!("✓ Transcripts export as SRT and WebVTT captions");
+}
+
+#[tokio::test]
+async fn test_export_documents() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+
+    let response = client
+        .post(format!("{}/api/videos/{}/speakers", base_url, video_id))
+        .json(&json!({ "name": "Ajahn Sumedho" }))
+        .send()
+        .await
+        .unwrap();
+    let speaker: serde_json::Value = response.json().await.unwrap();
+    for (start, end, text, speaker_id) in [
+        (0.0, 3.0, "Welcome, everyone.", None),
+        (3.5, 6.0, "Please settle in.", None),
+        (3725.0, 3730.0, "Rest in *awareness*.", Some(speaker["id"].clone())),
+    ] {
+        let response = client
+            .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+            .json(&json!({ "start_time": start, "end_time": end, "text": text, "speaker_id": speaker_id }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 201);
+    }
+    let export_url = format!("{}/api/videos/{}/transcript/export", base_url, video_id);
+
+    let response = client
+        .get(&export_url)
+        .query(&[("format", "txt"), ("timestamps", "true")])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "text/plain; charset=utf-8");
+    assert_eq!(
+        response.text().await.unwrap(),
+        "[00:00:00] Welcome, everyone. Please settle in.\n\nAjahn Sumedho:\n[01:02:05] Rest in *awareness*.\n\n"
+    );
+
+    let response = client.get(&export_url).query(&[("format", "md")]).send().await.unwrap();
+    assert_eq!(
+        response.headers()["content-disposition"],
+        "attachment; filename=\"test_video.md\"; filename*=UTF-8''test_video.md"
+    );
+    assert_eq!(
+        response.text().await.unwrap(),
+        "# test\\_video\n\nWelcome, everyone. Please settle in.\n\n## Ajahn Sumedho\n\nRest in \\*awareness\\*.\n\n"
+    );
+
+    // A DOCX is a zip package whose document holds the paragraphs
+    let response = client.get(&export_url).query(&[("format", "docx")]).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+    );
+    let bytes = response.bytes().await.unwrap();
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes.to_vec())).unwrap();
+    assert!(archive.by_name("[Content_Types].xml").is_ok());
+    assert!(archive.by_name("_rels/.rels").is_ok());
+    let mut document = String::new();
+    std::io::Read::read_to_string(&mut archive.by_name("word/document.xml").unwrap(), &mut document).unwrap();
+    assert!(document.contains(">Welcome, everyone. Please settle in.</w:t>"));
+    assert!(document.contains("<w:b/></w:rPr><w:t xml:space=\"preserve\">Ajahn Sumedho</w:t>"));
+
+    println!("✓ Transcripts export as plain text, Markdown and DOCX documents");
+}
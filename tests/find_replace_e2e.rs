@@ -0,0 +1,152 @@
+mod common;
+
+use common::{add_block, create_authenticated_client, create_test_state, seed_video_for_user, start_test_server};
+use serde_json::json;
+
+/// A five-second block with one-second words, so the test can check word rewrites
+fn block_with_words(start: f64, text: &str) -> serde_json::Value {
+    let words: Vec<serde_json::Value> = text
+        .split_whitespace()
+        .enumerate()
+        .map(|(i, word)| json!({ "start_time": start + i as f64, "end_time": start + i as f64 + 1.0, "text": word }))
+        .collect();
+    json!({ "start_time": start, "end_time": start + 5.0, "text": text, "words": words })
+}
+
+#[tokio::test]
+async fn test_find_replace_across_videos() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let first = seed_video_for_user(&state, "test@example.com").await;
+    let second = seed_video_for_user(&state, "test@example.com").await;
+
+    add_block(&client, &base_url, &first, block_with_words(0.0, "Ajahn Cha taught at Wat Pah Pong.")).await;
+    add_block(&client, &base_url, &first, block_with_words(5.0, "Nothing to change here.")).await;
+    add_block(&client, &base_url, &second, block_with_words(0.0, "As ajahn cha said, Chanting matters.")).await;
+
+    let replace_url = format!("{}/api/transcripts/replace", base_url);
+    let request = json!({
+        "video_ids": [first, second],
+        "find": "cha",
+        "replace": "Chah",
+        "mode": "whole-word",
+        "case_insensitive": true,
+        "dry_run": true
+    });
+
+    // Dry run reports the diff and saves nothing
+    let response = client.post(&replace_url).json(&request).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    let result: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(result["dry_run"], true);
+    assert_eq!(result["matches"], 2);
+    let blocks = result["blocks"].as_array().unwrap();
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0]["after"], "Ajahn Chah taught at Wat Pah Pong.");
+    assert_eq!(
+        blocks[1]["diff"],
+        json!([
+            { "op": "equal", "text": "As ajahn " },
+            { "op": "delete", "text": "cha" },
+            { "op": "insert", "text": "Chah" },
+            { "op": "equal", "text": " said, Chanting matters." }
+        ])
+    );
+    let stored = state.db.list_transcript_blocks(&first).await.unwrap();
+    assert_eq!(stored[0].text, "Ajahn Cha taught at Wat Pah Pong.");
+
+    // Commit the replacement
+    let mut request = request;
+    request["dry_run"] = json!(false);
+    let response = client.post(&replace_url).json(&request).send().await.unwrap();
+    let result: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(result["matches"], 2);
+
+    let stored = state.db.list_transcript_blocks(&first).await.unwrap();
+    assert_eq!(stored[0].text, "Ajahn Chah taught at Wat Pah Pong.");
+    assert_eq!(stored[0].words[1].text, "Chah");
+    // The text from before the last retranscription is not replaced by the edit
+    assert!(stored[0].previous_text.is_none());
+    let stored = state.db.list_transcript_blocks(&second).await.unwrap();
+    assert_eq!(stored[0].text, "As ajahn Chah said, Chanting matters.");
+
+    // Regex with group references
+    let response = client
+        .post(&replace_url)
+        .json(&json!({
+            "video_ids": [first],
+            "find": r"Wat (\w+) (\w+)",
+            "replace": "Wat $1 $2 monastery",
+            "mode": "regex"
+        }))
+        .send()
+        .await
+        .unwrap();
+    let result: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(result["blocks"][0]["after"], "Ajahn Chah taught at Wat Pah Pong monastery.");
+
+    let response = client
+        .post(&replace_url)
+        .json(&json!({ "video_ids": [first], "find": "(", "replace": "", "mode": "regex" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    // Every video must belong to the user; nothing is changed otherwise
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+    let response = other
+        .post(&replace_url)
+        .json(&json!({ "video_ids": [first], "find": "Chah", "replace": "Cha" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+    let stored = state.db.list_transcript_blocks(&first).await.unwrap();
+    assert_eq!(stored[0].text, "Ajahn Chah taught at Wat Pah Pong monastery.");
+
+    println!("✓ Find and replace previews and applies across videos");
+}
+
+#[tokio::test]
+async fn test_rewrite_refuses_blocks_edited_since_read() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    let user = state.db.get_user_by_email("test@example.com").await.unwrap().unwrap();
+
+    add_block(&client, &base_url, &video_id, block_with_words(0.0, "Ajahn Cha taught.")).await;
+    add_block(&client, &base_url, &video_id, block_with_words(5.0, "Ajahn Cha listened.")).await;
+
+    // Both blocks are read, then one is edited before the rewrite lands
+    let mut read = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    for block in &mut read {
+        block.text = block.text.replace("Cha", "Chah");
+    }
+    let response = client
+        .patch(format!("{}/api/videos/{}/transcript/blocks/{}", base_url, video_id, read[1].id))
+        .json(&json!({ "text": "Ajahn Cha listened closely." }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let written = state.db.rewrite_transcript_block_texts(&read, &user.id).await.unwrap();
+    assert!(!written);
+    let stored = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(stored[0].text, "Ajahn Cha taught.");
+    assert_eq!(stored[1].text, "Ajahn Cha listened closely.");
+
+    // Blocks read afresh go through
+    let mut read = stored;
+    for block in &mut read {
+        block.text = block.text.replace("Cha", "Chah");
+    }
+    assert!(state.db.rewrite_transcript_block_texts(&read, &user.id).await.unwrap());
+    let stored = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(stored[1].text, "Ajahn Chah listened closely.");
+
+    println!("✓ Bulk rewrites don't overwrite blocks edited after they were read");
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use common::{
+    create_authenticated_client, create_test_state, create_test_state_with_transcriber,
+    seed_audio_chunks, seed_video_for_user, start_test_server,
+};
+use gatha_transcribe::{
+    jobs,
+    transcriber::{MockTranscriber, Result, SegmentStream, Transcriber, TranscriptionRequest},
+};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+/// Mock transcriber that records the prompt of every request
+#[derive(Default)]
+struct RecordingTranscriber {
+    prompts: Mutex<Vec<Option<String>>>,
+}
+
+#[async_trait::async_trait]
+impl Transcriber for RecordingTranscriber {
+    async fn transcribe(&self, request: TranscriptionRequest) -> Result<SegmentStream> {
+        self.prompts.lock().unwrap().push(request.prompt.clone());
+        MockTranscriber::new().transcribe(request).await
+    }
+}
+
+#[tokio::test]
+async fn test_glossary_crud() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let glossary_url = format!("{}/api/glossary", base_url);
+
+    let response = client
+        .post(&glossary_url)
+        .json(&json!({ "term": "Sāriputta", "variants": ["Shariputra"] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let term: serde_json::Value = response.json().await.unwrap();
+    let term_id = term["id"].as_str().unwrap().to_string();
+    assert_eq!(term["variants"], json!(["Shariputra"]));
+    assert_eq!(term["fuzzy"], false);
+
+    client
+        .post(&glossary_url)
+        .json(&json!({ "term": "  Ajahn   Chah " }))
+        .send()
+        .await
+        .unwrap();
+
+    // Same spelling in another case is a duplicate
+    let response = client
+        .post(&glossary_url)
+        .json(&json!({ "term": "sāriputta" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    let response = client.post(&glossary_url).json(&json!({ "term": " " })).send().await.unwrap();
+    assert_eq!(response.status(), 400);
+
+    let terms: Vec<serde_json::Value> = client.get(&glossary_url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(terms.len(), 2);
+    assert_eq!(terms[0]["term"], "Ajahn Chah");
+
+    let prompt: serde_json::Value = client
+        .get(format!("{}/prompt", glossary_url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(prompt["prompt"], "Glossary: Ajahn Chah, Sāriputta.");
+
+    let response = client
+        .patch(format!("{}/{}", glossary_url, term_id))
+        .json(&json!({ "variants": [], "fuzzy": true }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let term: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(term["term"], "Sāriputta");
+    assert_eq!(term["variants"], json!([]));
+    assert_eq!(term["fuzzy"], true);
+
+    // Glossaries are private
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+    let terms: Vec<serde_json::Value> = other.get(&glossary_url).send().await.unwrap().json().await.unwrap();
+    assert!(terms.is_empty());
+    let response = other.delete(format!("{}/{}", glossary_url, term_id)).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+
+    let response = client.delete(format!("{}/{}", glossary_url, term_id)).send().await.unwrap();
+    assert_eq!(response.status(), 204);
+    let terms: Vec<serde_json::Value> = client.get(&glossary_url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(terms.len(), 1);
+
+    println!("✓ Glossary terms can be added, edited and removed");
+}
+
+#[tokio::test]
+async fn test_glossary_biases_transcription() {
+    let transcriber = Arc::new(RecordingTranscriber::default());
+    let (state, _db_dir, _filestore_dir) = create_test_state_with_transcriber(transcriber.clone()).await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    seed_audio_chunks(&state, &video_id, 1).await;
+
+    // The mock says "May all beings be at peace." and "...free from suffering."
+    for term in ["All Beings", "Suffering"] {
+        client
+            .post(format!("{}/api/glossary", base_url))
+            .json(&json!({ "term": term }))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let user = state.db.get_user_by_email("test@example.com").await.unwrap().unwrap();
+    let job = jobs::start_job(state.clone(), &video_id, &user.id).await.unwrap();
+    for _ in 0..100 {
+        let job = state.db.get_transcription_job(&job.id).await.unwrap().unwrap();
+        if job.status.is_finished() {
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+
+    assert_eq!(
+        *transcriber.prompts.lock().unwrap(),
+        vec![Some("Glossary: All Beings, Suffering.".to_string())]
+    );
+
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks.len(), 3);
+    assert_eq!(blocks[0].text, "May All Beings be happy.");
+    assert_eq!(blocks[1].text, "May All Beings be free from Suffering.");
+    // Single-word terms are fixed in the word timings too
+    assert_eq!(blocks[1].words.last().unwrap().text, "Suffering.");
+
+    println!("✓ Glossary prompts the engine and normalizes its spellings");
+}
+
+#[tokio::test]
+async fn test_parallel_adds_of_one_spelling_conflict() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let glossary_url = format!("{}/api/glossary", base_url);
+
+    // Both requests can pass the duplicate check; the unique index decides the loser
+    for round in 0..5 {
+        let term = format!("Anattā{}", round);
+        let (first, second) = tokio::join!(
+            client.post(&glossary_url).json(&json!({ "term": term })).send(),
+            client.post(&glossary_url).json(&json!({ "term": term.to_lowercase() })).send()
+        );
+        let mut statuses = vec![first.unwrap().status(), second.unwrap().status()];
+        statuses.sort();
+        assert_eq!(statuses, vec![201, 409]);
+    }
+
+    println!("✓ Racing glossary adds get a conflict, not a server error");
+}
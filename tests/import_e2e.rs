@@ -0,0 +1,173 @@
+mod common;
+
+use common::{
+    create_authenticated_client, create_test_state, create_test_state_with_transcriber, seed_audio_chunks,
+    seed_video_for_user, start_test_server,
+};
+use gatha_transcribe::transcriber::{MockTranscriber, SegmentStream, Transcriber, TranscriptionRequest};
+use reqwest::multipart;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Mock transcriber that takes a while per chunk, so imports can race a running job
+struct SlowTranscriber;
+
+#[async_trait::async_trait]
+impl Transcriber for SlowTranscriber {
+    async fn transcribe(
+        &self,
+        request: TranscriptionRequest,
+    ) -> gatha_transcribe::transcriber::Result<SegmentStream> {
+        tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+        MockTranscriber::new().transcribe(request).await
+    }
+}
+
+async fn import(client: &reqwest::Client, url: &str, filename: &str, contents: &str, format: Option<&str>) -> reqwest::Response {
+    let part = multipart::Part::bytes(contents.as_bytes().to_vec()).file_name(filename.to_string());
+    let mut form = multipart::Form::new();
+    if let Some(format) = format {
+        form = form.text("format", format.to_string());
+    }
+    client.post(url).multipart(form.part("file", part)).send().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_import_transcripts() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    let import_url = format!("{}/api/videos/{}/transcript/import", base_url, video_id);
+
+    client
+        .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+        .json(&json!({ "start_time": 0.0, "end_time": 1.0, "text": "Old draft." }))
+        .send()
+        .await
+        .unwrap();
+
+    // SRT replaces the existing transcript
+    let srt = "1\r\n00:00:01,000 --> 00:00:03,500\r\nGood evening,\r\n<i>everyone.</i>\r\n\r\n2\r\n00:00:04,000 --> 00:00:06,000\r\nLet us sit.\r\n";
+    let response = import(&client, &import_url, "talk.srt", srt, None).await;
+    assert_eq!(response.status(), 200);
+    let blocks: Vec<serde_json::Value> = response.json().await.unwrap();
+    assert_eq!(blocks.len(), 2);
+    let stored = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    let texts: Vec<&str> = stored.iter().map(|b| b.text.as_str()).collect();
+    assert_eq!(texts, vec!["Good evening, everyone.", "Let us sit."]);
+    assert_eq!((stored[0].start_time, stored[0].end_time), (1.0, 3.5));
+
+    // WebVTT, detected from the contents
+    let vtt = "WEBVTT\n\n00:10.000 --> 00:12.000 line:0\n<v Teacher>Breathe in.</v>\n";
+    let response = import(&client, &import_url, "captions", vtt, None).await;
+    assert_eq!(response.status(), 200);
+    let stored = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].text, "Breathe in.");
+
+    // JSON keeps word timings
+    let body = json!({ "blocks": [{
+        "start_time": 2.0,
+        "end_time": 3.0,
+        "text": "Sādhu sādhu",
+        "words": [
+            { "start_time": 2.0, "end_time": 2.5, "text": "Sādhu", "confidence": 0.8 },
+            { "start_time": 2.5, "end_time": 3.0, "text": "sādhu", "confidence": 0.6 }
+        ]
+    }]});
+    let response = import(&client, &import_url, "talk.json", &body.to_string(), Some("json")).await;
+    assert_eq!(response.status(), 200);
+    let stored = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(stored[0].words.len(), 2);
+    assert!((stored[0].confidence.unwrap() - 0.7).abs() < 1e-9);
+
+    // Every bad cue is reported with its line, and nothing is saved
+    let bad = "1\n00:00:01,000 --> 00:00:02,000\nFine\n\n2\n00:00:09,000 --> 00:00:04,000\nBackwards\n\n3\n00:00:xx,000 --> 00:00:12,000\nGarbled\n";
+    let response = import(&client, &import_url, "bad.srt", bad, None).await;
+    assert_eq!(response.status(), 400);
+    let error = response.text().await.unwrap();
+    assert!(error.contains("cue 2 (line 6): end time is before start time"), "{}", error);
+    assert!(error.contains("cue 3 (line 10): invalid start time '00:00:xx,000'"), "{}", error);
+    assert_eq!(state.db.list_transcript_blocks(&video_id).await.unwrap()[0].text, "Sādhu sādhu");
+
+    let response = import(&client, &import_url, "talk.srt", srt, Some("ass")).await;
+    assert_eq!(response.status(), 400);
+
+    // An import is one change that can be undone
+    let response = client
+        .post(format!("{}/api/videos/{}/transcript/undo", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let stored = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(stored[0].text, "Breathe in.");
+
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+    let response = import(&other, &import_url, "talk.srt", srt, None).await;
+    assert_eq!(response.status(), 404);
+
+    println!("✓ SRT, WebVTT and JSON transcripts are imported with per-cue errors");
+}
+
+#[tokio::test]
+async fn test_import_during_and_after_transcription() {
+    let (state, _db_dir, _filestore_dir) =
+        create_test_state_with_transcriber(Arc::new(SlowTranscriber)).await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    seed_audio_chunks(&state, &video_id, 4).await;
+    let import_url = format!("{}/api/videos/{}/transcript/import", base_url, video_id);
+    let transcription_url = format!("{}/api/videos/{}/transcription", base_url, video_id);
+    let srt = "1\r\n00:00:01,000 --> 00:00:03,500\r\nImported.\r\n";
+
+    let response = client.post(&transcription_url).send().await.unwrap();
+    assert_eq!(response.status(), 202);
+
+    // A running job would keep appending to the imported blocks
+    let response = import(&client, &import_url, "talk.srt", srt, None).await;
+    assert_eq!(response.status(), 409);
+
+    for _ in 0..100 {
+        if !state.db.list_transcript_blocks(&video_id).await.unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+    let response = client.delete(&transcription_url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    tokio::time::sleep(tokio::time::Duration::from_millis(800)).await;
+
+    // Once cancelled, the import replaces the partial transcript
+    let response = import(&client, &import_url, "talk.srt", srt, None).await;
+    assert_eq!(response.status(), 200);
+
+    // ...and the cancelled job is no longer resumed on top of it
+    let response = client.post(&transcription_url).send().await.unwrap();
+    assert_eq!(response.status(), 409);
+    let stored = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    let texts: Vec<&str> = stored.iter().map(|b| b.text.as_str()).collect();
+    assert_eq!(texts, vec!["Imported."]);
+
+    println!("✓ Imports wait for transcription and end a cancelled one");
+}
+
+#[tokio::test]
+async fn test_import_rejects_oversized_files() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    let import_url = format!("{}/api/videos/{}/transcript/import", base_url, video_id);
+
+    let contents = "x".repeat(gatha_transcribe::import::MAX_IMPORT_BYTES + 1);
+    let response = import(&client, &import_url, "huge.srt", &contents, None).await;
+    assert_eq!(response.status(), 400);
+    let error = response.text().await.unwrap();
+    assert!(error.contains("Failed to read file"), "{}", error);
+    assert!(state.db.list_transcript_blocks(&video_id).await.unwrap().is_empty());
+
+    println!("✓ Imports are limited to caption-sized files");
+}
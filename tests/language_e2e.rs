@@ -0,0 +1,180 @@
+mod common;
+
+use common::{
+    create_authenticated_client, create_test_state, create_test_state_with_transcriber,
+    seed_audio_chunks, seed_video_for_user, start_test_server,
+};
+use gatha_transcribe::{
+    jobs,
+    transcriber::{MockTranscriber, Result, SegmentStream, Transcriber, TranscriptionRequest},
+    upload::AppState,
+};
+use reqwest::multipart;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+/// Mock transcriber that records the language hint of every request
+#[derive(Default)]
+struct RecordingTranscriber {
+    languages: Mutex<Vec<Option<String>>>,
+}
+
+#[async_trait::async_trait]
+impl Transcriber for RecordingTranscriber {
+    async fn transcribe(&self, request: TranscriptionRequest) -> Result<SegmentStream> {
+        self.languages.lock().unwrap().push(request.language.clone());
+        MockTranscriber::new().transcribe(request).await
+    }
+}
+
+async fn run_job(state: &Arc<AppState>, video_id: &str, email: &str) {
+    let user = state.db.get_user_by_email(email).await.unwrap().unwrap();
+    let job = jobs::start_job(state.clone(), video_id, &user.id).await.unwrap();
+    for _ in 0..100 {
+        let job = state.db.get_transcription_job(&job.id).await.unwrap().unwrap();
+        if job.status.is_finished() {
+            return;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+    panic!("Transcription job {} did not finish", job.id);
+}
+
+#[tokio::test]
+async fn test_language_detected_when_unset() {
+    let transcriber = Arc::new(RecordingTranscriber::default());
+    let (state, _db_dir, _filestore_dir) = create_test_state_with_transcriber(transcriber.clone()).await;
+    let base_url = start_test_server(state.clone()).await;
+    let _client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    seed_audio_chunks(&state, &video_id, 2).await;
+
+    run_job(&state, &video_id, "test@example.com").await;
+
+    // Mock detector hears English; the result is stored and used as the hint for every chunk
+    let video = state.db.get_video(&video_id).await.unwrap().unwrap();
+    assert_eq!(video.language.as_deref(), Some("en"));
+    assert_eq!(
+        *transcriber.languages.lock().unwrap(),
+        vec![Some("en".to_string()), Some("en".to_string())]
+    );
+
+    println!("✓ Language is detected before transcribing a video without one");
+}
+
+#[tokio::test]
+async fn test_language_setting_is_passed_as_hint() {
+    let transcriber = Arc::new(RecordingTranscriber::default());
+    let (state, _db_dir, _filestore_dir) = create_test_state_with_transcriber(transcriber.clone()).await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    seed_audio_chunks(&state, &video_id, 1).await;
+
+    let language_url = format!("{}/api/videos/{}/language", base_url, video_id);
+
+    let response = client.put(&language_url).json(&json!({ "language": "HI" })).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    let video: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(video["language"], "hi");
+
+    let response = client
+        .put(&language_url)
+        .json(&json!({ "language": "Hindi" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    run_job(&state, &video_id, "test@example.com").await;
+
+    // The user's choice wins over detection
+    assert_eq!(*transcriber.languages.lock().unwrap(), vec![Some("hi".to_string())]);
+    let video = state.db.get_video(&video_id).await.unwrap().unwrap();
+    assert_eq!(video.language.as_deref(), Some("hi"));
+
+    // Clearing the language re-enables detection
+    let response = client.put(&language_url).json(&json!({ "language": null })).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    let video = state.db.get_video(&video_id).await.unwrap().unwrap();
+    assert!(video.language.is_none());
+
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+    let response = other.put(&language_url).json(&json!({ "language": "en" })).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+
+    println!("✓ Video language is editable and passed to the engine");
+}
+
+#[tokio::test]
+async fn test_language_set_at_upload() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+
+    // Non-MP4 uploads skip ffmpeg processing
+    let form = multipart::Form::new().text("language", "pi").part(
+        "video",
+        multipart::Part::bytes(vec![0u8; 1024])
+            .file_name("chanting.webm")
+            .mime_str("video/webm")
+            .unwrap(),
+    );
+    let response = client
+        .post(format!("{}/api/videos/upload", base_url))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let json: serde_json::Value = response.json().await.unwrap();
+
+    let video = state.db.get_video(json["id"].as_str().unwrap()).await.unwrap().unwrap();
+    assert_eq!(video.language.as_deref(), Some("pi"));
+
+    let form = multipart::Form::new().text("language", "not a language").part(
+        "video",
+        multipart::Part::bytes(vec![0u8; 16]).file_name("talk.webm"),
+    );
+    let response = client
+        .post(format!("{}/api/videos/upload", base_url))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    // The field order doesn't matter
+    let form = multipart::Form::new()
+        .part(
+            "video",
+            multipart::Part::bytes(vec![0u8; 16]).file_name("vandana.webm"),
+        )
+        .text("language", "HI");
+    let response = client
+        .post(format!("{}/api/videos/upload", base_url))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let json: serde_json::Value = response.json().await.unwrap();
+    let video = state.db.get_video(json["id"].as_str().unwrap()).await.unwrap().unwrap();
+    assert_eq!(video.language.as_deref(), Some("hi"));
+
+    let form = multipart::Form::new()
+        .part(
+            "video",
+            multipart::Part::bytes(vec![0u8; 16]).file_name("late.webm"),
+        )
+        .text("language", "not a language");
+    let response = client
+        .post(format!("{}/api/videos/upload", base_url))
+        .multipart(form)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    println!("✓ Language can be set when uploading");
+}
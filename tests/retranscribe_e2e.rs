@@ -0,0 +1,170 @@
+mod common;
+
+use common::{create_authenticated_client, create_test_state, seed_video_for_user, start_test_server};
+use gatha_transcribe::{db::TranscriptBlock, upload::AppState};
+use serde_json::json;
+
+/// Store ten seconds of generated audio as the video's media file
+async fn seed_video_media(state: &AppState, video_id: &str) {
+    let media = tempfile::Builder::new().suffix(".wav").tempfile().unwrap().into_temp_path();
+    let status = tokio::process::Command::new("ffmpeg")
+        .args(["-hide_banner", "-loglevel", "error", "-f", "lavfi", "-i", "sine=frequency=440:duration=10", "-y"])
+        .arg(&media)
+        .status()
+        .await
+        .expect("ffmpeg is required for this test");
+    assert!(status.success());
+
+    let video = state.db.get_video(video_id).await.unwrap().unwrap();
+    let data = tokio::fs::read(&media).await.unwrap();
+    state
+        .filestore
+        .save_file(&video.file_path, Box::new(std::io::Cursor::new(data)))
+        .await
+        .unwrap();
+}
+
+async fn seed_block(state: &AppState, video_id: &str, start: f64, end: f64, text: &str, ordering: i64) -> String {
+    let block = TranscriptBlock::new(video_id.to_string(), start, end, text.to_string(), ordering);
+    let video = state.db.get_video(video_id).await.unwrap().unwrap();
+    state.db.insert_transcript_block(&block, &video.user_id).await.unwrap();
+    block.id
+}
+
+#[tokio::test]
+async fn test_retranscribe_block_keeps_previous_text() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    seed_video_media(&state, &video_id).await;
+
+    let garbled = seed_block(&state, &video_id, 0.0, 2.0, "Mai all bings", 0).await;
+    let untouched = seed_block(&state, &video_id, 2.0, 4.0, "Correct text", 1).await;
+
+    let response = client
+        .post(format!(
+            "{}/api/videos/{}/transcript/blocks/{}/retranscribe",
+            base_url, video_id, garbled
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let block: serde_json::Value = response.json().await.unwrap();
+
+    // All mock segments land in the only block being retranscribed
+    assert_eq!(block["id"], garbled.as_str());
+    assert!(block["text"].as_str().unwrap().starts_with("May all beings be happy."));
+    assert_eq!(block["previous_text"], "Mai all bings");
+
+    let other = state.db.get_transcript_block(&untouched).await.unwrap().unwrap();
+    assert_eq!(other.text, "Correct text");
+    assert_eq!(other.previous_text, None);
+
+    println!("✓ Retranscribing a block replaces its text and keeps the previous text");
+}
+
+#[tokio::test]
+async fn test_retranscribe_time_range() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    seed_video_media(&state, &video_id).await;
+
+    let first = seed_block(&state, &video_id, 0.0, 2.0, "Untouched", 0).await;
+    seed_block(&state, &video_id, 2.0, 4.0, "Garbled one", 1).await;
+    seed_block(&state, &video_id, 4.0, 6.0, "Garbled two", 2).await;
+
+    // Overlaps the second and third block, so the span 2-6s is retranscribed
+    let response = client
+        .post(format!("{}/api/videos/{}/transcript/retranscribe", base_url, video_id))
+        .json(&json!({ "start_time": 2.5, "end_time": 5.0 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let blocks: Vec<serde_json::Value> = response.json().await.unwrap();
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0]["text"], "May all beings be happy.");
+    assert_eq!(blocks[0]["previous_text"], "Garbled one");
+    assert_eq!(
+        blocks[1]["text"],
+        "May all beings be free from suffering. May all beings be at peace."
+    );
+    assert_eq!(blocks[1]["previous_text"], "Garbled two");
+
+    let first = state.db.get_transcript_block(&first).await.unwrap().unwrap();
+    assert_eq!(first.text, "Untouched");
+
+    // Ranges without blocks and invalid ranges are rejected
+    for range in [json!({ "start_time": 7.0, "end_time": 9.0 }), json!({ "start_time": 3.0, "end_time": 1.0 })] {
+        let response = client
+            .post(format!("{}/api/videos/{}/transcript/retranscribe", base_url, video_id))
+            .json(&range)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400);
+    }
+
+    println!("✓ Retranscribing a time range updates every overlapping block");
+}
+
+#[tokio::test]
+async fn test_retranscribe_scoped_to_video_owner() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    create_authenticated_client(&base_url, "owner@example.com", "Owner").await;
+    let intruder = create_authenticated_client(&base_url, "intruder@example.com", "Intruder").await;
+    let video_id = seed_video_for_user(&state, "owner@example.com").await;
+    let block_id = seed_block(&state, &video_id, 0.0, 2.0, "Private", 0).await;
+
+    let response = intruder
+        .post(format!(
+            "{}/api/videos/{}/transcript/blocks/{}/retranscribe",
+            base_url, video_id, block_id
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    let block = state.db.get_transcript_block(&block_id).await.unwrap().unwrap();
+    assert_eq!(block.text, "Private");
+
+    println!("✓ Retranscribe is scoped to the video owner");
+}
+
+#[tokio::test]
+async fn test_retranscription_does_not_overwrite_edits() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    let block_id = seed_block(&state, &video_id, 0.0, 2.0, "Mai all bings", 0).await;
+    let user = state.db.get_user_by_email("test@example.com").await.unwrap().unwrap();
+
+    // The block is read when retranscription starts and corrected by hand while the engine runs
+    let mut retranscribed = state.db.get_transcript_block(&block_id).await.unwrap().unwrap();
+    retranscribed.text = "May all beings".to_string();
+    let response = client
+        .patch(format!("{}/api/videos/{}/transcript/blocks/{}", base_url, video_id, block_id))
+        .json(&json!({ "text": "May all beings be happy." }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let written = state
+        .db
+        .replace_transcript_block_texts(std::slice::from_ref(&retranscribed), &user.id)
+        .await
+        .unwrap();
+    assert!(!written);
+    let stored = state.db.get_transcript_block(&block_id).await.unwrap().unwrap();
+    assert_eq!(stored.text, "May all beings be happy.");
+
+    println!("✓ Retranscription results don't overwrite edits made while the engine ran");
+}
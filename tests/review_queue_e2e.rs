@@ -0,0 +1,143 @@
+mod common;
+
+use common::{create_authenticated_client, create_test_state, seed_video_for_user, start_test_server};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_review_queue_orders_by_confidence() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+
+    let blocks = [
+        // Confidence derived from words: (0.9 + 0.5) / 2 = 0.7
+        json!({
+            "start_time": 0.0, "end_time": 2.0, "text": "Namo tassa",
+            "words": [
+                { "start_time": 0.0, "end_time": 1.0, "text": "Namo", "confidence": 0.9 },
+                { "start_time": 1.0, "end_time": 2.0, "text": "tassa", "confidence": 0.5 }
+            ]
+        }),
+        json!({ "start_time": 2.0, "end_time": 4.0, "text": "bhagavato", "confidence": 0.3 }),
+        json!({ "start_time": 4.0, "end_time": 6.0, "text": "arahato", "confidence": 0.95 }),
+        // No confidence at all: never queued
+        json!({ "start_time": 6.0, "end_time": 8.0, "text": "sammasambuddhassa" }),
+    ];
+    let mut block_ids = Vec::new();
+    for block in blocks {
+        let response = client
+            .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+            .json(&block)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 201);
+        let block: serde_json::Value = response.json().await.unwrap();
+        block_ids.push(block["id"].as_str().unwrap().to_string());
+    }
+
+    let queue_url = format!("{}/api/videos/{}/review-queue", base_url, video_id);
+    let response = client.get(&queue_url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    let queue: Vec<serde_json::Value> = response.json().await.unwrap();
+    let ids: Vec<&str> = queue.iter().map(|b| b["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, vec![block_ids[1].as_str(), block_ids[0].as_str()]);
+    assert!((queue[1]["confidence"].as_f64().unwrap() - 0.7).abs() < 1e-9);
+
+    // Marking a block reviewed takes it out of the queue
+    let response = client
+        .post(format!("{}/{}", queue_url, block_ids[1]))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let block: serde_json::Value = response.json().await.unwrap();
+    assert!(block["reviewed_at"].is_string());
+    // The mark is a change to the block, so concurrent replacements see it
+    assert_eq!(block["updated_at"], block["reviewed_at"]);
+    let stored = state.db.get_transcript_block(&block_ids[1]).await.unwrap().unwrap();
+    assert_eq!(stored.updated_at, stored.reviewed_at.unwrap());
+
+    let queue: Vec<serde_json::Value> = client.get(&queue_url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue[0]["id"], block_ids[0].as_str());
+
+    let queue: Vec<serde_json::Value> = client
+        .get(format!("{}?include_reviewed=true&max_confidence=1.0", queue_url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(queue.len(), 3);
+
+    // Clearing the mark puts it back
+    let response = client
+        .delete(format!("{}/{}", queue_url, block_ids[1]))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let queue: Vec<serde_json::Value> = client
+        .get(format!("{}?limit=1", queue_url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue[0]["id"], block_ids[1].as_str());
+
+    println!("✓ Review queue lists the least confident blocks first");
+}
+
+#[tokio::test]
+async fn test_review_queue_scoped_to_video_owner() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let owner = create_authenticated_client(&base_url, "owner@example.com", "Owner").await;
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+    let video_id = seed_video_for_user(&state, "owner@example.com").await;
+
+    let response = owner
+        .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+        .json(&json!({ "start_time": 0.0, "end_time": 1.0, "text": "Private", "confidence": 0.2 }))
+        .send()
+        .await
+        .unwrap();
+    let block: serde_json::Value = response.json().await.unwrap();
+    let block_id = block["id"].as_str().unwrap();
+
+    let response = other
+        .get(format!("{}/api/videos/{}/review-queue", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    let response = other
+        .post(format!("{}/api/videos/{}/review-queue/{}", base_url, video_id, block_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    // Confidence outside 0..=1 is rejected
+    let response = owner
+        .patch(format!("{}/api/videos/{}/transcript/blocks/{}", base_url, video_id, block_id))
+        .json(&json!({ "confidence": -0.1 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    let block = state.db.get_transcript_block(block_id).await.unwrap().unwrap();
+    assert_eq!(block.confidence, Some(0.2));
+    assert!(block.reviewed_at.is_none());
+
+    println!("✓ Review queue access is scoped to the video owner");
+}
@@ -0,0 +1,207 @@
+mod common;
+
+use common::{add_block, create_authenticated_client, create_test_state, seed_video_for_user, start_test_server};
+use serde_json::json;
+
+async fn get_json(client: &reqwest::Client, url: String) -> serde_json::Value {
+    let response = client.get(url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_history_and_restore() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    let user = state.db.get_user_by_email("test@example.com").await.unwrap().unwrap();
+
+    let block_id = add_block(
+        &client,
+        &base_url,
+        &video_id,
+        json!({ "start_time": 0.0, "end_time": 5.0, "text": "First draft." }),
+    )
+    .await;
+    let block_url = format!("{}/api/videos/{}/transcript/blocks/{}", base_url, video_id, block_id);
+    client.patch(&block_url).json(&json!({ "text": "Second draft." })).send().await.unwrap();
+    client.patch(&block_url).json(&json!({ "text": "Third draft." })).send().await.unwrap();
+
+    // Video history, newest first, with author and action
+    let history = get_json(&client, format!("{}/api/videos/{}/transcript/revisions", base_url, video_id)).await;
+    let history = history.as_array().unwrap();
+    assert_eq!(history.len(), 3);
+    let actions: Vec<&str> = history.iter().map(|r| r["action"].as_str().unwrap()).collect();
+    assert_eq!(actions, vec!["update", "update", "create"]);
+    assert!(history.iter().all(|r| r["author_id"] == user.id.as_str()));
+    assert_eq!(history[0]["text"], "Third draft.");
+
+    // Paging
+    let before = history[0]["id"].as_i64().unwrap();
+    let page = get_json(
+        &client,
+        format!("{}/api/videos/{}/transcript/revisions?before={}&limit=1", base_url, video_id, before),
+    )
+    .await;
+    assert_eq!(page.as_array().unwrap().len(), 1);
+    assert_eq!(page[0]["text"], "Second draft.");
+
+    // Restore the first version
+    let versions = get_json(&client, format!("{}/revisions", block_url)).await;
+    let versions = versions.as_array().unwrap();
+    assert_eq!(versions.len(), 3);
+    let first_id = versions[2]["id"].as_i64().unwrap();
+    let response = client
+        .post(format!(
+            "{}/api/videos/{}/transcript/revisions/{}/restore",
+            base_url, video_id, first_id
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let block: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(block["text"], "First draft.");
+    let versions = get_json(&client, format!("{}/revisions", block_url)).await;
+    assert_eq!(versions[0]["action"], "restore");
+
+    // A deleted block keeps its history and can be brought back
+    let response = client.delete(&block_url).send().await.unwrap();
+    assert_eq!(response.status(), 204);
+    assert!(state.db.get_transcript_block(&block_id).await.unwrap().is_none());
+    let versions = get_json(&client, format!("{}/revisions", block_url)).await;
+    assert_eq!(versions[0]["action"], "delete");
+    assert_eq!(versions[0]["deleted"], true);
+    let second_id = versions
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["text"] == "Second draft.")
+        .unwrap()["id"]
+        .as_i64()
+        .unwrap();
+    let response = client
+        .post(format!(
+            "{}/api/videos/{}/transcript/revisions/{}/restore",
+            base_url, video_id, second_id
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let stored = state.db.get_transcript_block(&block_id).await.unwrap().unwrap();
+    assert_eq!(stored.text, "Second draft.");
+
+    // Other users can't see or restore the history
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+    let response = other
+        .get(format!("{}/api/videos/{}/transcript/revisions", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+    let response = other
+        .post(format!(
+            "{}/api/videos/{}/transcript/revisions/{}/restore",
+            base_url, video_id, first_id
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    // Revisions of another video aren't reachable through this one
+    let own_other_video = seed_video_for_user(&state, "test@example.com").await;
+    let response = client
+        .post(format!(
+            "{}/api/videos/{}/transcript/revisions/{}/restore",
+            base_url, own_other_video, first_id
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    println!("✓ Block history is recorded and old versions can be restored");
+}
+
+#[tokio::test]
+async fn test_undo() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    let undo_url = format!("{}/api/videos/{}/transcript/undo", base_url, video_id);
+
+    let first = add_block(
+        &client,
+        &base_url,
+        &video_id,
+        json!({ "start_time": 0.0, "end_time": 5.0, "text": "Ajahn Cha spoke." }),
+    )
+    .await;
+    let second = add_block(
+        &client,
+        &base_url,
+        &video_id,
+        json!({ "start_time": 5.0, "end_time": 10.0, "text": "Cha smiled." }),
+    )
+    .await;
+    client
+        .patch(format!("{}/api/videos/{}/transcript/blocks/{}", base_url, video_id, second))
+        .json(&json!({ "text": "Cha laughed." }))
+        .send()
+        .await
+        .unwrap();
+
+    // One find-and-replace touching two blocks is undone in one step
+    let response = client
+        .post(format!("{}/api/transcripts/replace", base_url))
+        .json(&json!({ "video_ids": [video_id], "find": "Cha", "replace": "Chah", "mode": "whole-word" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let response = client.post(&undo_url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    let undone: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(undone.as_array().unwrap().len(), 2);
+    assert!(undone.as_array().unwrap().iter().all(|r| r["action"] == "undo"));
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks[0].text, "Ajahn Cha spoke.");
+    assert_eq!(blocks[1].text, "Cha laughed.");
+
+    // Undoing again steps further back: the edit, then the second block's creation
+    client.post(&undo_url).send().await.unwrap();
+    let stored = state.db.get_transcript_block(&second).await.unwrap().unwrap();
+    assert_eq!(stored.text, "Cha smiled.");
+
+    client.post(&undo_url).send().await.unwrap();
+    assert!(state.db.get_transcript_block(&second).await.unwrap().is_none());
+    assert!(state.db.get_transcript_block(&first).await.unwrap().is_some());
+
+    // Undoing a delete brings the block back
+    client
+        .delete(format!("{}/api/videos/{}/transcript/blocks/{}", base_url, video_id, first))
+        .send()
+        .await
+        .unwrap();
+    client.post(&undo_url).send().await.unwrap();
+    let stored = state.db.get_transcript_block(&first).await.unwrap().unwrap();
+    assert_eq!(stored.text, "Ajahn Cha spoke.");
+
+    // Only the first creation is left, then nothing
+    let response = client.post(&undo_url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(state.db.list_transcript_blocks(&video_id).await.unwrap().is_empty());
+    let response = client.post(&undo_url).send().await.unwrap();
+    assert_eq!(response.status(), 409);
+
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+    let response = other.post(&undo_url).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+
+    println!("✓ Transcript changes are undone one operation at a time");
+}
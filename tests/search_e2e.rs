@@ -0,0 +1,162 @@
+mod common;
+
+use common::{add_block, create_authenticated_client, create_test_state, seed_video_for_user, start_test_server};
+use serde_json::json;
+
+async fn search(client: &reqwest::Client, base_url: &str, q: &str) -> Vec<serde_json::Value> {
+    let response = client
+        .get(format!("{}/api/search", base_url))
+        .query(&[("q", q)])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_search_across_videos() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+
+    let first = seed_video_for_user(&state, "test@example.com").await;
+    let second = seed_video_for_user(&state, "test@example.com").await;
+    let others = seed_video_for_user(&state, "other@example.com").await;
+
+    add_block(
+        &client,
+        &base_url,
+        &first,
+        json!({ "start_time": 0.0, "end_time": 5.0, "text": "Today we begin with the breath." }),
+    )
+    .await;
+    let block_id = add_block(
+        &client,
+        &base_url,
+        &first,
+        json!({ "start_time": 62.5, "end_time": 67.5, "text": "These are the four foundations of mindfulness." }),
+    )
+    .await;
+    add_block(
+        &client,
+        &base_url,
+        &second,
+        json!({ "start_time": 10.0, "end_time": 15.0, "text": "Sāriputta asked about the <four> noble truths." }),
+    )
+    .await;
+    add_block(
+        &other,
+        &base_url,
+        &others,
+        json!({ "start_time": 0.0, "end_time": 5.0, "text": "The four foundations, again." }),
+    )
+    .await;
+
+    let hits = search(&client, &base_url, "four foundations").await;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0]["video_id"], first);
+    assert_eq!(hits[0]["block_id"], block_id);
+    assert_eq!(hits[0]["start_time"], 62.5);
+    assert_eq!(hits[0]["video_filename"], "test_video.mp4");
+    assert_eq!(
+        hits[0]["snippet"],
+        "These are the <mark>four</mark> <mark>foundations</mark> of mindfulness."
+    );
+
+    // Both of the user's videos, and only theirs
+    let hits = search(&client, &base_url, "four").await;
+    assert_eq!(hits.len(), 2);
+    let snippets: Vec<&str> = hits.iter().map(|h| h["snippet"].as_str().unwrap()).collect();
+    assert!(snippets.contains(&"Sāriputta asked about the &lt;<mark>four</mark>&gt; noble truths."));
+
+    // Diacritics are optional
+    let hits = search(&client, &base_url, "sariputta").await;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0]["video_id"], second);
+
+    // Phrases must appear as written
+    assert!(search(&client, &base_url, "\"foundations four\"").await.is_empty());
+
+    // The index follows edits and deletes
+    client
+        .patch(format!("{}/api/videos/{}/transcript/blocks/{}", base_url, first, block_id))
+        .json(&json!({ "text": "The four frames of reference." }))
+        .send()
+        .await
+        .unwrap();
+    assert!(search(&client, &base_url, "foundations").await.is_empty());
+    assert_eq!(search(&client, &base_url, "frames").await.len(), 1);
+
+    client
+        .delete(format!("{}/api/videos/{}/transcript/blocks/{}", base_url, first, block_id))
+        .send()
+        .await
+        .unwrap();
+    assert!(search(&client, &base_url, "frames").await.is_empty());
+
+    let response = client
+        .get(format!("{}/api/search", base_url))
+        .query(&[("q", " \" ")])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    println!("✓ Transcripts are searchable across a user's videos");
+}
+
+#[tokio::test]
+async fn test_search_index_survives_vacuum_and_rebuild() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+
+    let first = add_block(
+        &client,
+        &base_url,
+        &video_id,
+        json!({ "start_time": 0.0, "end_time": 5.0, "text": "Today we begin with the breath." }),
+    )
+    .await;
+    add_block(
+        &client,
+        &base_url,
+        &video_id,
+        json!({ "start_time": 5.0, "end_time": 10.0, "text": "The four foundations of mindfulness." }),
+    )
+    .await;
+    let last = add_block(
+        &client,
+        &base_url,
+        &video_id,
+        json!({ "start_time": 10.0, "end_time": 15.0, "text": "The four noble truths." }),
+    )
+    .await;
+    client
+        .delete(format!("{}/api/videos/{}/transcript/blocks/{}", base_url, video_id, first))
+        .send()
+        .await
+        .unwrap();
+
+    // VACUUM may renumber implicit rowids; hits must still point at the right block
+    sqlx::query("VACUUM").execute(state.db.pool()).await.unwrap();
+    let hits = search(&client, &base_url, "truths").await;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0]["block_id"], last);
+
+    // An index that has drifted from the blocks can be rebuilt from them
+    sqlx::query("INSERT INTO transcript_blocks_fts (transcript_blocks_fts) VALUES ('delete-all')")
+        .execute(state.db.pool())
+        .await
+        .unwrap();
+    assert!(search(&client, &base_url, "truths").await.is_empty());
+    state.db.rebuild_transcript_search().await.unwrap();
+    let hits = search(&client, &base_url, "truths").await;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0]["block_id"], last);
+
+    println!("✓ Search hits stay attached to their blocks across VACUUM and rebuilds");
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use chrono::Utc;
+use common::{create_authenticated_client, create_test_state, seed_video_for_user, start_test_server};
+use gatha_transcribe::db::{SilenceAnalysis, SilenceInterval};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_get_stored_silences() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+
+    // Never analyzed: empty map with default parameters
+    let response = client
+        .get(format!("{}/api/videos/{}/silences", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert!(json["analyzed_at"].is_null());
+    assert_eq!(json["silences"].as_array().unwrap().len(), 0);
+
+    // Store an analysis as the upload pipeline would
+    let analysis = SilenceAnalysis {
+        video_id: video_id.clone(),
+        noise_db: -35.0,
+        min_duration: 0.8,
+        analyzed_at: Utc::now(),
+    };
+    let intervals = vec![
+        SilenceInterval { start_time: 12.0, end_time: 14.5 },
+        SilenceInterval { start_time: 0.0, end_time: 1.5 },
+    ];
+    state.db.replace_silences(&analysis, &intervals).await.unwrap();
+
+    let response = client
+        .get(format!("{}/api/videos/{}/silences", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(json["noise_db"], -35.0);
+    assert_eq!(json["min_duration"], 0.8);
+    let silences = json["silences"].as_array().unwrap();
+    assert_eq!(silences.len(), 2);
+    assert_eq!(silences[0]["start_time"], 0.0);
+    assert_eq!(silences[1]["end_time"], 14.5);
+
+    // Replacing the analysis drops the previous intervals
+    state
+        .db
+        .replace_silences(&analysis, &intervals[..1])
+        .await
+        .unwrap();
+    assert_eq!(state.db.list_silences(&video_id).await.unwrap().len(), 1);
+
+    println!("✓ Stored silence map is served per video");
+}
+
+#[tokio::test]
+async fn test_silences_rejects_invalid_params_and_other_users() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let owner = create_authenticated_client(&base_url, "owner@example.com", "Owner").await;
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+    let video_id = seed_video_for_user(&state, "owner@example.com").await;
+
+    let silences_url = format!("{}/api/videos/{}/silences", base_url, video_id);
+
+    let response = owner.post(&silences_url).json(&json!({ "noise_db": 10.0 })).send().await.unwrap();
+    assert_eq!(response.status(), 400);
+
+    let response = owner.post(&silences_url).json(&json!({ "min_duration": 0.0 })).send().await.unwrap();
+    assert_eq!(response.status(), 400);
+
+    // Parameters on a GET don't trigger an analysis
+    let response = owner.get(format!("{}?noise_db=10", silences_url)).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(state.db.get_silence_analysis(&video_id).await.unwrap().is_none());
+
+    let response = other.get(&silences_url).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+    let response = other.post(&silences_url).json(&json!({})).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+
+    println!("✓ Silence endpoint validates parameters and ownership");
+}
@@ -0,0 +1,365 @@
+mod common;
+
+use common::{
+    add_block, create_authenticated_client, create_test_state, create_test_state_with_transcriber, seed_audio_chunks,
+    start_test_server,
+};
+use gatha_transcribe::{
+    db::Video,
+    jobs,
+    transcriber::{MockTranscriber, SegmentStream, Transcriber, TranscriptionRequest},
+    upload::AppState,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+/// Mock transcriber that takes a while per chunk, so diarization can finish mid-job
+struct SlowTranscriber;
+
+#[async_trait::async_trait]
+impl Transcriber for SlowTranscriber {
+    async fn transcribe(
+        &self,
+        request: TranscriptionRequest,
+    ) -> gatha_transcribe::transcriber::Result<SegmentStream> {
+        tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+        MockTranscriber::new().transcribe(request).await
+    }
+}
+
+/// Store a 90s video for a registered user (the mock diarizer only needs the duration)
+async fn seed_timed_video(state: &AppState, email: &str) -> String {
+    let user = state.db.get_user_by_email(email).await.unwrap().unwrap();
+    let mut video = Video::new("talk.mp4".to_string(), "talk.mp4".to_string(), user.id);
+    video.file_path = format!("{}.mp4", video.id);
+    video.duration_seconds = Some(90.0);
+
+    state
+        .filestore
+        .save_file(&video.file_path, Box::new(&b"not really a video"[..]))
+        .await
+        .unwrap();
+    state.db.insert_video(&video).await.unwrap();
+    video.id
+}
+
+#[tokio::test]
+async fn test_diarization_assigns_and_merges_speakers() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_timed_video(&state, "test@example.com").await;
+
+    let mut block_ids = Vec::new();
+    for (start, end) in [(5.0, 10.0), (40.0, 45.0), (70.0, 75.0)] {
+        let response = client
+            .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+            .json(&json!({ "start_time": start, "end_time": end, "text": "..." }))
+            .send()
+            .await
+            .unwrap();
+        let block: serde_json::Value = response.json().await.unwrap();
+        block_ids.push(block["id"].as_str().unwrap().to_string());
+    }
+
+    // Mock diarizer alternates two voices every 30 seconds
+    let response = client
+        .post(format!("{}/api/videos/{}/speakers/diarize", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let speakers: Vec<serde_json::Value> = response.json().await.unwrap();
+    assert_eq!(speakers.len(), 2);
+    assert_eq!(speakers[0]["name"], "Speaker 1");
+    assert_ne!(speakers[0]["color"], speakers[1]["color"]);
+    let first = speakers[0]["id"].as_str().unwrap().to_string();
+    let second = speakers[1]["id"].as_str().unwrap().to_string();
+
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks[0].speaker_id.as_deref(), Some(first.as_str()));
+    assert_eq!(blocks[1].speaker_id.as_deref(), Some(second.as_str()));
+    assert_eq!(blocks[2].speaker_id.as_deref(), Some(first.as_str()));
+
+    // Rename
+    let response = client
+        .patch(format!("{}/api/videos/{}/speakers/{}", base_url, video_id, first))
+        .json(&json!({ "name": "Ajahn Chah" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let speaker: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(speaker["name"], "Ajahn Chah");
+
+    // Merge the second voice into the first
+    let response = client
+        .post(format!("{}/api/videos/{}/speakers/{}/merge", base_url, video_id, first))
+        .json(&json!({ "speaker_ids": [second] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let speakers = state.db.list_speakers(&video_id).await.unwrap();
+    assert_eq!(speakers.len(), 1);
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert!(blocks.iter().all(|b| b.speaker_id.as_deref() == Some(first.as_str())));
+
+    // Bulk reassign to a new questioner
+    let response = client
+        .post(format!("{}/api/videos/{}/speakers", base_url, video_id))
+        .json(&json!({ "name": "Questioner" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let questioner: serde_json::Value = response.json().await.unwrap();
+    let questioner_id = questioner["id"].as_str().unwrap();
+
+    let response = client
+        .post(format!("{}/api/videos/{}/speakers/assign", base_url, video_id))
+        .json(&json!({ "block_ids": [block_ids[1], block_ids[2]], "speaker_id": questioner_id }))
+        .send()
+        .await
+        .unwrap();
+    let result: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(result["updated"], 2);
+
+    // Deleting a speaker unassigns its blocks
+    let response = client
+        .delete(format!("{}/api/videos/{}/speakers/{}", base_url, video_id, questioner_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks[0].speaker_id.as_deref(), Some(first.as_str()));
+    assert!(blocks[1].speaker_id.is_none());
+    assert!(blocks[2].speaker_id.is_none());
+
+    println!("✓ Speakers can be diarized, renamed, merged and reassigned");
+}
+
+#[tokio::test]
+async fn test_transcription_uses_diarized_speakers() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_timed_video(&state, "test@example.com").await;
+    seed_audio_chunks(&state, &video_id, 2).await;
+
+    let response = client
+        .post(format!("{}/api/videos/{}/speakers/diarize", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    let speakers: Vec<serde_json::Value> = response.json().await.unwrap();
+
+    let user = state.db.get_user_by_email("test@example.com").await.unwrap().unwrap();
+    let job = jobs::start_job(state.clone(), &video_id, &user.id).await.unwrap();
+    for _ in 0..100 {
+        let job = state.db.get_transcription_job(&job.id).await.unwrap().unwrap();
+        if job.status.is_finished() {
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+
+    // First chunk's blocks (1-7s) fall in the first turn, second chunk's (46-52s) in the second
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks.len(), 6);
+    assert_eq!(blocks[0].speaker_id.as_deref(), speakers[0]["id"].as_str());
+    assert_eq!(blocks[3].speaker_id.as_deref(), speakers[1]["id"].as_str());
+
+    // Another user's speakers are off limits
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+    let response = other
+        .get(format!("{}/api/videos/{}/speakers", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    println!("✓ Transcribed blocks are labelled with diarized speakers");
+}
+
+#[tokio::test]
+async fn test_rediarization_is_one_revision_batch() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_timed_video(&state, "test@example.com").await;
+
+    for (start, end) in [(5.0, 10.0), (40.0, 45.0), (70.0, 75.0)] {
+        client
+            .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+            .json(&json!({ "start_time": start, "end_time": end, "text": "..." }))
+            .send()
+            .await
+            .unwrap();
+    }
+    for _ in 0..2 {
+        let response = client
+            .post(format!("{}/api/videos/{}/speakers/diarize", base_url, video_id))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+    }
+
+    // Each diarization re-assigns every block in one batch, never passing through unassigned
+    let history: Vec<serde_json::Value> = client
+        .get(format!("{}/api/videos/{}/transcript/revisions", base_url, video_id))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let updates: Vec<&serde_json::Value> = history.iter().filter(|r| r["action"] == "update").collect();
+    assert_eq!(updates.len(), 6);
+    assert!(updates.iter().all(|r| r["speaker_id"].is_string()));
+    assert!(updates[..3].iter().all(|r| r["batch_id"] == updates[0]["batch_id"]));
+
+    println!("✓ Re-running diarization re-assigns speakers in a single revision batch");
+}
+
+#[tokio::test]
+async fn test_job_picks_up_diarization_that_finishes_mid_run() {
+    let (state, _db_dir, _filestore_dir) = create_test_state_with_transcriber(Arc::new(SlowTranscriber)).await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_timed_video(&state, "test@example.com").await;
+    seed_audio_chunks(&state, &video_id, 2).await;
+
+    // The job starts before any speaker turns exist
+    let user = state.db.get_user_by_email("test@example.com").await.unwrap().unwrap();
+    let job = jobs::start_job(state.clone(), &video_id, &user.id).await.unwrap();
+    let response = client
+        .post(format!("{}/api/videos/{}/speakers/diarize", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    for _ in 0..100 {
+        let job = state.db.get_transcription_job(&job.id).await.unwrap().unwrap();
+        if job.status.is_finished() {
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks.len(), 6);
+    assert!(blocks.iter().all(|b| b.speaker_id.is_some()));
+
+    println!("✓ Blocks transcribed after diarization finishes get a speaker");
+}
+
+#[tokio::test]
+async fn test_rediarization_keeps_hand_edited_speakers() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_timed_video(&state, "test@example.com").await;
+    let diarize_url = format!("{}/api/videos/{}/speakers/diarize", base_url, video_id);
+    let speakers_url = format!("{}/api/videos/{}/speakers", base_url, video_id);
+    let ids = |speakers: &[gatha_transcribe::db::Speaker]| -> Vec<String> {
+        speakers.iter().map(|s| s.id.clone()).collect()
+    };
+
+    let response = client.post(&diarize_url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    // Diarized speakers alone can be replaced
+    let response = client.post(&diarize_url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    let response = client
+        .post(&speakers_url)
+        .json(&json!({ "name": "Questioner", "color": "#123456" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let before = state.db.list_speakers(&video_id).await.unwrap();
+    assert_eq!(before.len(), 3);
+
+    // A hand-made speaker would be lost, so diarization is refused and nothing changes
+    let response = client.post(&diarize_url).send().await.unwrap();
+    assert_eq!(response.status(), 409);
+    let after = state.db.list_speakers(&video_id).await.unwrap();
+    assert_eq!(ids(&after), ids(&before));
+
+    // Deleting every speaker starts over
+    for speaker in &before {
+        let response = client
+            .delete(format!("{}/{}", speakers_url, speaker.id))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 204);
+    }
+    let response = client.post(&diarize_url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    // Clearing a block's speaker counts as a hand edit, so the block stays unlabelled
+    let block_id = add_block(
+        &client,
+        &base_url,
+        &video_id,
+        json!({ "start_time": 5.0, "end_time": 10.0, "text": "Evaṃ me sutaṃ." }),
+    )
+    .await;
+    client.post(&diarize_url).send().await.unwrap();
+    let response = client
+        .post(format!("{}/assign", speakers_url))
+        .json(&json!({ "block_ids": [block_id], "speaker_id": null }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let response = client.post(&diarize_url).send().await.unwrap();
+    assert_eq!(response.status(), 409);
+    let block = state.db.get_transcript_block(&block_id).await.unwrap().unwrap();
+    assert!(block.speaker_id.is_none());
+
+    // So does deleting one of the diarized speakers
+    let before = state.db.list_speakers(&video_id).await.unwrap();
+    for speaker in &before {
+        client.delete(format!("{}/{}", speakers_url, speaker.id)).send().await.unwrap();
+    }
+    client.post(&diarize_url).send().await.unwrap();
+    let before = state.db.list_speakers(&video_id).await.unwrap();
+    let response = client
+        .delete(format!("{}/{}", speakers_url, before[1].id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+    let response = client.post(&diarize_url).send().await.unwrap();
+    assert_eq!(response.status(), 409);
+    let after = state.db.list_speakers(&video_id).await.unwrap();
+    assert_eq!(ids(&after), ids(&before[..1]));
+
+    // And renaming one
+    client.delete(format!("{}/{}", speakers_url, before[0].id)).send().await.unwrap();
+    client.post(&diarize_url).send().await.unwrap();
+    let before = state.db.list_speakers(&video_id).await.unwrap();
+    let response = client
+        .patch(format!("{}/{}", speakers_url, before[0].id))
+        .json(&json!({ "name": "Ajahn Chah" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let response = client.post(&diarize_url).send().await.unwrap();
+    assert_eq!(response.status(), 409);
+    let speaker = state.db.get_speaker(&before[0].id).await.unwrap().unwrap();
+    assert_eq!(speaker.name, "Ajahn Chah");
+
+    println!("✓ Re-running diarization never replaces hand-edited speakers");
+}
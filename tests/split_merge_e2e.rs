@@ -0,0 +1,193 @@
+mod common;
+
+use common::{add_block, create_authenticated_client, create_test_state, seed_video_for_user, start_test_server};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_split_and_merge_blocks() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+
+    let block_id = add_block(
+        &client,
+        &base_url,
+        &video_id,
+        json!({
+            "start_time": 0.0,
+            "end_time": 4.0,
+            "text": "Sabbe sattā. Bhavantu sukhitattā.",
+            "words": [
+                { "start_time": 0.0, "end_time": 0.7, "text": "Sabbe", "confidence": 0.9 },
+                { "start_time": 0.7, "end_time": 1.6, "text": "sattā.", "confidence": 0.5 },
+                { "start_time": 2.0, "end_time": 2.8, "text": "Bhavantu", "confidence": 0.8 },
+                { "start_time": 2.8, "end_time": 4.0, "text": "sukhitattā.", "confidence": 0.6 }
+            ]
+        }),
+    )
+    .await;
+    let last_id = add_block(
+        &client,
+        &base_url,
+        &video_id,
+        json!({ "start_time": 4.0, "end_time": 6.0, "text": "Sādhu." }),
+    )
+    .await;
+
+    // Split after "sattā." (a character offset, not a byte offset); the time comes from the words
+    let response = client
+        .post(format!("{}/api/videos/{}/transcript/blocks/{}/split", base_url, video_id, block_id))
+        .json(&json!({ "offset": 12 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let halves: Vec<serde_json::Value> = response.json().await.unwrap();
+    assert_eq!(halves[0]["id"], block_id);
+    assert_eq!(halves[0]["text"], "Sabbe sattā.");
+    assert_eq!(halves[0]["end_time"], 2.0);
+    assert_eq!(halves[0]["words"].as_array().unwrap().len(), 2);
+    assert_eq!(halves[1]["text"], "Bhavantu sukhitattā.");
+    assert_eq!(halves[1]["start_time"], 2.0);
+    assert_eq!(halves[1]["end_time"], 4.0);
+    assert_eq!(halves[1]["words"][1]["end_time"], 4.0);
+
+    // The new block sits between the halves' neighbours
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    let texts: Vec<&str> = blocks.iter().map(|b| b.text.as_str()).collect();
+    assert_eq!(texts, vec!["Sabbe sattā.", "Bhavantu sukhitattā.", "Sādhu."]);
+    let second_id = blocks[1].id.clone();
+
+    // Invalid splits change nothing
+    let split_url = format!("{}/api/videos/{}/transcript/blocks/{}/split", base_url, video_id, last_id);
+    let response = client.post(&split_url).json(&json!({ "offset": 0, "time": 5.0 })).send().await.unwrap();
+    assert_eq!(response.status(), 400);
+    let response = client.post(&split_url).json(&json!({ "offset": 3, "time": 9.0 })).send().await.unwrap();
+    assert_eq!(response.status(), 400);
+    // No words to take the time from
+    let response = client.post(&split_url).json(&json!({ "offset": 3 })).send().await.unwrap();
+    assert_eq!(response.status(), 400);
+
+    // Merging requires adjacent blocks
+    let merge_url = format!("{}/api/videos/{}/transcript/blocks/merge", base_url, video_id);
+    let response = client
+        .post(&merge_url)
+        .json(&json!({ "block_ids": [block_id, last_id] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    let response = client.post(&merge_url).json(&json!({ "block_ids": [block_id] })).send().await.unwrap();
+    assert_eq!(response.status(), 400);
+
+    // Merge all three, in any order
+    let response = client
+        .post(&merge_url)
+        .json(&json!({ "block_ids": [last_id, second_id, block_id] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let merged: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(merged["id"], block_id);
+    assert_eq!(merged["text"], "Sabbe sattā. Bhavantu sukhitattā. Sādhu.");
+    assert_eq!(merged["start_time"], 0.0);
+    assert_eq!(merged["end_time"], 6.0);
+    assert_eq!(merged["words"].as_array().unwrap().len(), 4);
+    assert_eq!(merged["words"][2]["start_time"], 2.0);
+    assert!((merged["confidence"].as_f64().unwrap() - 0.7).abs() < 1e-9);
+
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks.len(), 1);
+
+    // The merge is one change, undone at once
+    let response = client
+        .post(format!("{}/api/videos/{}/transcript/undo", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    let texts: Vec<&str> = blocks.iter().map(|b| b.text.as_str()).collect();
+    assert_eq!(texts, vec!["Sabbe sattā.", "Bhavantu sukhitattā.", "Sādhu."]);
+
+    // Other users can't split or merge
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+    let response = other
+        .post(format!("{}/api/videos/{}/transcript/blocks/{}/split", base_url, video_id, block_id))
+        .json(&json!({ "offset": 3, "time": 1.0 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+    let response = other
+        .post(&merge_url)
+        .json(&json!({ "block_ids": [block_id, second_id] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    println!("✓ Blocks are split and merged atomically with their word timings");
+}
+
+#[tokio::test]
+async fn test_restore_keeps_positions_shifted_by_splits() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+
+    let first_id = add_block(
+        &client,
+        &base_url,
+        &video_id,
+        json!({ "start_time": 0.0, "end_time": 4.0, "text": "One two. Three four." }),
+    )
+    .await;
+    let last_id = add_block(
+        &client,
+        &base_url,
+        &video_id,
+        json!({ "start_time": 4.0, "end_time": 6.0, "text": "Five." }),
+    )
+    .await;
+    let last_url = format!("{}/api/videos/{}/transcript/blocks/{}", base_url, video_id, last_id);
+    client.patch(&last_url).json(&json!({ "text": "Five!" })).send().await.unwrap();
+    let versions: Vec<serde_json::Value> =
+        client.get(format!("{}/revisions", last_url)).send().await.unwrap().json().await.unwrap();
+    let original = versions.last().unwrap()["id"].as_i64().unwrap();
+
+    // The split moves the last block down a place without recording it
+    let response = client
+        .post(format!("{}/api/videos/{}/transcript/blocks/{}/split", base_url, video_id, first_id))
+        .json(&json!({ "offset": 9, "time": 2.0 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let halves: Vec<serde_json::Value> = response.json().await.unwrap();
+    let second_half = halves[1]["id"].as_str().unwrap().to_string();
+
+    // Restoring the last block's first version must not move it back in front of the new block
+    let response = client
+        .post(format!("{}/api/videos/{}/transcript/revisions/{}/restore", base_url, video_id, original))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let response = client
+        .post(format!("{}/api/videos/{}/transcript/blocks/{}/split", base_url, video_id, second_half))
+        .json(&json!({ "offset": 6, "time": 3.0 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    let texts: Vec<&str> = blocks.iter().map(|b| b.text.as_str()).collect();
+    assert_eq!(texts, vec!["One two.", "Three", "four.", "Five."]);
+
+    println!("✓ Restored blocks keep the position later splits gave them");
+}
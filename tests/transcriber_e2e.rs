@@ -0,0 +1,145 @@
+mod common;
+
+use common::{
+    create_authenticated_client, create_test_state, create_test_state_with_transcriber,
+    seed_audio_chunks, seed_video_for_user, start_test_server,
+};
+use futures_util::stream;
+use gatha_transcribe::transcriber::{
+    SegmentStream, Transcriber, TranscriptSegment, TranscriptionRequest,
+};
+use reqwest::Client;
+use std::sync::{Arc, Mutex};
+
+/// Backend that echoes each request back as a single segment and records it
+#[derive(Default)]
+struct RecordingTranscriber {
+    requests: Mutex<Vec<TranscriptionRequest>>,
+}
+
+#[async_trait::async_trait]
+impl Transcriber for RecordingTranscriber {
+    async fn transcribe(
+        &self,
+        request: TranscriptionRequest,
+    ) -> gatha_transcribe::transcriber::Result<SegmentStream> {
+        let segment = TranscriptSegment {
+            start: request.time_offset,
+            end: request.time_offset + 5.0,
+            text: format!("Chunk at {}", request.time_offset),
+            words: Vec::new(),
+        };
+        self.requests.lock().unwrap().push(request);
+        Ok(Box::pin(stream::iter(vec![Ok(segment)])))
+    }
+}
+
+/// Start a transcription over the API and return the transcript once the job is done
+async fn transcribe_video(client: &Client, base_url: &str, video_id: &str) -> Vec<serde_json::Value> {
+    let response = client
+        .post(format!("{}/api/videos/{}/transcription", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 202);
+
+    for _ in 0..100 {
+        let status: serde_json::Value = client
+            .get(format!("{}/api/videos/{}/transcription", base_url, video_id))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        match status["status"].as_str() {
+            Some("done") => {
+                return client
+                    .get(format!("{}/api/videos/{}/transcript", base_url, video_id))
+                    .send()
+                    .await
+                    .unwrap()
+                    .json()
+                    .await
+                    .unwrap();
+            }
+            Some("failed") => panic!("Transcription failed: {}", status["error"]),
+            _ => tokio::time::sleep(tokio::time::Duration::from_millis(50)).await,
+        }
+    }
+    panic!("Transcription did not finish");
+}
+
+#[tokio::test]
+async fn test_mock_transcriber_drives_the_pipeline() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let first = seed_video_for_user(&state, "test@example.com").await;
+    let second = seed_video_for_user(&state, "test@example.com").await;
+    seed_audio_chunks(&state, &first, 2).await;
+    seed_audio_chunks(&state, &second, 2).await;
+
+    let blocks = transcribe_video(&client, &base_url, &first).await;
+
+    // Canned text, placed on the timeline at each chunk's trimmed start (1s and 46s)
+    let texts: Vec<&str> = blocks.iter().map(|b| b["text"].as_str().unwrap()).collect();
+    assert_eq!(
+        texts,
+        vec![
+            "May all beings be happy.",
+            "May all beings be free from suffering.",
+            "May all beings be at peace.",
+            "May all beings be happy.",
+            "May all beings be free from suffering.",
+            "May all beings be at peace.",
+        ]
+    );
+    let starts: Vec<f64> = blocks.iter().map(|b| b["start_time"].as_f64().unwrap()).collect();
+    assert_eq!(starts, vec![1.0, 3.0, 5.0, 46.0, 48.0, 50.0]);
+    assert_eq!(blocks[0]["words"].as_array().unwrap().len(), 5);
+
+    // The same audio layout gives the same transcript
+    let again = transcribe_video(&client, &base_url, &second).await;
+    let strip = |blocks: &[serde_json::Value]| -> Vec<(String, f64, f64)> {
+        blocks
+            .iter()
+            .map(|b| {
+                (
+                    b["text"].as_str().unwrap().to_string(),
+                    b["start_time"].as_f64().unwrap(),
+                    b["end_time"].as_f64().unwrap(),
+                )
+            })
+            .collect()
+    };
+    assert_eq!(strip(&blocks), strip(&again));
+
+    println!("✓ Mock transcriber produces a deterministic transcript through the API");
+}
+
+#[tokio::test]
+async fn test_transcriber_backend_is_pluggable() {
+    let transcriber = Arc::new(RecordingTranscriber::default());
+    let (state, _db_dir, _filestore_dir) = create_test_state_with_transcriber(transcriber.clone()).await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    seed_audio_chunks(&state, &video_id, 3).await;
+    state.db.update_video_language(&video_id, Some("pi")).await.unwrap();
+
+    let blocks = transcribe_video(&client, &base_url, &video_id).await;
+
+    let texts: Vec<&str> = blocks.iter().map(|b| b["text"].as_str().unwrap()).collect();
+    assert_eq!(texts, vec!["Chunk at 1", "Chunk at 46", "Chunk at 91"]);
+
+    // Each chunk was handed over with its offset, its audio on disk and the language hint
+    let requests = transcriber.requests.lock().unwrap();
+    assert_eq!(requests.len(), 3);
+    let offsets: Vec<f64> = requests.iter().map(|r| r.time_offset).collect();
+    assert_eq!(offsets, vec![1.0, 46.0, 91.0]);
+    assert!(requests.iter().all(|r| r.language.as_deref() == Some("pi")));
+    assert!(requests.iter().all(|r| r.audio_path.extension().is_some_and(|e| e == "wav")));
+
+    println!("✓ Transcription runs through whichever backend is in AppState");
+}
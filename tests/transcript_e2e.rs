@@ -0,0 +1,142 @@
+mod common;
+
+use common::{create_authenticated_client, create_test_state, seed_video_for_user, start_test_server};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_transcript_block_crud() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+
+    // Empty transcript to begin with
+    let response = client
+        .get(format!("{}/api/videos/{}/transcript", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let blocks: Vec<serde_json::Value> = response.json().await.unwrap();
+    assert!(blocks.is_empty());
+
+    // Create two blocks, second one appended after the first
+    let mut block_ids = Vec::new();
+    for (start, end, text) in [(0.0, 4.5, "First block"), (5.0, 9.0, "Second block")] {
+        let response = client
+            .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+            .json(&json!({ "start_time": start, "end_time": end, "text": text }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 201);
+        let block: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(block["text"], text);
+        block_ids.push(block["id"].as_str().unwrap().to_string());
+    }
+
+    // Edit the first block's text
+    let response = client
+        .patch(format!(
+            "{}/api/videos/{}/transcript/blocks/{}",
+            base_url, video_id, block_ids[0]
+        ))
+        .json(&json!({ "text": "Edited first block" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    // Edits survive a fresh fetch (the reason the transcript lives server-side)
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0].text, "Edited first block");
+    assert_eq!(blocks[0].start_time, 0.0);
+    assert_eq!(blocks[1].text, "Second block");
+    assert!(blocks[0].ordering < blocks[1].ordering);
+
+    // Delete the second block
+    let response = client
+        .delete(format!(
+            "{}/api/videos/{}/transcript/blocks/{}",
+            base_url, video_id, block_ids[1]
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+
+    let response = client
+        .get(format!("{}/api/videos/{}/transcript", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    let blocks: Vec<serde_json::Value> = response.json().await.unwrap();
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0]["id"], block_ids[0].as_str());
+
+    println!("✓ Transcript blocks can be created, edited, listed and deleted");
+}
+
+#[tokio::test]
+async fn test_transcript_scoped_to_video_owner() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let owner = create_authenticated_client(&base_url, "owner@example.com", "Owner").await;
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+    let video_id = seed_video_for_user(&state, "owner@example.com").await;
+
+    let response = owner
+        .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+        .json(&json!({ "start_time": 1.0, "end_time": 2.0, "text": "Private" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let block: serde_json::Value = response.json().await.unwrap();
+    let block_id = block["id"].as_str().unwrap();
+
+    // Another user can't see or modify the owner's transcript
+    let response = other
+        .get(format!("{}/api/videos/{}/transcript", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    let response = other
+        .delete(format!(
+            "{}/api/videos/{}/transcript/blocks/{}",
+            base_url, video_id, block_id
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    // Unauthenticated requests are rejected
+    let response = reqwest::Client::new()
+        .get(format!("{}/api/videos/{}/transcript", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+
+    // Invalid timing is rejected
+    let response = owner
+        .patch(format!(
+            "{}/api/videos/{}/transcript/blocks/{}",
+            base_url, video_id, block_id
+        ))
+        .json(&json!({ "end_time": 0.5 }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].end_time, 2.0);
+
+    println!("✓ Transcript access is scoped to the video owner");
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use common::{create_authenticated_client, create_test_state, seed_video_for_user, start_test_server};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_block_words_round_trip() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+
+    let response = client
+        .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+        .json(&json!({
+            "start_time": 0.0,
+            "end_time": 3.0,
+            "text": "Namo tassa bhagavato",
+            "words": [
+                { "start_time": 0.0, "end_time": 0.8, "text": "Namo", "confidence": 0.92 },
+                { "start_time": 0.8, "end_time": 1.5, "text": "tassa", "confidence": 0.41 },
+                { "start_time": 1.5, "end_time": 3.0, "text": "bhagavato", "confidence": null }
+            ]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let block: serde_json::Value = response.json().await.unwrap();
+    let block_id = block["id"].as_str().unwrap().to_string();
+
+    // Words come back with the block for playback highlighting
+    let response = client
+        .get(format!("{}/api/videos/{}/transcript", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    let blocks: Vec<serde_json::Value> = response.json().await.unwrap();
+    let words = blocks[0]["words"].as_array().unwrap();
+    assert_eq!(words.len(), 3);
+    assert_eq!(words[1]["text"], "tassa");
+    assert_eq!(words[1]["start_time"], 0.8);
+    assert_eq!(words[1]["confidence"], 0.41);
+    assert!(words[2]["confidence"].is_null());
+
+    // A block without words has an empty list
+    let response = client
+        .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+        .json(&json!({ "start_time": 4.0, "end_time": 5.0, "text": "arahato" }))
+        .send()
+        .await
+        .unwrap();
+    let block: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(block["words"], json!([]));
+
+    // Confidence outside 0..=1 is rejected
+    let response = client
+        .patch(format!(
+            "{}/api/videos/{}/transcript/blocks/{}",
+            base_url, video_id, block_id
+        ))
+        .json(&json!({
+            "words": [{ "start_time": 0.0, "end_time": 0.8, "text": "Namo", "confidence": 1.5 }]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    let stored = state.db.get_transcript_block(&block_id).await.unwrap().unwrap();
+    assert_eq!(stored.words.len(), 3);
+
+    println!("✓ Word timings and confidence are stored with transcript blocks");
+}
+
+#[tokio::test]
+async fn test_low_confidence_words_query() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+
+    let mut block_ids = Vec::new();
+    for (start, words) in [
+        (0.0, json!([
+            { "start_time": 0.0, "end_time": 1.0, "text": "Sabbe", "confidence": 0.9 },
+            { "start_time": 1.0, "end_time": 2.0, "text": "satta", "confidence": 0.3 }
+        ])),
+        (2.0, json!([
+            { "start_time": 2.0, "end_time": 3.0, "text": "bhavantu", "confidence": 0.55 },
+            { "start_time": 3.0, "end_time": 4.0, "text": "sukhitatta", "confidence": null }
+        ])),
+    ] {
+        let response = client
+            .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+            .json(&json!({ "start_time": start, "end_time": start + 2.0, "text": "", "words": words }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 201);
+        let block: serde_json::Value = response.json().await.unwrap();
+        block_ids.push(block["id"].as_str().unwrap().to_string());
+    }
+
+    // Default threshold is 0.6, least confident first, unscored words left out
+    let response = client
+        .get(format!("{}/api/videos/{}/transcript/words", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let words: Vec<serde_json::Value> = response.json().await.unwrap();
+    assert_eq!(words.len(), 2);
+    assert_eq!(words[0]["text"], "satta");
+    assert_eq!(words[0]["block_id"], block_ids[0].as_str());
+    assert_eq!(words[0]["word_index"], 1);
+    assert_eq!(words[1]["text"], "bhavantu");
+    assert_eq!(words[1]["start_time"], 2.0);
+
+    let response = client
+        .get(format!(
+            "{}/api/videos/{}/transcript/words?max_confidence=0.5",
+            base_url, video_id
+        ))
+        .send()
+        .await
+        .unwrap();
+    let words: Vec<serde_json::Value> = response.json().await.unwrap();
+    assert_eq!(words.len(), 1);
+
+    // Scoped to the video owner
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+    let response = other
+        .get(format!("{}/api/videos/{}/transcript/words", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    println!("✓ Low-confidence words can be queried for review");
+}
@@ -0,0 +1,231 @@
+mod common;
+
+use common::{
+    create_authenticated_client, create_test_state, create_test_state_with_transcriber,
+    seed_audio_chunks, seed_video_for_user, start_test_server,
+};
+use gatha_transcribe::transcriber::{
+    MockTranscriber, SegmentStream, Transcriber, TranscriptionRequest,
+};
+use reqwest::Client;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Mock transcriber that takes a while per chunk, so jobs can be cancelled mid-run
+struct SlowTranscriber;
+
+#[async_trait::async_trait]
+impl Transcriber for SlowTranscriber {
+    async fn transcribe(
+        &self,
+        request: TranscriptionRequest,
+    ) -> gatha_transcribe::transcriber::Result<SegmentStream> {
+        tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+        MockTranscriber::new().transcribe(request).await
+    }
+}
+
+/// Poll the status endpoint until the condition holds
+async fn wait_for_status(
+    client: &Client,
+    base_url: &str,
+    video_id: &str,
+    condition: impl Fn(&serde_json::Value) -> bool,
+) -> serde_json::Value {
+    for _ in 0..100 {
+        let status: serde_json::Value = client
+            .get(format!("{}/api/videos/{}/transcription", base_url, video_id))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        if condition(&status) {
+            return status;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+    panic!("Transcription status condition not reached");
+}
+
+#[tokio::test]
+async fn test_start_transcription_and_poll_status() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    seed_audio_chunks(&state, &video_id, 3).await;
+
+    // No job yet
+    let response = client
+        .get(format!("{}/api/videos/{}/transcription", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    let response = client
+        .post(format!("{}/api/videos/{}/transcription", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 202);
+    let job: serde_json::Value = response.json().await.unwrap();
+    assert!(job["job_id"].is_string());
+
+    let status = wait_for_status(&client, &base_url, &video_id, |s| s["status"] == "done").await;
+    assert_eq!(status["chunks_total"], 3);
+    assert_eq!(status["chunks_completed"], 3);
+    assert_eq!(status["job_id"], job["job_id"]);
+
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks.len(), 9);
+
+    println!("✓ Transcription can be started and its progress polled");
+}
+
+#[tokio::test]
+async fn test_cancel_keeps_completed_blocks_editable() {
+    let (state, _db_dir, _filestore_dir) =
+        create_test_state_with_transcriber(Arc::new(SlowTranscriber)).await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    seed_audio_chunks(&state, &video_id, 5).await;
+
+    let response = client
+        .post(format!("{}/api/videos/{}/transcription", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 202);
+
+    // A second start while running is rejected
+    let response = client
+        .post(format!("{}/api/videos/{}/transcription", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    wait_for_status(&client, &base_url, &video_id, |s| {
+        s["chunks_completed"].as_i64().unwrap() >= 1
+    })
+    .await;
+
+    let response = client
+        .delete(format!("{}/api/videos/{}/transcription", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let status: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(status["status"], "cancelled");
+
+    // Give the runner time to notice; it must not finish the remaining chunks
+    tokio::time::sleep(tokio::time::Duration::from_millis(800)).await;
+    let status = wait_for_status(&client, &base_url, &video_id, |_| true).await;
+    assert_eq!(status["status"], "cancelled");
+    let completed = status["chunks_completed"].as_i64().unwrap();
+    assert!((1..5).contains(&completed), "completed = {}", completed);
+
+    // Completed blocks are kept and can still be edited
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks.len() as i64, completed * 3);
+
+    let response = client
+        .patch(format!(
+            "{}/api/videos/{}/transcript/blocks/{}",
+            base_url, video_id, blocks[0].id
+        ))
+        .json(&json!({ "text": "Corrected after cancel" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    // A finished job can't be cancelled again
+    let response = client
+        .delete(format!("{}/api/videos/{}/transcription", base_url, video_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    println!("✓ Cancelled transcription keeps completed blocks editable");
+}
+
+#[tokio::test]
+async fn test_transcription_runs_once_per_video() {
+    let (state, _db_dir, _filestore_dir) =
+        create_test_state_with_transcriber(Arc::new(SlowTranscriber)).await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    seed_audio_chunks(&state, &video_id, 2).await;
+    let transcription_url = format!("{}/api/videos/{}/transcription", base_url, video_id);
+
+    // Parallel starts race for the same video; only one of them gets a job
+    let (first, second) = tokio::join!(
+        client.post(&transcription_url).send(),
+        client.post(&transcription_url).send()
+    );
+    let mut statuses = vec![first.unwrap().status(), second.unwrap().status()];
+    statuses.sort();
+    assert_eq!(statuses, vec![202, 409]);
+
+    wait_for_status(&client, &base_url, &video_id, |s| s["status"] == "done").await;
+    assert_eq!(state.db.list_transcript_blocks(&video_id).await.unwrap().len(), 6);
+
+    // Running again would duplicate the transcript
+    let response = client.post(&transcription_url).send().await.unwrap();
+    assert_eq!(response.status(), 409);
+    assert_eq!(state.db.list_transcript_blocks(&video_id).await.unwrap().len(), 6);
+
+    println!("✓ A video is transcribed by at most one job, and only once");
+}
+
+#[tokio::test]
+async fn test_start_after_cancel_resumes_unfinished_chunks() {
+    let (state, _db_dir, _filestore_dir) =
+        create_test_state_with_transcriber(Arc::new(SlowTranscriber)).await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    seed_audio_chunks(&state, &video_id, 4).await;
+    let transcription_url = format!("{}/api/videos/{}/transcription", base_url, video_id);
+
+    let response = client.post(&transcription_url).send().await.unwrap();
+    assert_eq!(response.status(), 202);
+    wait_for_status(&client, &base_url, &video_id, |s| {
+        s["chunks_completed"].as_i64().unwrap() >= 1
+    })
+    .await;
+    let response = client.delete(&transcription_url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    // Let the runner stop, then start again
+    tokio::time::sleep(tokio::time::Duration::from_millis(800)).await;
+    let response = client.post(&transcription_url).send().await.unwrap();
+    assert_eq!(response.status(), 202);
+    let job: serde_json::Value = response.json().await.unwrap();
+
+    let status = wait_for_status(&client, &base_url, &video_id, |s| s["status"] == "done").await;
+    assert_eq!(status["job_id"], job["job_id"]);
+    assert_eq!(status["chunks_total"], 4);
+    assert_eq!(status["chunks_completed"], 4);
+
+    // Chunks from the cancelled run aren't transcribed twice
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks.len(), 12);
+    let mut starts: Vec<f64> = blocks.iter().map(|b| b.start_time).collect();
+    starts.dedup();
+    assert_eq!(starts.len(), 12);
+
+    // Once done, starting again is refused
+    let response = client.post(&transcription_url).send().await.unwrap();
+    assert_eq!(response.status(), 409);
+
+    println!("✓ Starting after a cancel resumes from the unfinished chunks");
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use common::{create_test_state, seed_audio_chunks};
+use gatha_transcribe::{
+    db::{ChunkStatus, JobStatus, TranscriptBlock, TranscriptionJob},
+    jobs,
+    test_data,
+    upload::AppState,
+};
+
+/// Seed a user and a video with three extracted audio chunks
+async fn seed_chunked_video(state: &AppState) -> (String, String) {
+    let user = test_data::seed_test_user(&state.db).await.unwrap();
+    let video = test_data::seed_test_videos(&state.db, &user.id, &["talk.mp4"])
+        .await
+        .unwrap()
+        .remove(0);
+    seed_audio_chunks(state, &video.id, 3).await;
+
+    (user.id, video.id)
+}
+
+/// Poll until the job stops running
+async fn wait_for_job(state: &AppState, job_id: &str) -> TranscriptionJob {
+    for _ in 0..100 {
+        let job = state.db.get_transcription_job(job_id).await.unwrap().unwrap();
+        if job.status.is_finished() {
+            return job;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+    panic!("Transcription job {} did not finish", job_id);
+}
+
+#[tokio::test]
+async fn test_job_transcribes_every_chunk() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let (user_id, video_id) = seed_chunked_video(&state).await;
+
+    let job = jobs::start_job(state.clone(), &video_id, &user_id).await.unwrap();
+    let job = wait_for_job(&state, &job.id).await;
+    assert_eq!(job.status, JobStatus::Done, "Job failed: {:?}", job.error);
+
+    let chunks = state.db.list_job_chunks(&job.id).await.unwrap();
+    assert_eq!(chunks.len(), 3);
+    assert!(chunks.iter().all(|c| c.status == ChunkStatus::Done));
+
+    // Mock transcriber emits three segments per chunk, offset to the trimmed chunk start
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks.len(), 9);
+    assert_eq!(blocks[0].start_time, 1.0);
+    assert_eq!(blocks[3].start_time, 46.0);
+    assert!(blocks.windows(2).all(|w| w[0].start_time < w[1].start_time));
+
+    // Engine word timings are stored with each block
+    assert_eq!(blocks[0].words.len(), 5);
+    assert_eq!(blocks[0].words[0].start_time, 1.0);
+    assert!(blocks[0].words.iter().all(|w| w.confidence.is_some()));
+
+    println!("✓ Transcription job persists blocks for every chunk");
+}
+
+#[tokio::test]
+async fn test_interrupted_job_resumes_from_first_unfinished_chunk() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let (user_id, video_id) = seed_chunked_video(&state).await;
+
+    // Simulate a server that crashed after finishing chunk 0 of a running job
+    let job = TranscriptionJob::new(video_id.clone(), user_id);
+    state.db.insert_transcription_job(&job).await.unwrap();
+    state
+        .db
+        .update_transcription_job_status(&job.id, JobStatus::Running, None)
+        .await
+        .unwrap();
+    state.db.insert_job_chunks(&job.id, &[0, 1, 2], &[]).await.unwrap();
+    let first_chunk_block = TranscriptBlock::new(
+        video_id.clone(),
+        1.0,
+        3.0,
+        "Transcribed before the crash".to_string(),
+        0,
+    );
+    state
+        .db
+        .complete_job_chunk(&job.id, 0, vec![first_chunk_block], &job.user_id)
+        .await
+        .unwrap();
+
+    // Server restart picks the job back up
+    let resumed = jobs::resume_unfinished_jobs(state.clone()).await.unwrap();
+    assert_eq!(resumed, 1);
+
+    let job = wait_for_job(&state, &job.id).await;
+    assert_eq!(job.status, JobStatus::Done, "Job failed: {:?}", job.error);
+
+    // Chunk 0 was not transcribed again; chunks 1 and 2 added three blocks each
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks.len(), 7);
+    assert_eq!(blocks[0].text, "Transcribed before the crash");
+    assert_eq!(blocks[1].start_time, 46.0);
+
+    // Finished jobs are not resumed again
+    assert_eq!(jobs::resume_unfinished_jobs(state.clone()).await.unwrap(), 0);
+
+    println!("✓ Interrupted job resumes from the first unfinished chunk");
+}
+
+#[tokio::test]
+async fn test_finished_job_status_is_not_overwritten() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let (user_id, video_id) = seed_chunked_video(&state).await;
+
+    // A cancel that lands before the runner marks the job running
+    let job = TranscriptionJob::new(video_id.clone(), user_id);
+    state.db.insert_transcription_job(&job).await.unwrap();
+    jobs::cancel_job(&state, &job.id).await.unwrap();
+
+    let updated = state
+        .db
+        .update_transcription_job_status(&job.id, JobStatus::Running, None)
+        .await
+        .unwrap();
+    assert!(!updated);
+    let updated = state
+        .db
+        .update_transcription_job_status(&job.id, JobStatus::Failed, Some("late failure"))
+        .await
+        .unwrap();
+    assert!(!updated);
+
+    let stored = state.db.get_transcription_job(&job.id).await.unwrap().unwrap();
+    assert_eq!(stored.status, JobStatus::Cancelled);
+    assert!(stored.error.is_none());
+
+    // Cancelling again is a conflict, and nothing is left to resume on restart
+    assert!(jobs::cancel_job(&state, &job.id).await.is_err());
+    assert_eq!(jobs::resume_unfinished_jobs(state.clone()).await.unwrap(), 0);
+    assert!(state.db.list_transcript_blocks(&video_id).await.unwrap().is_empty());
+
+    println!("✓ Cancelled jobs stay cancelled");
+}
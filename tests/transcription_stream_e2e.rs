@@ -0,0 +1,176 @@
+mod common;
+
+use common::{create_test_state, seed_audio_chunks, seed_video_for_user, start_test_server};
+use futures_util::stream::{SplitStream, StreamExt};
+use gatha_transcribe::{jobs, messages::ServerMessage};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    connect_async, tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream,
+};
+
+type SocketReader = SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>;
+
+/// Register a user and return the auth cookie
+async fn register(base_url: &str, email: &str) -> String {
+    let response = reqwest::Client::new()
+        .post(format!("{}/api/auth/register", base_url))
+        .json(&serde_json::json!({
+            "name": "Test User",
+            "email": email,
+            "password": "password123"
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    response
+        .headers()
+        .get("set-cookie")
+        .expect("No Set-Cookie header in registration response")
+        .to_str()
+        .unwrap()
+        .split(';')
+        .next()
+        .unwrap()
+        .to_string()
+}
+
+/// Connect to a video's WebSocket and consume the initial VideoMetadata and StateSync
+async fn connect(base_url: &str, cookie: &str, video_id: &str) -> SocketReader {
+    let url = format!("{}/ws/{}", base_url.replace("http://", "ws://"), video_id);
+    let mut request =
+        tokio_tungstenite::tungstenite::client::IntoClientRequest::into_client_request(&url).unwrap();
+    request.headers_mut().insert("Cookie", cookie.parse().unwrap());
+
+    let (stream, _) = connect_async(request).await.unwrap();
+    let (_write, mut read) = stream.split();
+
+    for expected in ["VideoMetadata", "StateSync"] {
+        let message = next_message(&mut read).await.expect("Timeout waiting for initial sync");
+        assert_eq!(message["type"], expected);
+    }
+
+    read
+}
+
+/// Read the next server message, or None if nothing arrives within a second
+async fn next_message(read: &mut SocketReader) -> Option<serde_json::Value> {
+    let message = tokio::time::timeout(tokio::time::Duration::from_secs(1), read.next())
+        .await
+        .ok()?
+        .expect("WebSocket closed")
+        .expect("WebSocket error");
+
+    match message {
+        Message::Text(text) => Some(serde_json::from_str(&text).unwrap()),
+        other => panic!("Unexpected WebSocket message: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_events_reach_every_socket_of_the_video() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let cookie = register(&base_url, "test@example.com").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    let other_video_id = seed_video_for_user(&state, "test@example.com").await;
+
+    let mut first = connect(&base_url, &cookie, &video_id).await;
+    let mut second = connect(&base_url, &cookie, &video_id).await;
+    let mut other = connect(&base_url, &cookie, &other_video_id).await;
+
+    // Fake event source standing in for a transcription task
+    state.events.publish(
+        &video_id,
+        ServerMessage::ChunkStarted {
+            chunk_index: 2,
+            start: 90.0,
+            end: 135.0,
+        },
+    );
+
+    for read in [&mut first, &mut second] {
+        let message = next_message(read).await.expect("Event not forwarded");
+        assert_eq!(message["type"], "ChunkStarted");
+        assert_eq!(message["chunk_index"], 2);
+        assert_eq!(message["start"], 90.0);
+        assert_eq!(message["end"], 135.0);
+    }
+
+    assert!(
+        next_message(&mut other).await.is_none(),
+        "Sockets of other videos must not receive the event"
+    );
+
+    println!("✓ Transcription events reach every socket connected to the video");
+}
+
+#[tokio::test]
+async fn test_job_streams_text_as_it_arrives() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let cookie = register(&base_url, "test@example.com").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    seed_audio_chunks(&state, &video_id, 2).await;
+    let user = state.db.get_user_by_email("test@example.com").await.unwrap().unwrap();
+
+    let mut read = connect(&base_url, &cookie, &video_id).await;
+
+    jobs::start_job(state.clone(), &video_id, &user.id).await.unwrap();
+
+    let mut streamed_block_ids = Vec::new();
+    for chunk_index in 0..2 {
+        let started = next_message(&mut read).await.expect("No ChunkStarted");
+        assert_eq!(started["type"], "ChunkStarted");
+        assert_eq!(started["chunk_index"], chunk_index);
+        assert_eq!(started["start"], chunk_index as f64 * 45.0 + 1.0);
+
+        // The mock transcriber emits three segments per chunk
+        for _ in 0..3 {
+            let delta = next_message(&mut read).await.expect("No BlockTextDelta");
+            assert_eq!(delta["type"], "BlockTextDelta");
+            assert_eq!(delta["chunk_index"], chunk_index);
+            assert!(!delta["text"].as_str().unwrap().is_empty());
+            streamed_block_ids.push(delta["block_id"].as_str().unwrap().to_string());
+        }
+
+        let completed = next_message(&mut read).await.expect("No ChunkCompleted");
+        assert_eq!(completed["type"], "ChunkCompleted");
+        assert_eq!(completed["chunk_index"], chunk_index);
+        assert_eq!(completed["chunks_completed"], chunk_index + 1);
+        assert_eq!(completed["chunks_total"], 2);
+    }
+
+    // Streamed block IDs are the IDs of the stored blocks
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    let stored_block_ids: Vec<String> = blocks.into_iter().map(|b| b.id).collect();
+    assert_eq!(streamed_block_ids, stored_block_ids);
+
+    println!("✓ Transcription job streams block text over the WebSocket");
+}
+
+#[tokio::test]
+async fn test_socket_of_another_users_video_is_rejected() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    register(&base_url, "owner@example.com").await;
+    let intruder = register(&base_url, "intruder@example.com").await;
+    let video_id = seed_video_for_user(&state, "owner@example.com").await;
+
+    let url = format!("{}/ws/{}", base_url.replace("http://", "ws://"), video_id);
+    let mut request =
+        tokio_tungstenite::tungstenite::client::IntoClientRequest::into_client_request(&url).unwrap();
+    request.headers_mut().insert("Cookie", intruder.parse().unwrap());
+
+    // The upgrade is refused like any other request for a video the user doesn't own
+    match connect_async(request).await {
+        Err(tokio_tungstenite::tungstenite::Error::Http(response)) => {
+            assert_eq!(response.status(), 404);
+        }
+        Err(e) => panic!("Unexpected WebSocket error: {}", e),
+        Ok(_) => panic!("Another user's socket must not be accepted"),
+    }
+
+    println!("✓ WebSocket rejects users who don't own the video");
+}
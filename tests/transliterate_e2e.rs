@@ -0,0 +1,174 @@
+mod common;
+
+use common::{create_authenticated_client, create_test_state, seed_video_for_user, start_test_server};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_transliterate_text() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+
+    let response = client
+        .post(format!("{}/api/transliterate", base_url))
+        .json(&json!({ "text": "namo buddhAya, zAntiH", "from": "harvard-kyoto", "to": "iast" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let result: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(result["text"], "namo buddhāya, śāntiḥ");
+
+    let response = client
+        .post(format!("{}/api/transliterate", base_url))
+        .json(&json!({ "text": "Śāntiḥ", "from": "iast", "to": "ascii" }))
+        .send()
+        .await
+        .unwrap();
+    let result: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(result["text"], "Shantih");
+
+    let response = client
+        .post(format!("{}/api/transliterate", base_url))
+        .json(&json!({ "text": "om", "from": "devanagari", "to": "iast" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 422);
+
+    println!("✓ Text converts between romanization schemes");
+}
+
+#[tokio::test]
+async fn test_transliterate_transcript() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    let blocks_url = format!("{}/api/videos/{}/transcript/blocks", base_url, video_id);
+
+    let mut block_ids = Vec::new();
+    for (start, text, word) in [
+        (0.0, "Namo tassa bhagavato", "bhagavato"),
+        (5.0, "arahato sammaasambuddhassa", "sammaasambuddhassa"),
+    ] {
+        let response = client
+            .post(&blocks_url)
+            .json(&json!({
+                "start_time": start,
+                "end_time": start + 4.0,
+                "text": text,
+                "words": [{ "start_time": start + 1.0, "end_time": start + 2.0, "text": word }]
+            }))
+            .send()
+            .await
+            .unwrap();
+        let block: serde_json::Value = response.json().await.unwrap();
+        block_ids.push(block["id"].as_str().unwrap().to_string());
+    }
+
+    let response = client
+        .post(format!("{}/api/videos/{}/transcript/transliterate", base_url, video_id))
+        // A repeated block is converted once
+        .json(&json!({ "from": "velthuis", "to": "iast", "block_ids": [block_ids[0], block_ids[1], block_ids[1]] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    // Only the block that changed is returned, and previous_text is untouched
+    let changed: Vec<serde_json::Value> = response.json().await.unwrap();
+    assert_eq!(changed.len(), 1);
+    assert_eq!(changed[0]["id"], block_ids[1]);
+    assert_eq!(changed[0]["text"], "arahato sammāsambuddhassa");
+    assert!(changed[0]["previous_text"].is_null());
+    assert_eq!(changed[0]["words"][0]["text"], "sammāsambuddhassa");
+
+    // Selected blocks only
+    let response = client
+        .post(format!("{}/api/videos/{}/transcript/transliterate", base_url, video_id))
+        .json(&json!({ "from": "iast", "to": "ascii", "block_ids": [block_ids[0]] }))
+        .send()
+        .await
+        .unwrap();
+    let changed: Vec<serde_json::Value> = response.json().await.unwrap();
+    assert!(changed.is_empty());
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks[1].text, "arahato sammāsambuddhassa");
+
+    let other = create_authenticated_client(&base_url, "other@example.com", "Other").await;
+    let response = other
+        .post(format!("{}/api/videos/{}/transcript/transliterate", base_url, video_id))
+        .json(&json!({ "from": "iast", "to": "ascii" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    println!("✓ Stored transcript text can be transliterated in bulk");
+}
+
+#[tokio::test]
+async fn test_transliterate_transcript_leaves_english_alone() {
+    let (state, _db_dir, _filestore_dir) = create_test_state().await;
+    let base_url = start_test_server(state.clone()).await;
+    let client = create_authenticated_client(&base_url, "test@example.com", "Test User").await;
+    let video_id = seed_video_for_user(&state, "test@example.com").await;
+    let transliterate_url = format!("{}/api/videos/{}/transcript/transliterate", base_url, video_id);
+
+    let mut block_ids = Vec::new();
+    for (start, text) in [
+        (0.0, "I said The Dhamma is for everyone."),
+        (5.0, "arahato sammaasambuddhassa"),
+        (10.0, "Aaron left at 5 p.m."),
+    ] {
+        let response = client
+            .post(format!("{}/api/videos/{}/transcript/blocks", base_url, video_id))
+            .json(&json!({ "start_time": start, "end_time": start + 4.0, "text": text }))
+            .send()
+            .await
+            .unwrap();
+        let block: serde_json::Value = response.json().await.unwrap();
+        block_ids.push(block["id"].as_str().unwrap().to_string());
+    }
+
+    // Reading these schemes over the whole transcript would also convert the English
+    for from in ["harvard-kyoto", "velthuis"] {
+        let response = client
+            .post(&transliterate_url)
+            .json(&json!({ "from": from, "to": "iast" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400);
+    }
+
+    let response = client
+        .post(&transliterate_url)
+        .json(&json!({ "from": "velthuis", "to": "iast", "block_ids": [block_ids[1]] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    let texts: Vec<&str> = blocks.iter().map(|b| b.text.as_str()).collect();
+    assert_eq!(
+        texts,
+        vec!["I said The Dhamma is for everyone.", "arahato sammāsambuddhassa", "Aaron left at 5 p.m."]
+    );
+
+    // Writing only touches words with diacritics
+    let response = client
+        .post(&transliterate_url)
+        .json(&json!({ "from": "iast", "to": "harvard-kyoto" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let blocks = state.db.list_transcript_blocks(&video_id).await.unwrap();
+    assert_eq!(blocks[0].text, "I said The Dhamma is for everyone.");
+    assert_eq!(blocks[1].text, "arahato sammAsambuddhassa");
+
+    println!("✓ Bulk transliteration leaves English around the Pali alone");
+}